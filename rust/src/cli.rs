@@ -1,4 +1,5 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -15,21 +16,113 @@ pub struct Cli {
         help = "Print the awww command used for transitions"
     )]
     pub debug_awww: bool,
+    #[arg(
+        long,
+        global = true,
+        help = "Print the awww transition command without running it"
+    )]
+    pub print_awww_cmd: bool,
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value_t = ColorChoice::Auto,
+        value_name = "WHEN",
+        help = "Colorize output: auto (TTY only, honors NO_COLOR), always, or never"
+    )]
+    pub color: ColorChoice,
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Load this config file instead of the user and local config files"
+    )]
+    pub config: Option<PathBuf>,
+    #[arg(
+        long,
+        global = true,
+        help = "Ignore ./.theme-manager.toml in the current directory (or set THEME_MANAGER_NO_LOCAL_CONFIG=1)"
+    )]
+    pub no_local_config: bool,
+    #[arg(
+        long,
+        global = true,
+        help = "Apply [paths], restart/on-apply commands, theme hooks, and the awww log file from ./.theme-manager.toml (or set THEME_MANAGER_TRUST_LOCAL=1); ignored with a warning otherwise"
+    )]
+    pub trust_local: bool,
+    #[arg(
+        long,
+        global = true,
+        value_name = "NAME",
+        help = "Layer ~/.config/theme-manager/profiles/<NAME>.toml over the config (separate history and presets)"
+    )]
+    pub profile: Option<String>,
+    #[arg(
+        long,
+        global = true,
+        help = "Run the omarchy-theme-set-* helpers concurrently (overrides behavior.parallel_setters)"
+    )]
+    pub parallel_setters: bool,
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        help = "Seed the random background pick and transition angle so runs are reproducible"
+    )]
+    pub seed: Option<u64>,
+    #[arg(
+        long,
+        global = true,
+        help = "Skip the global and theme-local theme-set hooks"
+    )]
+    pub no_hook: bool,
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Manage this host hyprlock.conf instead of ~/.config/hypr/hyprlock.conf (overrides paths.hyprlock_host)"
+    )]
+    pub hyprlock_host: Option<PathBuf>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Command {
-    List,
+    List(ListArgs),
     Set(SetArgs),
     Next(NextArgs),
     Browse(BrowseArgs),
-    Current,
-    BgNext,
+    Reapply(ReapplyArgs),
+    #[cfg(feature = "watch")]
+    Watch(WatchArgs),
+    Current(CurrentArgs),
+    Reload(ReloadArgs),
+    Preview(PreviewArgs),
+    Palette(PaletteArgs),
+    History(HistoryArgs),
+    Undo(UndoArgs),
+    BgNext(BgNextArgs),
+    BgList(BgListArgs),
     PrintConfig,
+    Config(ConfigArgs),
     Version,
     Install(InstallArgs),
-    Update,
+    Update(UpdateArgs),
     Remove(RemoveArgs),
+    Link(LinkArgs),
+    #[command(alias = "deregister")]
+    Unlink(UnlinkArgs),
+    GenTheme(GenThemeArgs),
+    Lint(LintArgs),
+    Backup(BackupArgs),
+    Restore(RestoreArgs),
     Preset(PresetArgs),
     Waybar(WaybarArgs),
     Walker(WalkerArgs),
@@ -37,21 +130,207 @@ pub enum Command {
     Starship(StarshipArgs),
 }
 
+#[derive(Parser, Debug)]
+pub struct ListArgs {
+    #[arg(
+        long,
+        value_name = "TAG",
+        help = "Only list themes tagged TAG in theme.toml"
+    )]
+    pub tag: Option<String>,
+    #[arg(long, help = "Print each theme's tags")]
+    pub tags: bool,
+    #[arg(long, help = "Mark the active theme with a trailing `*`")]
+    pub current: bool,
+}
+
 #[derive(Parser, Debug)]
 pub struct SetArgs {
-    pub theme: String,
+    #[arg(required_unless_present_any = ["last", "stdin", "from_url"])]
+    pub theme: Option<String>,
+    #[arg(
+        long,
+        conflicts_with = "theme",
+        help = "Reapply the previously used theme from history"
+    )]
+    pub last: bool,
+    #[arg(
+        long,
+        conflicts_with_all = ["theme", "last"],
+        help = "Read the theme name from the first line of stdin"
+    )]
+    pub stdin: bool,
+    #[arg(
+        long = "from-url",
+        value_name = "GIT_URL",
+        conflicts_with_all = ["theme", "last", "stdin"],
+        help = "Install the theme from a git URL unless it is already present, then apply it"
+    )]
+    pub from_url: Option<String>,
     #[arg(short = 'w', long = "waybar", num_args = 0..=1, value_name = "NAME")]
     pub waybar: Option<Option<String>>,
     #[arg(short = 'k', long = "walker", num_args = 0..=1, value_name = "NAME")]
     pub walker: Option<Option<String>>,
     #[arg(long = "hyprlock", num_args = 0..=1, value_name = "NAME")]
     pub hyprlock: Option<Option<String>>,
+    #[arg(
+        long = "only",
+        value_name = "COMPONENTS",
+        help = "Apply only these components (comma list of waybar,walker,hyprlock,starship); the rest are left untouched"
+    )]
+    pub only: Option<String>,
     #[arg(short = 'q', long = "quiet")]
     pub quiet: bool,
+    #[arg(
+        long = "force-bg",
+        help = "Run the wallpaper transition even when the background is unchanged"
+    )]
+    pub force_bg: bool,
+    #[arg(
+        long = "background",
+        value_name = "IMAGE|random|next|keep",
+        help = "Background to use: the next one (default), a random one, a file from the theme, or keep the current link"
+    )]
+    pub background: Option<String>,
+    #[arg(
+        long = "wait",
+        help = "Wait for Waybar to be running again before returning"
+    )]
+    pub wait: bool,
+    #[arg(
+        long = "timings",
+        help = "Print how long each phase (staging, components, background, reload) took to stderr"
+    )]
+    pub timings: bool,
+    #[command(flatten)]
+    pub awww: AwwwArgs,
 }
 
 #[derive(Parser, Debug)]
 pub struct NextArgs {
+    #[arg(
+        long,
+        value_name = "SUBSTRING",
+        help = "Only cycle among themes whose name contains SUBSTRING (case-insensitive)"
+    )]
+    pub filter: Option<String>,
+    #[arg(
+        long,
+        value_name = "TAG",
+        help = "Only cycle among themes tagged TAG in theme.toml"
+    )]
+    pub tag: Option<String>,
+    #[arg(
+        long = "no-wrap",
+        help = "Stop at the last theme instead of wrapping around to the first"
+    )]
+    pub no_wrap: bool,
+    #[arg(
+        long = "count",
+        value_name = "N",
+        default_value_t = 1,
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "Advance N themes; only the final one is applied unless --each-delay is given"
+    )]
+    pub count: u32,
+    #[arg(
+        long = "each-delay",
+        value_name = "DURATION",
+        requires = "count",
+        help = "Apply every step of --count, pausing DURATION (e.g. 500ms, 2s) between them"
+    )]
+    pub each_delay: Option<String>,
+    #[arg(short = 'w', long = "waybar", num_args = 0..=1, value_name = "NAME")]
+    pub waybar: Option<Option<String>>,
+    #[arg(short = 'k', long = "walker", num_args = 0..=1, value_name = "NAME")]
+    pub walker: Option<Option<String>>,
+    #[arg(long = "hyprlock", num_args = 0..=1, value_name = "NAME")]
+    pub hyprlock: Option<Option<String>>,
+    #[arg(short = 'q', long = "quiet")]
+    pub quiet: bool,
+    #[arg(
+        long = "wait",
+        help = "Wait for Waybar to be running again before returning"
+    )]
+    pub wait: bool,
+    #[arg(
+        long = "timings",
+        help = "Print how long each phase (staging, components, background, reload) took to stderr"
+    )]
+    pub timings: bool,
+    #[command(flatten)]
+    pub awww: AwwwArgs,
+}
+
+#[derive(Parser, Debug)]
+pub struct BgNextArgs {
+    #[command(flatten)]
+    pub awww: AwwwArgs,
+}
+
+#[derive(Parser, Debug)]
+pub struct BgListArgs {
+    #[arg(
+        long,
+        value_name = "THEME",
+        help = "List wallpapers for THEME instead of the current theme"
+    )]
+    pub theme: Option<String>,
+}
+
+#[derive(Args, Debug, Clone, Default)]
+pub struct AwwwArgs {
+    #[arg(long = "transition-type", value_name = "TYPE")]
+    pub transition_type: Option<String>,
+    #[arg(long = "duration", value_name = "SECONDS")]
+    pub duration: Option<f32>,
+    #[arg(long = "angle", value_name = "DEGREES", allow_negative_numbers = true)]
+    pub angle: Option<f32>,
+    #[arg(long = "fps", value_name = "FPS")]
+    pub fps: Option<u32>,
+    #[arg(long = "output", value_name = "NAME")]
+    pub outputs: Vec<String>,
+    #[arg(
+        long = "verbose-awww-log",
+        value_name = "FILE",
+        help = "Append each awww command and its full stdout/stderr to FILE"
+    )]
+    pub awww_log: Option<PathBuf>,
+    #[arg(
+        long = "rotate-awww-log",
+        help = "Move the existing awww log to FILE.1 before writing"
+    )]
+    pub rotate_awww_log: bool,
+    #[arg(
+        long = "no-transition",
+        help = "Swap the wallpaper instantly for this run, without an awww transition"
+    )]
+    pub no_transition: bool,
+}
+
+#[derive(Parser, Debug)]
+#[command(
+    about = "Reapply the current theme's components (Waybar, Walker, Hyprlock, Starship) and reload apps without switching themes."
+)]
+pub struct ReapplyArgs {
+    #[arg(short = 'w', long = "waybar", num_args = 0..=1, value_name = "NAME")]
+    pub waybar: Option<Option<String>>,
+    #[arg(short = 'k', long = "walker", num_args = 0..=1, value_name = "NAME")]
+    pub walker: Option<Option<String>>,
+    #[arg(long = "hyprlock", num_args = 0..=1, value_name = "NAME")]
+    pub hyprlock: Option<Option<String>>,
+    #[arg(short = 'q', long = "quiet")]
+    pub quiet: bool,
+}
+
+#[cfg(feature = "watch")]
+#[derive(Parser, Debug)]
+#[command(
+    about = "Watch a theme's source directory and reapply its components whenever files change (Ctrl+C to stop)."
+)]
+pub struct WatchArgs {
+    #[arg(help = "Theme to watch (default: the current theme); it is set first if not current")]
+    pub theme: Option<String>,
     #[arg(short = 'w', long = "waybar", num_args = 0..=1, value_name = "NAME")]
     pub waybar: Option<Option<String>>,
     #[arg(short = 'k', long = "walker", num_args = 0..=1, value_name = "NAME")]
@@ -62,6 +341,79 @@ pub struct NextArgs {
     pub quiet: bool,
 }
 
+#[derive(Parser, Debug)]
+#[command(about = "Run `git pull` in every git-based theme.")]
+pub struct UpdateArgs {
+    #[arg(
+        short = 'q',
+        long = "quiet",
+        help = "Hide the per-theme progress lines"
+    )]
+    pub quiet: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct CurrentArgs {
+    #[arg(
+        long,
+        help = "Print whether the current theme is light, dark, or unknown"
+    )]
+    pub variant: bool,
+    #[arg(
+        long,
+        conflicts_with = "variant",
+        help = "Print the absolute path of the current theme directory (symlinks resolved)"
+    )]
+    pub path: bool,
+}
+
+#[derive(Parser, Debug)]
+#[command(about = "Restart themed components without changing any config or links.")]
+pub struct ReloadArgs {
+    #[arg(short = 'w', long = "waybar", help = "Restart Waybar only")]
+    pub waybar: bool,
+    #[arg(short = 'k', long = "walker", help = "Restart Walker only")]
+    pub walker: bool,
+    #[arg(long = "hyprlock", help = "Restart Hyprlock only")]
+    pub hyprlock: bool,
+    #[arg(
+        long = "all",
+        conflicts_with_all = ["waybar", "walker", "hyprlock"],
+        help = "Restart all components and rerun theme setters (default)"
+    )]
+    pub all: bool,
+    #[arg(short = 'q', long = "quiet")]
+    pub quiet: bool,
+}
+
+#[derive(Parser, Debug)]
+#[command(about = "Render a theme's preview image inline (kitty/chafa) and print its colors.")]
+pub struct PreviewArgs {
+    pub theme: String,
+}
+
+#[derive(Parser, Debug)]
+#[command(about = "Print the color variables defined in a theme's hyprland.conf.")]
+pub struct PaletteArgs {
+    pub theme: String,
+}
+
+#[derive(Parser, Debug)]
+#[command(about = "Show recently applied themes (oldest first).")]
+pub struct HistoryArgs {
+    #[arg(short = 'n', long = "limit", default_value_t = 10, value_name = "N")]
+    pub limit: usize,
+}
+
+#[derive(Parser, Debug)]
+#[command(
+    about = "Switch back to the theme (and component selections) active before the last switch."
+)]
+pub struct UndoArgs {
+    #[arg(short = 'q', long = "quiet")]
+    pub quiet: bool,
+}
+
 #[derive(Parser, Debug)]
 #[command(
     about = "Interactive picker with inline search (type to filter, Backspace deletes, Ctrl+u clears)."
@@ -69,11 +421,35 @@ pub struct NextArgs {
 pub struct BrowseArgs {
     #[arg(short = 'q', long = "quiet")]
     pub quiet: bool,
+    #[arg(
+        long = "no-mouse",
+        help = "Leave mouse capture off (keyboard only) for terminals that mishandle it"
+    )]
+    pub no_mouse: bool,
+    #[arg(
+        long = "no-color-preview",
+        help = "Show code previews as plain text without syntax colors (overrides tui.color_preview)"
+    )]
+    pub no_color_preview: bool,
 }
 
 #[derive(Parser, Debug)]
 pub struct InstallArgs {
     pub git_url: String,
+    #[arg(
+        long = "apply",
+        overrides_with = "no_apply",
+        help = "Switch to the theme after cloning (default)"
+    )]
+    pub apply: bool,
+    #[arg(
+        long = "no-apply",
+        overrides_with = "apply",
+        help = "Clone only; keep the current theme"
+    )]
+    pub no_apply: bool,
+    #[arg(long = "force", help = "Replace an existing theme with a fresh clone")]
+    pub force: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -81,6 +457,71 @@ pub struct RemoveArgs {
     pub theme: Option<String>,
 }
 
+#[derive(Parser, Debug)]
+#[command(about = "Register an external theme directory by symlinking it into the theme root.")]
+pub struct LinkArgs {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+#[derive(Parser, Debug)]
+#[command(about = "Remove a theme registered with `link`; real theme directories are left alone.")]
+pub struct UnlinkArgs {
+    pub name: String,
+}
+
+#[derive(Parser, Debug)]
+#[command(
+    about = "Create a theme skeleton (palette, Waybar, Walker, Hyprlock, Starship, backgrounds/) in the theme root."
+)]
+pub struct GenThemeArgs {
+    pub name: String,
+}
+
+#[derive(Parser, Debug)]
+#[command(
+    about = "Check a theme's components (Waybar, Walker, Hyprlock, Starship, backgrounds) before publishing it."
+)]
+pub struct LintArgs {
+    pub theme: String,
+}
+
+#[derive(Parser, Debug)]
+#[command(
+    about = "Snapshot current/, managed Waybar/Walker/Hyprlock/Starship files, and presets into DIR."
+)]
+pub struct BackupArgs {
+    pub dir: PathBuf,
+}
+
+#[derive(Parser, Debug)]
+#[command(about = "Restore a snapshot created by `backup` (asks for confirmation).")]
+pub struct RestoreArgs {
+    #[arg(help = "Backup folder printed by `backup`")]
+    pub dir: PathBuf,
+    #[arg(short = 'y', long = "yes", help = "Skip the confirmation prompt")]
+    pub yes: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    #[command(about = "Open the user config in $EDITOR (creates a commented template if missing)")]
+    Edit,
+    #[command(about = "Print the user config path")]
+    Path,
+    #[command(about = "Describe config keys: type, default, and environment override")]
+    Explain {
+        #[arg(value_name = "KEY", help = "Dotted key such as waybar.apply_mode")]
+        key: Option<String>,
+    },
+}
+
 #[derive(Parser, Debug)]
 pub struct PresetArgs {
     #[command(subcommand)]
@@ -98,7 +539,7 @@ pub enum PresetCommand {
 #[derive(Parser, Debug)]
 pub struct PresetSaveArgs {
     pub name: String,
-    #[arg(long)]
+    #[arg(long, conflicts_with = "from_current")]
     pub theme: Option<String>,
     #[arg(long, value_name = "MODE|NAME")]
     pub waybar: Option<String>,
@@ -108,6 +549,13 @@ pub struct PresetSaveArgs {
     pub hyprlock: Option<String>,
     #[arg(long, value_name = "MODE|NAME")]
     pub starship: Option<String>,
+    #[arg(long, help = "Replace an existing preset with the same name")]
+    pub overwrite: bool,
+    #[arg(
+        long = "from-current",
+        help = "Take unspecified components from what is applied now instead of config defaults"
+    )]
+    pub from_current: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -121,6 +569,18 @@ pub struct PresetLoadArgs {
     pub hyprlock: Option<Option<String>>,
     #[arg(short = 'q', long = "quiet")]
     pub quiet: bool,
+    #[arg(
+        long = "preview",
+        visible_alias = "diff",
+        help = "Show the current state and what the preset would change, then ask before applying"
+    )]
+    pub preview: bool,
+    #[arg(
+        long = "dry-run",
+        conflicts_with = "preview",
+        help = "Show what the preset would change without applying it"
+    )]
+    pub dry_run: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -130,28 +590,102 @@ pub struct PresetRemoveArgs {
 
 #[derive(Parser, Debug)]
 pub struct WaybarArgs {
-    pub mode: String,
+    #[arg(required_unless_present = "list")]
+    pub mode: Option<String>,
+    #[arg(
+        long = "list",
+        conflicts_with = "mode",
+        help = "List the available Waybar modes and named themes"
+    )]
+    pub list: bool,
+    #[arg(
+        long = "theme",
+        value_name = "NAME",
+        help = "Resolve `auto` against this theme instead of the current one"
+    )]
+    pub theme: Option<String>,
     #[arg(short = 'q', long = "quiet")]
     pub quiet: bool,
+    #[arg(
+        long = "no-restart",
+        help = "Apply without restarting; run `reload` afterwards"
+    )]
+    pub no_restart: bool,
+    #[arg(
+        long = "check",
+        visible_alias = "validate-only",
+        help = "Validate the Waybar theme (files, JSONC, CSS imports) without applying it"
+    )]
+    pub check: bool,
+    #[arg(
+        long = "apply-mode",
+        value_name = "MODE",
+        value_parser = ["symlink", "copy", "hardlink"],
+        help = "Override waybar.apply_mode for this run"
+    )]
+    pub apply_mode: Option<String>,
 }
 
 #[derive(Parser, Debug)]
 pub struct WalkerArgs {
-    pub mode: String,
+    #[arg(required_unless_present = "list")]
+    pub mode: Option<String>,
+    #[arg(
+        long = "list",
+        conflicts_with = "mode",
+        help = "List the available Walker modes and named themes"
+    )]
+    pub list: bool,
+    #[arg(
+        long = "theme",
+        value_name = "NAME",
+        help = "Resolve `auto` against this theme instead of the current one"
+    )]
+    pub theme: Option<String>,
     #[arg(short = 'q', long = "quiet")]
     pub quiet: bool,
+    #[arg(
+        long = "no-restart",
+        help = "Apply without restarting; run `reload` afterwards"
+    )]
+    pub no_restart: bool,
 }
 
 #[derive(Parser, Debug)]
 pub struct HyprlockArgs {
-    pub mode: String,
+    #[arg(required_unless_present = "list")]
+    pub mode: Option<String>,
+    #[arg(
+        long = "list",
+        conflicts_with = "mode",
+        help = "List the available Hyprlock modes and named themes"
+    )]
+    pub list: bool,
+    #[arg(
+        long = "theme",
+        value_name = "NAME",
+        help = "Resolve `auto` against this theme instead of the current one"
+    )]
+    pub theme: Option<String>,
     #[arg(short = 'q', long = "quiet")]
     pub quiet: bool,
+    #[arg(
+        long = "no-restart",
+        help = "Apply without restarting; run `reload` afterwards"
+    )]
+    pub no_restart: bool,
 }
 
 #[derive(Parser, Debug)]
 pub struct StarshipArgs {
-    pub mode: String,
+    #[arg(required_unless_present = "list")]
+    pub mode: Option<String>,
+    #[arg(
+        long = "list",
+        conflicts_with = "mode",
+        help = "List available Starship presets and named themes"
+    )]
+    pub list: bool,
     #[arg(short = 'q', long = "quiet")]
     pub quiet: bool,
 }
@@ -3,6 +3,9 @@ use serde::Deserialize;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::error::ThemeError;
 
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct FileConfig {
@@ -18,17 +21,53 @@ pub struct FileConfig {
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct PathsConfig {
     pub theme_root_dir: Option<String>,
+    pub theme_root_dirs: Option<Vec<String>>,
     pub current_theme_link: Option<String>,
     pub current_background_link: Option<String>,
     pub omarchy_bin_dir: Option<String>,
+    pub extra_bin_dirs: Option<Vec<String>>,
     pub waybar_dir: Option<String>,
     pub waybar_themes_dir: Option<String>,
     pub walker_dir: Option<String>,
     pub walker_themes_dir: Option<String>,
     pub hyprlock_dir: Option<String>,
     pub hyprlock_themes_dir: Option<String>,
+    pub hyprlock_host: Option<String>,
     pub starship_config: Option<String>,
     pub starship_themes_dir: Option<String>,
+    pub lock_file: Option<String>,
+    pub history_file: Option<String>,
+}
+
+impl PathsConfig {
+    // Keys this section sets, named for the untrusted local config warning.
+    fn set_keys(&self) -> Vec<&'static str> {
+        [
+            ("theme_root_dir", self.theme_root_dir.is_some()),
+            ("theme_root_dirs", self.theme_root_dirs.is_some()),
+            ("current_theme_link", self.current_theme_link.is_some()),
+            (
+                "current_background_link",
+                self.current_background_link.is_some(),
+            ),
+            ("omarchy_bin_dir", self.omarchy_bin_dir.is_some()),
+            ("extra_bin_dirs", self.extra_bin_dirs.is_some()),
+            ("waybar_dir", self.waybar_dir.is_some()),
+            ("waybar_themes_dir", self.waybar_themes_dir.is_some()),
+            ("walker_dir", self.walker_dir.is_some()),
+            ("walker_themes_dir", self.walker_themes_dir.is_some()),
+            ("hyprlock_dir", self.hyprlock_dir.is_some()),
+            ("hyprlock_themes_dir", self.hyprlock_themes_dir.is_some()),
+            ("hyprlock_host", self.hyprlock_host.is_some()),
+            ("starship_config", self.starship_config.is_some()),
+            ("starship_themes_dir", self.starship_themes_dir.is_some()),
+            ("lock_file", self.lock_file.is_some()),
+            ("history_file", self.history_file.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(key, set)| set.then_some(key))
+        .collect()
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -38,11 +77,13 @@ pub struct WaybarConfig {
     pub restart_logs: Option<bool>,
     pub default_mode: Option<String>,
     pub default_name: Option<String>,
+    pub include_optional_files: Option<bool>,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct WalkerConfig {
     pub apply_mode: Option<String>,
+    pub restart_cmd: Option<String>,
     pub default_mode: Option<String>,
     pub default_name: Option<String>,
 }
@@ -50,6 +91,7 @@ pub struct WalkerConfig {
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct HyprlockConfig {
     pub apply_mode: Option<String>,
+    pub restart_cmd: Option<String>,
     pub default_mode: Option<String>,
     pub default_name: Option<String>,
 }
@@ -64,6 +106,8 @@ pub struct StarshipConfig {
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct TuiConfig {
     pub apply_key: Option<String>,
+    pub code_theme: Option<String>,
+    pub color_preview: Option<bool>,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -77,38 +121,425 @@ pub struct BehaviorConfig {
     pub awww_transition_pos: Option<String>,
     pub awww_transition_bezier: Option<String>,
     pub awww_transition_wave: Option<String>,
+    pub awww_outputs: Option<Vec<String>>,
+    pub awww_log_file: Option<String>,
     pub awww_auto_start: Option<bool>,
+    pub warn_unknown_config: Option<bool>,
+    pub on_apply_cmd: Option<String>,
+    pub notify_on_change: Option<bool>,
+    pub git_retries: Option<u32>,
+    pub restart_wait_ms: Option<u64>,
+    pub command_timeout_ms: Option<u64>,
+    pub parallel_setters: Option<bool>,
+    pub run_theme_hooks: Option<bool>,
+}
+
+pub struct ConfigKeyDoc {
+    pub key: &'static str,
+    pub ty: &'static str,
+    pub env: Option<&'static str>,
+    pub default: fn(&ResolvedConfig) -> String,
+}
+
+// Keep in sync with `FileConfig` and its section structs; also drives unknown-key warnings.
+pub const CONFIG_KEYS: &[ConfigKeyDoc] = &[
+    ConfigKeyDoc {
+        key: "paths.theme_root_dir",
+        ty: "path",
+        env: Some("THEME_ROOT_DIR"),
+        default: |d| path_default(&d.theme_root_dir),
+    },
+    ConfigKeyDoc {
+        key: "paths.theme_root_dirs",
+        ty: "list of paths",
+        env: Some("THEME_ROOT_DIRS"),
+        default: |d| format!("{:?}", d.theme_root_dirs),
+    },
+    ConfigKeyDoc {
+        key: "paths.current_theme_link",
+        ty: "path",
+        env: Some("CURRENT_THEME_LINK"),
+        default: |d| path_default(&d.current_theme_link),
+    },
+    ConfigKeyDoc {
+        key: "paths.current_background_link",
+        ty: "path",
+        env: Some("CURRENT_BACKGROUND_LINK"),
+        default: |d| path_default(&d.current_background_link),
+    },
+    ConfigKeyDoc {
+        key: "paths.omarchy_bin_dir",
+        ty: "path",
+        env: Some("OMARCHY_BIN_DIR"),
+        default: |d| optional_default(d.omarchy_bin_dir.as_deref().map(path_default)),
+    },
+    ConfigKeyDoc {
+        key: "paths.extra_bin_dirs",
+        ty: "list of paths",
+        env: None,
+        default: |d| format!("{:?}", d.extra_bin_dirs),
+    },
+    ConfigKeyDoc {
+        key: "paths.waybar_dir",
+        ty: "path",
+        env: Some("WAYBAR_DIR"),
+        default: |d| path_default(&d.waybar_dir),
+    },
+    ConfigKeyDoc {
+        key: "paths.waybar_themes_dir",
+        ty: "path",
+        env: Some("WAYBAR_THEMES_DIR"),
+        default: |d| path_default(&d.waybar_themes_dir),
+    },
+    ConfigKeyDoc {
+        key: "paths.walker_dir",
+        ty: "path",
+        env: Some("WALKER_DIR"),
+        default: |d| path_default(&d.walker_dir),
+    },
+    ConfigKeyDoc {
+        key: "paths.walker_themes_dir",
+        ty: "path",
+        env: Some("WALKER_THEMES_DIR"),
+        default: |d| path_default(&d.walker_themes_dir),
+    },
+    ConfigKeyDoc {
+        key: "paths.hyprlock_dir",
+        ty: "path",
+        env: Some("HYPRLOCK_DIR"),
+        default: |d| path_default(&d.hyprlock_dir),
+    },
+    ConfigKeyDoc {
+        key: "paths.hyprlock_themes_dir",
+        ty: "path",
+        env: Some("HYPRLOCK_THEMES_DIR"),
+        default: |d| path_default(&d.hyprlock_themes_dir),
+    },
+    ConfigKeyDoc {
+        key: "paths.hyprlock_host",
+        ty: "path",
+        env: Some("HYPRLOCK_HOST"),
+        default: |d| path_default(&d.hyprlock_host_path()),
+    },
+    ConfigKeyDoc {
+        key: "paths.starship_config",
+        ty: "path",
+        env: Some("STARSHIP_CONFIG"),
+        default: |d| path_default(&d.starship_config),
+    },
+    ConfigKeyDoc {
+        key: "paths.starship_themes_dir",
+        ty: "path",
+        env: Some("STARSHIP_THEMES_DIR"),
+        default: |d| path_default(&d.starship_themes_dir),
+    },
+    ConfigKeyDoc {
+        key: "paths.lock_file",
+        ty: "path",
+        env: Some("THEME_MANAGER_LOCK_FILE"),
+        default: |d| path_default(&d.lock_file),
+    },
+    ConfigKeyDoc {
+        key: "paths.history_file",
+        ty: "path",
+        env: Some("THEME_MANAGER_HISTORY_FILE"),
+        default: |d| path_default(&d.history_file),
+    },
+    ConfigKeyDoc {
+        key: "waybar.apply_mode",
+        ty: "string",
+        env: Some("WAYBAR_APPLY_MODE"),
+        default: |d| string_default(&d.waybar_apply_mode),
+    },
+    ConfigKeyDoc {
+        key: "waybar.restart_cmd",
+        ty: "string",
+        env: Some("WAYBAR_RESTART_CMD"),
+        default: |d| optional_default(d.waybar_restart_cmd.as_deref().map(string_default)),
+    },
+    ConfigKeyDoc {
+        key: "waybar.restart_logs",
+        ty: "bool",
+        env: Some("WAYBAR_RESTART_LOGS"),
+        default: |d| d.waybar_restart_logs.to_string(),
+    },
+    ConfigKeyDoc {
+        key: "waybar.default_mode",
+        ty: "string",
+        env: Some("DEFAULT_WAYBAR_MODE"),
+        default: |d| optional_default(d.default_waybar_mode.as_deref().map(string_default)),
+    },
+    ConfigKeyDoc {
+        key: "waybar.default_name",
+        ty: "string",
+        env: Some("DEFAULT_WAYBAR_NAME"),
+        default: |d| optional_default(d.default_waybar_name.as_deref().map(string_default)),
+    },
+    ConfigKeyDoc {
+        key: "waybar.include_optional_files",
+        ty: "bool",
+        env: None,
+        default: |d| d.waybar_include_optional_files.to_string(),
+    },
+    ConfigKeyDoc {
+        key: "walker.apply_mode",
+        ty: "string",
+        env: Some("WALKER_APPLY_MODE"),
+        default: |d| string_default(&d.walker_apply_mode),
+    },
+    ConfigKeyDoc {
+        key: "walker.restart_cmd",
+        ty: "string",
+        env: Some("WALKER_RESTART_CMD"),
+        default: |d| optional_default(d.walker_restart_cmd.as_deref().map(string_default)),
+    },
+    ConfigKeyDoc {
+        key: "walker.default_mode",
+        ty: "string",
+        env: Some("DEFAULT_WALKER_MODE"),
+        default: |d| optional_default(d.default_walker_mode.as_deref().map(string_default)),
+    },
+    ConfigKeyDoc {
+        key: "walker.default_name",
+        ty: "string",
+        env: Some("DEFAULT_WALKER_NAME"),
+        default: |d| optional_default(d.default_walker_name.as_deref().map(string_default)),
+    },
+    ConfigKeyDoc {
+        key: "hyprlock.apply_mode",
+        ty: "string",
+        env: Some("HYPRLOCK_APPLY_MODE"),
+        default: |d| string_default(&d.hyprlock_apply_mode),
+    },
+    ConfigKeyDoc {
+        key: "hyprlock.restart_cmd",
+        ty: "string",
+        env: Some("HYPRLOCK_RESTART_CMD"),
+        default: |d| optional_default(d.hyprlock_restart_cmd.as_deref().map(string_default)),
+    },
+    ConfigKeyDoc {
+        key: "hyprlock.default_mode",
+        ty: "string",
+        env: Some("DEFAULT_HYPRLOCK_MODE"),
+        default: |d| optional_default(d.default_hyprlock_mode.as_deref().map(string_default)),
+    },
+    ConfigKeyDoc {
+        key: "hyprlock.default_name",
+        ty: "string",
+        env: Some("DEFAULT_HYPRLOCK_NAME"),
+        default: |d| optional_default(d.default_hyprlock_name.as_deref().map(string_default)),
+    },
+    ConfigKeyDoc {
+        key: "starship.default_mode",
+        ty: "string",
+        env: Some("DEFAULT_STARSHIP_MODE"),
+        default: |d| optional_default(d.default_starship_mode.as_deref().map(string_default)),
+    },
+    ConfigKeyDoc {
+        key: "starship.default_preset",
+        ty: "string",
+        env: Some("DEFAULT_STARSHIP_PRESET"),
+        default: |d| optional_default(d.default_starship_preset.as_deref().map(string_default)),
+    },
+    ConfigKeyDoc {
+        key: "starship.default_name",
+        ty: "string",
+        env: Some("DEFAULT_STARSHIP_NAME"),
+        default: |d| optional_default(d.default_starship_name.as_deref().map(string_default)),
+    },
+    ConfigKeyDoc {
+        key: "tui.apply_key",
+        ty: "string",
+        env: None,
+        default: |d| optional_default(d.tui_apply_key.as_deref().map(string_default)),
+    },
+    ConfigKeyDoc {
+        key: "tui.code_theme",
+        ty: "string",
+        env: None,
+        default: |d| optional_default(d.tui_code_theme.as_deref().map(string_default)),
+    },
+    ConfigKeyDoc {
+        key: "tui.color_preview",
+        ty: "bool",
+        env: None,
+        default: |d| d.tui_color_preview.to_string(),
+    },
+    ConfigKeyDoc {
+        key: "behavior.quiet_default",
+        ty: "bool",
+        env: Some("QUIET_MODE_DEFAULT"),
+        default: |d| d.quiet_default.to_string(),
+    },
+    ConfigKeyDoc {
+        key: "behavior.awww_transition",
+        ty: "bool",
+        env: Some("THEME_MANAGER_AWWW_TRANSITION"),
+        default: |d| d.awww_transition.to_string(),
+    },
+    ConfigKeyDoc {
+        key: "behavior.awww_transition_type",
+        ty: "string",
+        env: None,
+        default: |d| string_default(&d.awww_transition_type),
+    },
+    ConfigKeyDoc {
+        key: "behavior.awww_transition_duration",
+        ty: "float",
+        env: None,
+        default: |d| d.awww_transition_duration.to_string(),
+    },
+    ConfigKeyDoc {
+        key: "behavior.awww_transition_angle",
+        ty: "float",
+        env: None,
+        default: |d| d.awww_transition_angle.to_string(),
+    },
+    ConfigKeyDoc {
+        key: "behavior.awww_transition_fps",
+        ty: "integer",
+        env: None,
+        default: |d| d.awww_transition_fps.to_string(),
+    },
+    ConfigKeyDoc {
+        key: "behavior.awww_transition_pos",
+        ty: "string",
+        env: Some("THEME_MANAGER_AWWW_TRANSITION_POS"),
+        default: |d| string_default(&d.awww_transition_pos),
+    },
+    ConfigKeyDoc {
+        key: "behavior.awww_transition_bezier",
+        ty: "string",
+        env: Some("THEME_MANAGER_AWWW_TRANSITION_BEZIER"),
+        default: |d| string_default(&d.awww_transition_bezier),
+    },
+    ConfigKeyDoc {
+        key: "behavior.awww_transition_wave",
+        ty: "string",
+        env: Some("THEME_MANAGER_AWWW_TRANSITION_WAVE"),
+        default: |d| string_default(&d.awww_transition_wave),
+    },
+    ConfigKeyDoc {
+        key: "behavior.awww_outputs",
+        ty: "list of strings",
+        env: Some("THEME_MANAGER_AWWW_OUTPUTS"),
+        default: |d| format!("{:?}", d.awww_outputs),
+    },
+    ConfigKeyDoc {
+        key: "behavior.awww_log_file",
+        ty: "path",
+        env: None,
+        default: |d| optional_default(d.awww_log_file.as_deref().map(path_default)),
+    },
+    ConfigKeyDoc {
+        key: "behavior.awww_auto_start",
+        ty: "bool",
+        env: Some("THEME_MANAGER_AWWW_AUTO_START"),
+        default: |d| d.awww_auto_start.to_string(),
+    },
+    ConfigKeyDoc {
+        key: "behavior.warn_unknown_config",
+        ty: "bool",
+        env: None,
+        default: |d| d.warn_unknown_config.to_string(),
+    },
+    ConfigKeyDoc {
+        key: "behavior.on_apply_cmd",
+        ty: "string",
+        env: Some("THEME_MANAGER_ON_APPLY_CMD"),
+        default: |d| optional_default(d.on_apply_cmd.as_deref().map(string_default)),
+    },
+    ConfigKeyDoc {
+        key: "behavior.notify_on_change",
+        ty: "bool",
+        env: None,
+        default: |d| d.notify_on_change.to_string(),
+    },
+    ConfigKeyDoc {
+        key: "behavior.git_retries",
+        ty: "integer",
+        env: None,
+        default: |d| d.git_retries.to_string(),
+    },
+    ConfigKeyDoc {
+        key: "behavior.restart_wait_ms",
+        ty: "integer",
+        env: None,
+        default: |d| d.restart_wait_ms.to_string(),
+    },
+    ConfigKeyDoc {
+        key: "behavior.command_timeout_ms",
+        ty: "integer",
+        env: None,
+        default: |d| d.command_timeout_ms.to_string(),
+    },
+    ConfigKeyDoc {
+        key: "behavior.parallel_setters",
+        ty: "bool",
+        env: None,
+        default: |d| d.parallel_setters.to_string(),
+    },
+    ConfigKeyDoc {
+        key: "behavior.run_theme_hooks",
+        ty: "bool",
+        env: None,
+        default: |d| d.run_theme_hooks.to_string(),
+    },
+];
+
+fn path_default(path: &Path) -> String {
+    format!("\"{}\"", path.to_string_lossy())
+}
+
+fn string_default(value: &str) -> String {
+    format!("\"{value}\"")
+}
+
+fn optional_default(value: Option<String>) -> String {
+    value.unwrap_or_else(|| "(unset)".to_string())
 }
 
 #[derive(Debug, Clone)]
 pub struct ResolvedConfig {
     pub theme_root_dir: PathBuf,
+    // Extra roots searched after `theme_root_dir`; installs still go to `theme_root_dir`.
+    pub theme_root_dirs: Vec<PathBuf>,
     pub current_theme_link: PathBuf,
     pub current_background_link: PathBuf,
     pub omarchy_bin_dir: Option<PathBuf>,
+    pub extra_bin_dirs: Vec<PathBuf>,
     pub waybar_dir: PathBuf,
     pub waybar_themes_dir: PathBuf,
     pub waybar_apply_mode: String,
     pub waybar_restart_cmd: Option<String>,
     pub waybar_restart_logs: bool,
+    pub waybar_include_optional_files: bool,
     pub default_waybar_mode: Option<String>,
     pub default_waybar_name: Option<String>,
     pub walker_dir: PathBuf,
     pub walker_themes_dir: PathBuf,
     pub walker_apply_mode: String,
+    pub walker_restart_cmd: Option<String>,
     pub default_walker_mode: Option<String>,
     pub default_walker_name: Option<String>,
     pub hyprlock_dir: PathBuf,
     pub hyprlock_themes_dir: PathBuf,
+    pub hyprlock_host: Option<PathBuf>,
     pub hyprlock_apply_mode: String,
+    pub hyprlock_restart_cmd: Option<String>,
     pub default_hyprlock_mode: Option<String>,
     pub default_hyprlock_name: Option<String>,
     pub starship_config: PathBuf,
     pub starship_themes_dir: PathBuf,
+    pub lock_file: PathBuf,
+    pub history_file: PathBuf,
+    pub presets_file: PathBuf,
     pub default_starship_mode: Option<String>,
     pub default_starship_preset: Option<String>,
     pub default_starship_name: Option<String>,
     pub tui_apply_key: Option<String>,
+    pub tui_code_theme: Option<String>,
+    pub tui_color_preview: bool,
     pub quiet_default: bool,
     pub awww_transition: bool,
     pub awww_transition_type: String,
@@ -118,27 +549,98 @@ pub struct ResolvedConfig {
     pub awww_transition_pos: String,
     pub awww_transition_bezier: String,
     pub awww_transition_wave: String,
+    pub awww_outputs: Vec<String>,
+    pub awww_log_file: Option<PathBuf>,
+    // Set by `--rotate-awww-log` only: move the previous log aside before this run.
+    pub awww_log_rotate: bool,
+    // Set by `--no-transition` only: swap the wallpaper with a plain `awww img`.
+    pub awww_no_transition: bool,
+    // Set by `--seed` only: makes random backgrounds and transition angles repeatable.
+    pub seed: Option<u64>,
     pub awww_auto_start: bool,
+    pub warn_unknown_config: bool,
+    pub on_apply_cmd: Option<String>,
+    pub notify_on_change: bool,
+    pub git_retries: u32,
+    pub restart_wait_ms: u64,
+    pub command_timeout_ms: u64,
+    pub parallel_setters: bool,
+    pub run_theme_hooks: bool,
 }
 
 impl ResolvedConfig {
-    pub fn load() -> Result<Self> {
+    // An explicit `config_path` replaces the user and local config files; a profile is
+    // layered over either and gets its own history and presets files. The local file is
+    // also skipped for `no_local_config` or THEME_MANAGER_NO_LOCAL_CONFIG=1, and its
+    // `[paths]` and command/hook/log keys (which run code or write files) need
+    // `trust_local` or THEME_MANAGER_TRUST_LOCAL=1.
+    pub fn load(
+        config_path: Option<&Path>,
+        profile: Option<&str>,
+        no_local_config: bool,
+        trust_local: bool,
+    ) -> Result<Self> {
         let home = env::var("HOME").map_err(|_| anyhow!("HOME is not set"))?;
         let home_path = PathBuf::from(&home);
 
         let mut config = ResolvedConfig::defaults(&home_path);
+        let mut unknown_keys = Vec::new();
 
-        if let Some(user_cfg) = load_toml(&home_path.join(".config/theme-manager/config.toml"))? {
-            config.apply_file_config(&user_cfg, &home_path);
+        let profile_path = match profile {
+            Some(name) => {
+                let path = profile_config_path(&home_path, name)?;
+                let data_dir = path.with_extension("");
+                config.history_file = data_dir.join("history.jsonl");
+                config.presets_file = data_dir.join("presets.toml");
+                Some(path)
+            }
+            None => None,
+        };
+
+        if let Some(path) = config_path {
+            let cfg = load_toml(path, &mut unknown_keys)?
+                .ok_or_else(|| anyhow!("config file not found: {}", path.to_string_lossy()))?;
+            config.apply_file_config(&cfg, &home_path);
+        } else {
+            if let Some(user_cfg) = load_toml(&user_config_path(&home_path), &mut unknown_keys)? {
+                config.apply_file_config(&user_cfg, &home_path);
+            }
+            let skip_local = no_local_config || env_flag("THEME_MANAGER_NO_LOCAL_CONFIG");
+            if !skip_local {
+                let local_path = current_dir()?.join(".theme-manager.toml");
+                if let Some(mut local_cfg) = load_toml(&local_path, &mut unknown_keys)? {
+                    let trusted = trust_local || env_flag("THEME_MANAGER_TRUST_LOCAL");
+                    if !trusted {
+                        let ignored = strip_untrusted_keys(&mut local_cfg);
+                        warn_untrusted_local_keys(&ignored, &local_path);
+                    }
+                    config.apply_file_config(&local_cfg, &home_path);
+                }
+            }
         }
-        if let Some(local_cfg) = load_toml(&current_dir()?.join(".theme-manager.toml"))? {
-            config.apply_file_config(&local_cfg, &home_path);
+        if let Some(path) = profile_path {
+            let cfg = load_toml(&path, &mut unknown_keys)?
+                .ok_or_else(|| anyhow!("profile not found: {}", path.to_string_lossy()))?;
+            config.apply_file_config(&cfg, &home_path);
         }
 
         config.apply_env_overrides(&home_path)?;
+        if config.warn_unknown_config {
+            for warning in unknown_keys {
+                eprintln!("{} {warning}", crate::output::warning_prefix());
+            }
+        }
         Ok(config)
     }
 
+    // The host hyprlock.conf that sources the current theme; defaults to the one in
+    // `hyprlock_dir`.
+    pub fn hyprlock_host_path(&self) -> PathBuf {
+        self.hyprlock_host
+            .clone()
+            .unwrap_or_else(|| self.hyprlock_dir.join("hyprlock.conf"))
+    }
+
     fn defaults(home: &Path) -> Self {
         let theme_root_dir = home.join(".config/omarchy/themes");
         let current_theme_link = home.join(".config/omarchy/current/theme");
@@ -152,9 +654,13 @@ impl ResolvedConfig {
         let hyprlock_themes_dir = hyprlock_dir.join("themes/hyprlock");
         let starship_config = home.join(".config/starship.toml");
         let starship_themes_dir = home.join(".config/starship-themes");
+        let lock_file = home.join(".config/theme-manager/.lock");
+        let history_file = home.join(".config/theme-manager/history.jsonl");
+        let presets_file = home.join(".config/theme-manager/presets.toml");
 
         ResolvedConfig {
             theme_root_dir,
+            theme_root_dirs: Vec::new(),
             current_theme_link,
             current_background_link,
             omarchy_bin_dir: if default_omarchy_bin.is_dir() {
@@ -162,29 +668,39 @@ impl ResolvedConfig {
             } else {
                 None
             },
+            extra_bin_dirs: Vec::new(),
             waybar_dir,
             waybar_themes_dir,
             waybar_apply_mode: "symlink".to_string(),
             waybar_restart_cmd: None,
             waybar_restart_logs: false,
+            waybar_include_optional_files: false,
             default_waybar_mode: None,
             default_waybar_name: None,
             walker_dir,
             walker_themes_dir,
             walker_apply_mode: "symlink".to_string(),
+            walker_restart_cmd: None,
             default_walker_mode: None,
             default_walker_name: None,
             hyprlock_dir,
             hyprlock_themes_dir,
+            hyprlock_host: None,
             hyprlock_apply_mode: "symlink".to_string(),
+            hyprlock_restart_cmd: None,
             default_hyprlock_mode: None,
             default_hyprlock_name: None,
             starship_config,
             starship_themes_dir,
+            lock_file,
+            history_file,
+            presets_file,
             default_starship_mode: None,
             default_starship_preset: None,
             default_starship_name: None,
             tui_apply_key: None,
+            tui_code_theme: None,
+            tui_color_preview: true,
             quiet_default: false,
             awww_transition: true,
             awww_transition_type: "grow".to_string(),
@@ -194,7 +710,20 @@ impl ResolvedConfig {
             awww_transition_pos: "center".to_string(),
             awww_transition_bezier: ".42,0,.2,1".to_string(),
             awww_transition_wave: "28,12".to_string(),
+            awww_outputs: Vec::new(),
+            awww_log_file: None,
+            awww_log_rotate: false,
+            awww_no_transition: false,
+            seed: None,
             awww_auto_start: false,
+            warn_unknown_config: true,
+            on_apply_cmd: None,
+            notify_on_change: false,
+            git_retries: 2,
+            restart_wait_ms: 5000,
+            command_timeout_ms: 10000,
+            parallel_setters: false,
+            run_theme_hooks: false,
         }
     }
 
@@ -203,6 +732,12 @@ impl ResolvedConfig {
             if let Some(val) = &paths.theme_root_dir {
                 self.theme_root_dir = expand_path(val, home);
             }
+            if let Some(val) = &paths.theme_root_dirs {
+                self.theme_root_dirs = clean_list(val)
+                    .iter()
+                    .map(|dir| expand_path(dir, home))
+                    .collect();
+            }
             if let Some(val) = &paths.current_theme_link {
                 self.current_theme_link = expand_path(val, home);
             }
@@ -212,6 +747,12 @@ impl ResolvedConfig {
             if let Some(val) = &paths.omarchy_bin_dir {
                 self.omarchy_bin_dir = Some(expand_path(val, home));
             }
+            if let Some(val) = &paths.extra_bin_dirs {
+                self.extra_bin_dirs = clean_list(val)
+                    .iter()
+                    .map(|dir| expand_path(dir, home))
+                    .collect();
+            }
             if let Some(val) = &paths.waybar_dir {
                 self.waybar_dir = expand_path(val, home);
             }
@@ -236,12 +777,21 @@ impl ResolvedConfig {
             } else {
                 self.hyprlock_themes_dir = self.hyprlock_dir.join("themes/hyprlock");
             }
+            if let Some(val) = &paths.hyprlock_host {
+                self.hyprlock_host = Some(expand_path(val, home));
+            }
             if let Some(val) = &paths.starship_config {
                 self.starship_config = expand_path(val, home);
             }
             if let Some(val) = &paths.starship_themes_dir {
                 self.starship_themes_dir = expand_path(val, home);
             }
+            if let Some(val) = &paths.lock_file {
+                self.lock_file = expand_path(val, home);
+            }
+            if let Some(val) = &paths.history_file {
+                self.history_file = expand_path(val, home);
+            }
         }
 
         if let Some(waybar) = &cfg.waybar {
@@ -254,6 +804,9 @@ impl ResolvedConfig {
             if let Some(val) = waybar.restart_logs {
                 self.waybar_restart_logs = val;
             }
+            if let Some(val) = waybar.include_optional_files {
+                self.waybar_include_optional_files = val;
+            }
             if let Some(val) = &waybar.default_mode {
                 self.default_waybar_mode = Some(val.clone());
             }
@@ -278,6 +831,9 @@ impl ResolvedConfig {
             if let Some(val) = &walker.apply_mode {
                 self.walker_apply_mode = val.clone();
             }
+            if let Some(val) = &walker.restart_cmd {
+                self.walker_restart_cmd = Some(val.clone());
+            }
             if let Some(val) = &walker.default_mode {
                 self.default_walker_mode = Some(val.clone());
             }
@@ -290,6 +846,9 @@ impl ResolvedConfig {
             if let Some(val) = &hyprlock.apply_mode {
                 self.hyprlock_apply_mode = val.clone();
             }
+            if let Some(val) = &hyprlock.restart_cmd {
+                self.hyprlock_restart_cmd = Some(val.clone());
+            }
             if let Some(val) = &hyprlock.default_mode {
                 self.default_hyprlock_mode = Some(val.clone());
             }
@@ -302,6 +861,12 @@ impl ResolvedConfig {
             if let Some(val) = &tui.apply_key {
                 self.tui_apply_key = Some(val.clone());
             }
+            if let Some(val) = &tui.code_theme {
+                self.tui_code_theme = (!val.trim().is_empty()).then(|| val.trim().to_string());
+            }
+            if let Some(val) = tui.color_preview {
+                self.tui_color_preview = val;
+            }
         }
 
         if let Some(behavior) = &cfg.behavior {
@@ -332,9 +897,39 @@ impl ResolvedConfig {
             if let Some(val) = &behavior.awww_transition_wave {
                 self.awww_transition_wave = val.clone();
             }
+            if let Some(val) = &behavior.awww_outputs {
+                self.awww_outputs = clean_list(val);
+            }
+            if let Some(val) = &behavior.awww_log_file {
+                self.awww_log_file = (!val.trim().is_empty()).then(|| expand_path(val, home));
+            }
             if let Some(val) = behavior.awww_auto_start {
                 self.awww_auto_start = val;
             }
+            if let Some(val) = behavior.warn_unknown_config {
+                self.warn_unknown_config = val;
+            }
+            if let Some(val) = &behavior.on_apply_cmd {
+                self.on_apply_cmd = (!val.trim().is_empty()).then(|| val.clone());
+            }
+            if let Some(val) = behavior.notify_on_change {
+                self.notify_on_change = val;
+            }
+            if let Some(val) = behavior.git_retries {
+                self.git_retries = val;
+            }
+            if let Some(val) = behavior.restart_wait_ms {
+                self.restart_wait_ms = val;
+            }
+            if let Some(val) = behavior.command_timeout_ms {
+                self.command_timeout_ms = val;
+            }
+            if let Some(val) = behavior.parallel_setters {
+                self.parallel_setters = val;
+            }
+            if let Some(val) = behavior.run_theme_hooks {
+                self.run_theme_hooks = val;
+            }
         }
     }
 
@@ -342,6 +937,13 @@ impl ResolvedConfig {
         if let Ok(val) = env::var("THEME_ROOT_DIR") {
             self.theme_root_dir = expand_path(&val, home);
         }
+        if let Ok(val) = env::var("THEME_ROOT_DIRS") {
+            let dirs: Vec<String> = val.split(':').map(|s| s.to_string()).collect();
+            self.theme_root_dirs = clean_list(&dirs)
+                .iter()
+                .map(|dir| expand_path(dir, home))
+                .collect();
+        }
         if let Ok(val) = env::var("CURRENT_THEME_LINK") {
             self.current_theme_link = expand_path(&val, home);
         }
@@ -379,9 +981,15 @@ impl ResolvedConfig {
         if let Ok(val) = env::var("HYPRLOCK_THEMES_DIR") {
             self.hyprlock_themes_dir = expand_path(&val, home);
         }
+        if let Ok(val) = env::var("HYPRLOCK_HOST") {
+            self.hyprlock_host = Some(expand_path(&val, home));
+        }
         if let Ok(val) = env::var("HYPRLOCK_APPLY_MODE") {
             self.hyprlock_apply_mode = val;
         }
+        if let Ok(val) = env::var("HYPRLOCK_RESTART_CMD") {
+            self.hyprlock_restart_cmd = Some(val);
+        }
         if let Ok(val) = env::var("DEFAULT_HYPRLOCK_MODE") {
             self.default_hyprlock_mode = Some(val);
         }
@@ -391,6 +999,9 @@ impl ResolvedConfig {
         if let Ok(val) = env::var("WALKER_APPLY_MODE") {
             self.walker_apply_mode = val;
         }
+        if let Ok(val) = env::var("WALKER_RESTART_CMD") {
+            self.walker_restart_cmd = Some(val);
+        }
         if let Ok(val) = env::var("DEFAULT_WALKER_MODE") {
             self.default_walker_mode = Some(val);
         }
@@ -422,6 +1033,16 @@ impl ResolvedConfig {
         if let Ok(val) = env::var("STARSHIP_THEMES_DIR") {
             self.starship_themes_dir = expand_path(&val, home);
         }
+        if let Ok(val) = env::var("THEME_MANAGER_LOCK_FILE") {
+            if !val.is_empty() {
+                self.lock_file = expand_path(&val, home);
+            }
+        }
+        if let Ok(val) = env::var("THEME_MANAGER_HISTORY_FILE") {
+            if !val.is_empty() {
+                self.history_file = expand_path(&val, home);
+            }
+        }
         if let Ok(val) = env::var("DEFAULT_STARSHIP_MODE") {
             self.default_starship_mode = Some(val);
         }
@@ -440,11 +1061,7 @@ impl ResolvedConfig {
             self.quiet_default = true;
         }
         if let Ok(val) = env::var("THEME_MANAGER_AWWW_TRANSITION") {
-            if val == "0" || val.eq_ignore_ascii_case("false") {
-                self.awww_transition = false;
-            } else {
-                self.awww_transition = true;
-            }
+            self.awww_transition = !(val == "0" || val.eq_ignore_ascii_case("false"));
         }
         if let Ok(val) = env::var("THEME_MANAGER_AWWW_AUTO_START") {
             if val == "1" || val.eq_ignore_ascii_case("true") {
@@ -466,19 +1083,354 @@ impl ResolvedConfig {
                 self.awww_transition_wave = val;
             }
         }
+        if let Ok(val) = env::var("THEME_MANAGER_AWWW_OUTPUTS") {
+            let outputs: Vec<String> = val.split(',').map(|s| s.to_string()).collect();
+            self.awww_outputs = clean_list(&outputs);
+        }
+        if let Ok(val) = env::var("THEME_MANAGER_ON_APPLY_CMD") {
+            self.on_apply_cmd = (!val.trim().is_empty()).then_some(val);
+        }
         Ok(())
     }
 }
 
-fn load_toml(path: &Path) -> Result<Option<FileConfig>> {
+fn clean_list(values: &[String]) -> Vec<String> {
+    values
+        .iter()
+        .map(|val| val.trim().to_string())
+        .filter(|val| !val.is_empty())
+        .collect()
+}
+
+pub fn user_config_path(home: &Path) -> PathBuf {
+    home.join(".config/theme-manager/config.toml")
+}
+
+pub fn profile_config_path(home: &Path, name: &str) -> Result<PathBuf> {
+    let name = name.trim();
+    if name.is_empty() || name.contains('/') || name.starts_with('.') {
+        return Err(anyhow!("invalid profile name: {name:?}"));
+    }
+    Ok(home
+        .join(".config/theme-manager/profiles")
+        .join(format!("{name}.toml")))
+}
+
+// The file `config edit`/`config path` act on: the profile when one is given (it is
+// layered last), else `--config`, else the user config.
+fn effective_config_path(
+    home: &Path,
+    config_path: Option<&Path>,
+    profile: Option<&str>,
+) -> Result<PathBuf> {
+    if let Some(name) = profile {
+        return profile_config_path(home, name);
+    }
+    Ok(config_path
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| user_config_path(home)))
+}
+
+pub fn cmd_config_path(config_path: Option<&Path>, profile: Option<&str>) -> Result<()> {
+    let home = env::var("HOME").map_err(|_| anyhow!("HOME is not set"))?;
+    let path = effective_config_path(Path::new(&home), config_path, profile)?;
+    println!("{}", path.to_string_lossy());
+    Ok(())
+}
+
+pub fn cmd_config_explain(key: Option<&str>) -> Result<()> {
+    let home = env::var("HOME").map_err(|_| anyhow!("HOME is not set"))?;
+    let defaults = ResolvedConfig::defaults(Path::new(&home));
+    let docs: Vec<&ConfigKeyDoc> = match key {
+        Some(key) => {
+            let doc = CONFIG_KEYS
+                .iter()
+                .find(|doc| doc.key == key)
+                .ok_or_else(|| anyhow!("unknown config key: {key}"))?;
+            vec![doc]
+        }
+        None => CONFIG_KEYS.iter().collect(),
+    };
+    for (idx, doc) in docs.iter().enumerate() {
+        if idx > 0 {
+            println!();
+        }
+        println!("{}", doc.key);
+        println!("  type: {}", doc.ty);
+        println!("  default: {}", (doc.default)(&defaults));
+        println!("  env: {}", doc.env.unwrap_or("(none)"));
+    }
+    Ok(())
+}
+
+pub fn cmd_config_edit(config_path: Option<&Path>, profile: Option<&str>) -> Result<()> {
+    let home = env::var("HOME").map_err(|_| anyhow!("HOME is not set"))?;
+    let home_path = PathBuf::from(&home);
+    let path = effective_config_path(&home_path, config_path, profile)?;
+    if !path.exists() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, config_template(&home_path))?;
+    }
+
+    let editor = env::var("VISUAL")
+        .ok()
+        .filter(|val| !val.trim().is_empty())
+        .or_else(|| env::var("EDITOR").ok().filter(|val| !val.trim().is_empty()))
+        .ok_or_else(|| {
+            anyhow!(
+                "EDITOR is not set; edit {} manually",
+                path.to_string_lossy()
+            )
+        })?;
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().ok_or_else(|| anyhow!("EDITOR is not set"))?;
+    let status = Command::new(program).args(parts).arg(&path).status()?;
+    if !status.success() {
+        return Err(anyhow!("{program} exited with {status}"));
+    }
+    Ok(())
+}
+
+pub fn config_template(home: &Path) -> String {
+    let d = ResolvedConfig::defaults(home);
+    let path = |p: &Path| display_path(p, home);
+    format!(
+        r#"# Theme Manager+ config
+# Uncomment a key to override its default. Local ./.theme-manager.toml overrides this file;
+# environment variables and CLI flags override both.
+
+[paths]
+# theme_root_dir = "{theme_root_dir}"
+# theme_root_dirs = [] # extra theme roots searched after theme_root_dir; first match wins
+# current_theme_link = "{current_theme_link}"
+# current_background_link = "{current_background_link}"
+# omarchy_bin_dir = "~/.local/share/omarchy/bin"
+# extra_bin_dirs = [] # searched after omarchy_bin_dir for helpers such as custom omarchy-theme-set-* scripts
+# waybar_dir = "{waybar_dir}"
+# waybar_themes_dir = "{waybar_themes_dir}"
+# walker_dir = "{walker_dir}"
+# walker_themes_dir = "{walker_themes_dir}"
+# hyprlock_dir = "{hyprlock_dir}"
+# hyprlock_themes_dir = "{hyprlock_themes_dir}"
+# hyprlock_host = "{hyprlock_host}" # host hyprlock.conf that sources the current theme
+# starship_config = "{starship_config}"
+# starship_themes_dir = "{starship_themes_dir}"
+# lock_file = "{lock_file}"
+# history_file = "{history_file}"
+
+[waybar]
+# apply_mode = "{waybar_apply_mode}" # symlink|copy|hardlink
+# restart_cmd = "" # custom restart command run via sh -c, or "builtin" to pkill and relaunch waybar
+# restart_logs = false
+# default_mode = "" # auto|named|"" (empty = none)
+# default_name = "" # e.g. "omarchy-default"
+# include_optional_files = {waybar_include_optional_files} # also link/copy loose files like colors.css
+
+[walker]
+# apply_mode = "{walker_apply_mode}" # symlink|copy
+# restart_cmd = "" # custom restart command run via sh -c (default: omarchy-restart-walker)
+# default_mode = "" # auto|named|"" (empty = none)
+# default_name = "" # e.g. "omarchy-default"
+
+[hyprlock]
+# apply_mode = "{hyprlock_apply_mode}" # symlink|copy
+# restart_cmd = "" # custom restart command run via sh -c, or "noop" to skip (default: omarchy-restart-hyprlock)
+# default_mode = "" # auto|named|"" (empty = none)
+# default_name = "" # e.g. "omarchy-default"
+
+[starship]
+# default_mode = "" # preset|named|theme|"" (empty = none)
+# default_preset = ""
+# default_name = "" # e.g. "omarchy-default"
+
+[tui]
+# apply_key = "ctrl+enter" # examples: "ctrl+enter", "ctrl+m", "enter"
+# code_theme = "base16-ocean.dark" # syntax colors for code previews, e.g. "InspiredGitHub", "Solarized (dark)"
+# color_preview = {tui_color_preview} # false = plain, unhighlighted code previews
+
+[behavior]
+# quiet_default = {quiet_default}
+# awww_transition = {awww_transition}
+# awww_transition_type = "{awww_transition_type}"
+# awww_transition_duration = {awww_transition_duration}
+# awww_transition_angle = {awww_transition_angle}
+# awww_transition_fps = {awww_transition_fps}
+# awww_transition_pos = "{awww_transition_pos}"
+# awww_transition_bezier = "{awww_transition_bezier}"
+# awww_transition_wave = "{awww_transition_wave}"
+# awww_outputs = [] # e.g. ["DP-1", "HDMI-A-1"]; empty = all outputs
+# awww_log_file = "" # append each awww command and its full output here
+# awww_auto_start = {awww_auto_start}
+# warn_unknown_config = {warn_unknown_config} # warn about misspelled or unsupported keys
+# on_apply_cmd = "" # run after a successful switch, e.g. "paplay ~/sounds/chime.ogg"
+# notify_on_change = {notify_on_change} # desktop notification via notify-send after a switch
+# git_retries = {git_retries} # extra `git clone` attempts for `install` on failure
+# restart_wait_ms = {restart_wait_ms} # how long `set --wait` / `next --wait` wait for waybar
+# command_timeout_ms = {command_timeout_ms} # kill hung Omarchy restart/setter helpers after this long (0 = never)
+# parallel_setters = {parallel_setters} # run the omarchy-theme-set-* helpers concurrently
+# run_theme_hooks = {run_theme_hooks} # run a theme's own hooks/theme-set after the global hook
+"#,
+        theme_root_dir = path(&d.theme_root_dir),
+        current_theme_link = path(&d.current_theme_link),
+        current_background_link = path(&d.current_background_link),
+        waybar_dir = path(&d.waybar_dir),
+        waybar_themes_dir = path(&d.waybar_themes_dir),
+        walker_dir = path(&d.walker_dir),
+        walker_themes_dir = path(&d.walker_themes_dir),
+        hyprlock_dir = path(&d.hyprlock_dir),
+        hyprlock_themes_dir = path(&d.hyprlock_themes_dir),
+        hyprlock_host = path(&d.hyprlock_host_path()),
+        starship_config = path(&d.starship_config),
+        starship_themes_dir = path(&d.starship_themes_dir),
+        lock_file = path(&d.lock_file),
+        history_file = path(&d.history_file),
+        waybar_apply_mode = d.waybar_apply_mode,
+        waybar_include_optional_files = d.waybar_include_optional_files,
+        walker_apply_mode = d.walker_apply_mode,
+        hyprlock_apply_mode = d.hyprlock_apply_mode,
+        tui_color_preview = d.tui_color_preview,
+        quiet_default = d.quiet_default,
+        awww_transition = d.awww_transition,
+        awww_transition_type = d.awww_transition_type,
+        awww_transition_duration = d.awww_transition_duration,
+        awww_transition_angle = d.awww_transition_angle,
+        awww_transition_fps = d.awww_transition_fps,
+        awww_transition_pos = d.awww_transition_pos,
+        awww_transition_bezier = d.awww_transition_bezier,
+        awww_transition_wave = d.awww_transition_wave,
+        awww_auto_start = d.awww_auto_start,
+        warn_unknown_config = d.warn_unknown_config,
+        notify_on_change = d.notify_on_change,
+        git_retries = d.git_retries,
+        restart_wait_ms = d.restart_wait_ms,
+        command_timeout_ms = d.command_timeout_ms,
+        parallel_setters = d.parallel_setters,
+        run_theme_hooks = d.run_theme_hooks,
+    )
+}
+
+fn display_path(path: &Path, home: &Path) -> String {
+    match path.strip_prefix(home) {
+        Ok(rest) => format!("~/{}", rest.to_string_lossy()),
+        Err(_) => path.to_string_lossy().to_string(),
+    }
+}
+
+fn env_flag(name: &str) -> bool {
+    env::var(name).is_ok_and(|val| val == "1" || val.eq_ignore_ascii_case("true"))
+}
+
+// Removes the keys an untrusted local config may not set: every `[paths]` key plus
+// the ones that run commands or write files. Returns their dotted names.
+fn strip_untrusted_keys(cfg: &mut FileConfig) -> Vec<String> {
+    let mut ignored: Vec<String> = cfg
+        .paths
+        .take()
+        .map(|paths| paths.set_keys())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|key| format!("paths.{key}"))
+        .collect();
+    let mut strip = |key: &str, set: bool| {
+        if set {
+            ignored.push(key.to_string());
+        }
+    };
+    if let Some(waybar) = &mut cfg.waybar {
+        strip("waybar.restart_cmd", waybar.restart_cmd.take().is_some());
+    }
+    if let Some(walker) = &mut cfg.walker {
+        strip("walker.restart_cmd", walker.restart_cmd.take().is_some());
+    }
+    if let Some(hyprlock) = &mut cfg.hyprlock {
+        strip(
+            "hyprlock.restart_cmd",
+            hyprlock.restart_cmd.take().is_some(),
+        );
+    }
+    if let Some(behavior) = &mut cfg.behavior {
+        strip(
+            "behavior.on_apply_cmd",
+            behavior.on_apply_cmd.take().is_some(),
+        );
+        strip(
+            "behavior.run_theme_hooks",
+            behavior.run_theme_hooks.take().is_some(),
+        );
+        strip(
+            "behavior.awww_log_file",
+            behavior.awww_log_file.take().is_some(),
+        );
+    }
+    ignored
+}
+
+fn warn_untrusted_local_keys(keys: &[String], local_path: &Path) {
+    if keys.is_empty() {
+        return;
+    }
+    let keys = keys.join(", ");
+    eprintln!(
+        "{} ignoring {keys} from untrusted {}; pass --trust-local or set THEME_MANAGER_TRUST_LOCAL=1 to apply them",
+        crate::output::warning_prefix(),
+        local_path.to_string_lossy()
+    );
+}
+
+fn load_toml(path: &Path, unknown_keys: &mut Vec<String>) -> Result<Option<FileConfig>> {
     if !path.is_file() {
         return Ok(None);
     }
     let content = fs::read_to_string(path)?;
-    let cfg: FileConfig = toml::from_str(&content)?;
+    let cfg: FileConfig = toml::from_str(&content).map_err(|err| {
+        let message = match err.span() {
+            Some(span) => {
+                let line = content[..span.start].matches('\n').count() + 1;
+                format!("line {line}: {}", err.message())
+            }
+            None => err.message().to_string(),
+        };
+        ThemeError::ConfigParse {
+            path: path.to_path_buf(),
+            message,
+        }
+    })?;
+    if let Ok(value) = toml::from_str::<toml::Value>(&content) {
+        for key in unknown_config_keys(&value) {
+            unknown_keys.push(format!(
+                "unknown config key `{key}` in {}",
+                path.to_string_lossy()
+            ));
+        }
+    }
     Ok(Some(cfg))
 }
 
+fn unknown_config_keys(value: &toml::Value) -> Vec<String> {
+    let mut unknown = Vec::new();
+    let Some(table) = value.as_table() else {
+        return unknown;
+    };
+    for (section, section_value) in table {
+        let prefix = format!("{section}.");
+        if !CONFIG_KEYS.iter().any(|doc| doc.key.starts_with(&prefix)) {
+            unknown.push(section.clone());
+            continue;
+        }
+        if let Some(section_table) = section_value.as_table() {
+            for key in section_table.keys() {
+                let full = format!("{section}.{key}");
+                if !CONFIG_KEYS.iter().any(|doc| doc.key == full) {
+                    unknown.push(full);
+                }
+            }
+        }
+    }
+    unknown
+}
+
 fn expand_path(path: &str, home: &Path) -> PathBuf {
     let mut expanded = path.replace("${HOME}", &home.to_string_lossy());
     expanded = expanded.replace("$HOME", &home.to_string_lossy());
@@ -505,6 +1457,15 @@ fn current_dir() -> Result<PathBuf> {
 
 pub fn print_config(config: &ResolvedConfig) {
     println!("THEME_ROOT_DIR={}", config.theme_root_dir.to_string_lossy());
+    println!(
+        "THEME_ROOT_DIRS={}",
+        config
+            .theme_root_dirs
+            .iter()
+            .map(|dir| dir.to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join(":")
+    );
     println!(
         "CURRENT_THEME_LINK={}",
         config.current_theme_link.to_string_lossy()
@@ -521,6 +1482,15 @@ pub fn print_config(config: &ResolvedConfig) {
             .map(|p| p.to_string_lossy().to_string())
             .unwrap_or_default()
     );
+    println!(
+        "EXTRA_BIN_DIRS={}",
+        config
+            .extra_bin_dirs
+            .iter()
+            .map(|dir| dir.to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join(":")
+    );
     println!("WAYBAR_DIR={}", config.waybar_dir.to_string_lossy());
     println!(
         "WAYBAR_THEMES_DIR={}",
@@ -535,12 +1505,24 @@ pub fn print_config(config: &ResolvedConfig) {
         "WAYBAR_RESTART_LOGS={}",
         if config.waybar_restart_logs { "1" } else { "" }
     );
+    println!(
+        "WAYBAR_INCLUDE_OPTIONAL_FILES={}",
+        if config.waybar_include_optional_files {
+            "1"
+        } else {
+            ""
+        }
+    );
     println!("WALKER_DIR={}", config.walker_dir.to_string_lossy());
     println!(
         "WALKER_THEMES_DIR={}",
         config.walker_themes_dir.to_string_lossy()
     );
     println!("WALKER_APPLY_MODE={}", config.walker_apply_mode);
+    println!(
+        "WALKER_RESTART_CMD={}",
+        config.walker_restart_cmd.as_deref().unwrap_or("")
+    );
     println!(
         "DEFAULT_WALKER_MODE={}",
         config.default_walker_mode.as_deref().unwrap_or("")
@@ -554,7 +1536,15 @@ pub fn print_config(config: &ResolvedConfig) {
         "HYPRLOCK_THEMES_DIR={}",
         config.hyprlock_themes_dir.to_string_lossy()
     );
+    println!(
+        "HYPRLOCK_HOST={}",
+        config.hyprlock_host_path().to_string_lossy()
+    );
     println!("HYPRLOCK_APPLY_MODE={}", config.hyprlock_apply_mode);
+    println!(
+        "HYPRLOCK_RESTART_CMD={}",
+        config.hyprlock_restart_cmd.as_deref().unwrap_or("")
+    );
     println!(
         "DEFAULT_HYPRLOCK_MODE={}",
         config.default_hyprlock_mode.as_deref().unwrap_or("")
@@ -571,6 +1561,9 @@ pub fn print_config(config: &ResolvedConfig) {
         "STARSHIP_THEMES_DIR={}",
         config.starship_themes_dir.to_string_lossy()
     );
+    println!("LOCK_FILE={}", config.lock_file.to_string_lossy());
+    println!("HISTORY_FILE={}", config.history_file.to_string_lossy());
+    println!("PRESETS_FILE={}", config.presets_file.to_string_lossy());
     println!(
         "DEFAULT_WAYBAR_MODE={}",
         config.default_waybar_mode.as_deref().unwrap_or("")
@@ -595,6 +1588,14 @@ pub fn print_config(config: &ResolvedConfig) {
         "TUI_APPLY_KEY={}",
         config.tui_apply_key.as_deref().unwrap_or("")
     );
+    println!(
+        "TUI_CODE_THEME={}",
+        config.tui_code_theme.as_deref().unwrap_or("")
+    );
+    println!(
+        "TUI_COLOR_PREVIEW={}",
+        if config.tui_color_preview { "1" } else { "" }
+    );
     println!(
         "QUIET_MODE_DEFAULT={}",
         if config.quiet_default { "1" } else { "" }
@@ -614,8 +1615,40 @@ pub fn print_config(config: &ResolvedConfig) {
     println!("AWWW_TRANSITION_POS={}", config.awww_transition_pos);
     println!("AWWW_TRANSITION_BEZIER={}", config.awww_transition_bezier);
     println!("AWWW_TRANSITION_WAVE={}", config.awww_transition_wave);
+    println!("AWWW_OUTPUTS={}", config.awww_outputs.join(","));
+    println!(
+        "AWWW_LOG_FILE={}",
+        config
+            .awww_log_file
+            .as_deref()
+            .map(|path| path.to_string_lossy().to_string())
+            .unwrap_or_default()
+    );
     println!(
         "AWWW_AUTO_START={}",
         if config.awww_auto_start { "1" } else { "" }
     );
+    println!(
+        "WARN_UNKNOWN_CONFIG={}",
+        if config.warn_unknown_config { "1" } else { "" }
+    );
+    println!(
+        "NOTIFY_ON_CHANGE={}",
+        if config.notify_on_change { "1" } else { "" }
+    );
+    println!(
+        "ON_APPLY_CMD={}",
+        config.on_apply_cmd.as_deref().unwrap_or("")
+    );
+    println!("GIT_RETRIES={}", config.git_retries);
+    println!("RESTART_WAIT_MS={}", config.restart_wait_ms);
+    println!("COMMAND_TIMEOUT_MS={}", config.command_timeout_ms);
+    println!(
+        "PARALLEL_SETTERS={}",
+        if config.parallel_setters { "1" } else { "" }
+    );
+    println!(
+        "RUN_THEME_HOOKS={}",
+        if config.run_theme_hooks { "1" } else { "" }
+    );
 }
@@ -0,0 +1,70 @@
+use std::fmt;
+use std::path::PathBuf;
+
+// Failures callers may want to tell apart; everything else stays a plain `anyhow` error.
+#[derive(Debug)]
+pub enum ThemeError {
+    ThemeNotFound {
+        name: String,
+        requested: Option<String>,
+    },
+    BrokenSymlink(PathBuf),
+    ComponentMissing {
+        component: &'static str,
+        path: PathBuf,
+    },
+    ConfigParse {
+        path: PathBuf,
+        message: String,
+    },
+    GitFailed(String),
+}
+
+impl ThemeError {
+    pub fn theme_not_found(name: &str) -> Self {
+        ThemeError::ThemeNotFound {
+            name: name.to_string(),
+            requested: None,
+        }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ThemeError::ThemeNotFound { .. } => 2,
+            ThemeError::ConfigParse { .. } => 3,
+            ThemeError::GitFailed(_) => 4,
+            ThemeError::BrokenSymlink(_) => 5,
+            ThemeError::ComponentMissing { .. } => 6,
+        }
+    }
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeError::ThemeNotFound {
+                name,
+                requested: Some(requested),
+            } => write!(f, "theme not found: {name} (from '{requested}')"),
+            ThemeError::ThemeNotFound { name, .. } => write!(f, "theme not found: {name}"),
+            ThemeError::BrokenSymlink(path) => {
+                write!(f, "theme symlink is broken: {}", path.to_string_lossy())
+            }
+            ThemeError::ComponentMissing { component, path } => {
+                write!(f, "{component} theme not found: {}", path.to_string_lossy())
+            }
+            ThemeError::ConfigParse { path, message } => {
+                write!(f, "invalid config {}: {message}", path.to_string_lossy())
+            }
+            ThemeError::GitFailed(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+// Exit status for `main`: 1 for untyped errors, otherwise the `ThemeError` code.
+pub fn exit_code(err: &anyhow::Error) -> i32 {
+    err.downcast_ref::<ThemeError>()
+        .map_or(1, ThemeError::exit_code)
+}
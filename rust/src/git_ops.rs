@@ -1,18 +1,23 @@
 use anyhow::{anyhow, Result};
 use std::fs;
+use std::io::{IsTerminal, Write};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::thread;
+use std::time::Duration;
 
 use crate::config::ResolvedConfig;
+use crate::error::ThemeError;
 use crate::omarchy;
 use crate::paths::normalize_theme_name;
 use crate::theme_ops::{self, hyprlock_from_defaults, walker_from_defaults, CommandContext};
+use crate::tui;
 
 pub struct GitContext<'a> {
     pub config: &'a ResolvedConfig,
 }
 
-pub fn cmd_install(ctx: &GitContext<'_>, git_url: &str) -> Result<()> {
+pub fn cmd_install(ctx: &GitContext<'_>, git_url: &str, apply: bool, force: bool) -> Result<()> {
     if git_url.trim().is_empty() {
         return Err(anyhow!("missing git URL"));
     }
@@ -25,23 +30,48 @@ pub fn cmd_install(ctx: &GitContext<'_>, git_url: &str) -> Result<()> {
 
     fs::create_dir_all(&ctx.config.theme_root_dir)?;
     let theme_path = ctx.config.theme_root_dir.join(&theme_name);
-    if theme_path.exists() {
-        return Err(anyhow!("theme already exists: {theme_name}"));
+    if theme_path.exists() || is_symlink(&theme_path)? {
+        if !force {
+            return Err(anyhow!("theme already exists: {theme_name}"));
+        }
+        if is_current_theme(ctx.config, &theme_name)? {
+            let entries = theme_ops::list_theme_entries(&ctx.config.theme_root_dir)?;
+            if entries.len() <= 1 {
+                return Err(anyhow!("cannot remove the only theme"));
+            }
+        }
+        println!("theme-manager: replacing {}", theme_path.to_string_lossy());
+        replace_with_clone(ctx, git_url, &theme_name, &theme_path)?;
+    } else {
+        clone_with_retries(git_url, &theme_path, ctx.config.git_retries)?;
     }
 
-    let status = Command::new("git")
-        .args(["clone", git_url, theme_path.to_string_lossy().as_ref()])
-        .status()?;
-    if !status.success() {
-        return Err(anyhow!("git clone failed"));
+    if !apply {
+        println!("theme-manager: installed {theme_name}");
+        return Ok(());
     }
-
     let command_ctx = default_command_context(ctx.config);
     theme_ops::cmd_set(&command_ctx, &theme_name)?;
     Ok(())
 }
 
-pub fn cmd_update(ctx: &GitContext<'_>) -> Result<()> {
+// `set --from-url`: clones the theme unless one with the derived name already
+// exists, and returns the name to apply.
+pub fn ensure_installed(ctx: &GitContext<'_>, git_url: &str) -> Result<String> {
+    if git_url.trim().is_empty() {
+        return Err(anyhow!("missing git URL"));
+    }
+    let theme_name = normalize_theme_name(&derive_repo_name(git_url));
+    let theme_path = ctx.config.theme_root_dir.join(&theme_name);
+    if theme_path.exists() || is_symlink(&theme_path)? {
+        println!("theme-manager: {theme_name} already installed");
+        return Ok(theme_name);
+    }
+    cmd_install(ctx, git_url, false, false)?;
+    Ok(theme_name)
+}
+
+pub fn cmd_update(ctx: &GitContext<'_>, quiet: bool) -> Result<()> {
     if !ctx.config.theme_root_dir.is_dir() {
         return Err(anyhow!(
             "themes directory not found: {}",
@@ -52,35 +82,65 @@ pub fn cmd_update(ctx: &GitContext<'_>) -> Result<()> {
         return Err(anyhow!("git is required to update themes"));
     }
 
-    let mut updated = 0;
+    let mut git_themes = Vec::new();
     for entry in fs::read_dir(&ctx.config.theme_root_dir)? {
         let entry = entry?;
         let path = resolve_entry(entry.path());
         if path.join(".git").is_dir() {
-            let status = Command::new("git")
-                .args(["-C", path.to_string_lossy().as_ref(), "pull"])
-                .status()?;
-            if status.success() {
-                updated += 1;
+            git_themes.push((entry.file_name().to_string_lossy().to_string(), path));
+        }
+    }
+    git_themes.sort();
+
+    // On a terminal the progress rewrites one line and git stays quiet; piped
+    // output gets a plain line per theme next to git's own output.
+    let tty = std::io::stdout().is_terminal();
+    let total = git_themes.len();
+    let mut failed = Vec::new();
+    for (idx, (name, path)) in git_themes.iter().enumerate() {
+        if !quiet {
+            let progress = format!("[{}/{total}] updating {name}...", idx + 1);
+            if tty {
+                print!("\r\x1b[2K{progress}");
+                let _ = std::io::stdout().flush();
+            } else {
+                println!("{progress}");
             }
         }
+        let mut command = Command::new("git");
+        command.args(["-C", path.to_string_lossy().as_ref(), "pull"]);
+        if tty && !quiet {
+            command.arg("--quiet");
+        }
+        if !command.status()?.success() {
+            failed.push(name.clone());
+        }
+    }
+    if tty && !quiet && total > 0 {
+        println!();
     }
 
-    if updated == 0 {
+    if total == 0 {
         eprintln!("theme-manager: no git-based themes found");
     }
+    if !failed.is_empty() {
+        failed.sort();
+        return Err(
+            ThemeError::GitFailed(format!("git pull failed for: {}", failed.join(", "))).into(),
+        );
+    }
     Ok(())
 }
 
 pub fn cmd_remove(ctx: &GitContext<'_>, theme: Option<&str>) -> Result<()> {
     let theme_name = match theme {
         Some(name) => normalize_theme_name(name),
-        None => select_removable_theme(&ctx.config.theme_root_dir)?,
+        None => select_removable_theme(ctx.config)?,
     };
 
     let theme_path = ctx.config.theme_root_dir.join(&theme_name);
     if !theme_path.exists() && !is_symlink(&theme_path)? {
-        return Err(anyhow!("theme not found: {theme_name}"));
+        return Err(ThemeError::theme_not_found(&theme_name).into());
     }
 
     if is_current_theme(ctx.config, &theme_name)? {
@@ -89,20 +149,128 @@ pub fn cmd_remove(ctx: &GitContext<'_>, theme: Option<&str>) -> Result<()> {
             return Err(anyhow!("cannot remove the only theme"));
         }
         let command_ctx = default_command_context(ctx.config);
-        theme_ops::cmd_next(&command_ctx)?;
+        theme_ops::cmd_next(&command_ctx, None, None, true, 1, None)?;
     }
 
     remove_path(&theme_path)?;
     Ok(())
 }
 
+pub fn cmd_link(ctx: &GitContext<'_>, name: &str, source: &Path) -> Result<()> {
+    let theme_name = normalize_theme_name(name);
+    if theme_name.is_empty() {
+        return Err(anyhow!("missing theme name"));
+    }
+    if !source.is_dir() {
+        return Err(anyhow!(
+            "theme source is not a directory: {}",
+            source.to_string_lossy()
+        ));
+    }
+    let source = fs::canonicalize(source)?;
+
+    fs::create_dir_all(&ctx.config.theme_root_dir)?;
+    let theme_path = ctx.config.theme_root_dir.join(&theme_name);
+    if theme_path.exists() || is_symlink(&theme_path)? {
+        return Err(anyhow!("theme already exists: {theme_name}"));
+    }
+
+    std::os::unix::fs::symlink(&source, &theme_path)?;
+    println!(
+        "theme-manager: linked {theme_name} -> {}",
+        source.to_string_lossy()
+    );
+    Ok(())
+}
+
+pub fn cmd_unlink(ctx: &GitContext<'_>, name: &str) -> Result<()> {
+    let theme_name = normalize_theme_name(name);
+    let theme_path = ctx.config.theme_root_dir.join(&theme_name);
+    if !is_symlink(&theme_path)? {
+        if theme_path.exists() {
+            return Err(anyhow!("not a linked theme: {theme_name}"));
+        }
+        return Err(ThemeError::theme_not_found(&theme_name).into());
+    }
+
+    fs::remove_file(&theme_path)?;
+    println!("theme-manager: unlinked {theme_name}");
+    Ok(())
+}
+
+// `install --force`: the existing theme is only swapped out once the new clone has
+// succeeded, so a failed clone leaves it untouched.
+fn replace_with_clone(
+    ctx: &GitContext<'_>,
+    git_url: &str,
+    theme_name: &str,
+    theme_path: &Path,
+) -> Result<()> {
+    let pid = std::process::id();
+    let staging = ctx
+        .config
+        .theme_root_dir
+        .join(format!(".{theme_name}.install-{pid}"));
+    let previous = ctx
+        .config
+        .theme_root_dir
+        .join(format!(".{theme_name}.previous-{pid}"));
+    remove_path(&staging)?;
+    if let Err(err) = clone_with_retries(git_url, &staging, ctx.config.git_retries) {
+        let _ = remove_path(&staging);
+        return Err(err);
+    }
+
+    fs::rename(theme_path, &previous)?;
+    if let Err(err) = fs::rename(&staging, theme_path) {
+        let _ = fs::rename(&previous, theme_path);
+        let _ = remove_path(&staging);
+        return Err(err.into());
+    }
+    remove_path(&previous)
+}
+
+// Retries a failed clone `retries` more times, doubling the delay each time and
+// removing any partial checkout before the next attempt.
+fn clone_with_retries(git_url: &str, theme_path: &Path, retries: u32) -> Result<()> {
+    let attempts = retries + 1;
+    let mut delay = Duration::from_millis(500);
+    for attempt in 1..=attempts {
+        let status = Command::new("git")
+            .args(["clone", git_url, theme_path.to_string_lossy().as_ref()])
+            .status()?;
+        if status.success() {
+            return Ok(());
+        }
+        if theme_path.exists() {
+            fs::remove_dir_all(theme_path)?;
+        }
+        if attempt < attempts {
+            eprintln!(
+                "theme-manager: git clone failed (attempt {attempt}/{attempts}); retrying in {}ms",
+                delay.as_millis()
+            );
+            thread::sleep(delay);
+            delay *= 2;
+        }
+    }
+    Err(ThemeError::GitFailed(format!("git clone failed after {attempts} attempt(s)")).into())
+}
+
+// Accepts `scheme://host/path`, scp-style `user@host:path`, and local paths.
 fn derive_repo_name(git_url: &str) -> String {
-    let name = git_url
-        .trim_end_matches('/')
-        .split('/')
-        .last()
-        .unwrap_or(git_url);
-    let name = name.trim_end_matches(".git");
+    let url = git_url.trim();
+    let url = url.split(['?', '#']).next().unwrap_or(url);
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/').map_or("", |(_, path)| path),
+        None => match url.split_once(':') {
+            Some((host, path)) if !host.contains('/') => path,
+            _ => url,
+        },
+    };
+    let path = path.trim_end_matches('/');
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let name = name.strip_suffix(".git").unwrap_or(name);
     let name = name.strip_prefix("omarchy-").unwrap_or(name);
     let name = name.strip_suffix("-theme").unwrap_or(name);
     name.to_string()
@@ -140,10 +308,17 @@ fn default_command_context<'a>(config: &'a ResolvedConfig) -> CommandContext<'a>
         hyprlock_name,
         starship_mode,
         debug_awww: false,
+        print_awww_cmd: false,
+        force_bg: false,
+        background: theme_ops::BackgroundChoice::Next,
+        wait_for_waybar: false,
+        timings: false,
+        rng: theme_ops::seeded_rng(config.seed),
     }
 }
 
-fn select_removable_theme(theme_root: &Path) -> Result<String> {
+fn select_removable_theme(config: &ResolvedConfig) -> Result<String> {
+    let theme_root = &config.theme_root_dir;
     let mut extras = Vec::new();
     for entry in fs::read_dir(theme_root)? {
         let entry = entry?;
@@ -161,6 +336,11 @@ fn select_removable_theme(theme_root: &Path) -> Result<String> {
 
     extras.sort();
 
+    if std::io::stdin().is_terminal() && std::io::stdout().is_terminal() {
+        return tui::pick_theme(config, "Select theme to remove", theme_root, &extras)?
+            .ok_or_else(|| anyhow!("remove cancelled"));
+    }
+
     println!("Select a theme to remove:");
     for (idx, name) in extras.iter().enumerate() {
         println!("{:>2}) {}", idx + 1, name);
@@ -201,3 +381,25 @@ fn remove_path(path: &Path) -> Result<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derive_repo_name_handles_url_forms() {
+        for url in [
+            "https://github.com/user/omarchy-foo-theme",
+            "https://github.com/user/omarchy-foo-theme.git",
+            "https://github.com/user/omarchy-foo-theme/",
+            "https://github.com/user/omarchy-foo-theme.git?token=abc/def",
+            "https://github.com/user/omarchy-foo-theme#main",
+            "ssh://git@github.com/user/omarchy-foo-theme.git",
+            "git@github.com:user/omarchy-foo-theme.git",
+            "git@github.com:omarchy-foo-theme.git",
+            "/srv/git/omarchy-foo-theme",
+        ] {
+            assert_eq!(derive_repo_name(url), "foo", "{url}");
+        }
+    }
+}
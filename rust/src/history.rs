@@ -0,0 +1,146 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config::ResolvedConfig;
+use crate::theme_ops::{CommandContext, HyprlockMode, StarshipMode, WalkerMode, WaybarMode};
+
+const HISTORY_LIMIT: usize = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: u64,
+    pub theme: String,
+    // Component specs use the same syntax as `preset save` flags.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub waybar: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub walker: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hyprlock: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub starship: Option<String>,
+}
+
+pub fn read_entries(path: &Path) -> Result<Vec<HistoryEntry>> {
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(path)?;
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+pub fn record(ctx: &CommandContext<'_>, theme: &str) -> Result<()> {
+    let path = &ctx.config.history_file;
+    let mut entries = read_entries(path)?;
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    entries.push(HistoryEntry {
+        timestamp,
+        theme: theme.to_string(),
+        waybar: Some(named_spec(
+            matches!(ctx.waybar_mode, WaybarMode::Auto),
+            matches!(ctx.waybar_mode, WaybarMode::Named),
+            ctx.waybar_name.as_deref(),
+        )),
+        walker: Some(named_spec(
+            matches!(ctx.walker_mode, WalkerMode::Auto),
+            matches!(ctx.walker_mode, WalkerMode::Named),
+            ctx.walker_name.as_deref(),
+        )),
+        hyprlock: Some(named_spec(
+            matches!(ctx.hyprlock_mode, HyprlockMode::Auto),
+            matches!(ctx.hyprlock_mode, HyprlockMode::Named),
+            ctx.hyprlock_name.as_deref(),
+        )),
+        starship: Some(starship_spec(&ctx.starship_mode)),
+    });
+    let skip = entries.len().saturating_sub(HISTORY_LIMIT);
+
+    let mut content = String::new();
+    for entry in &entries[skip..] {
+        content.push_str(&serde_json::to_string(entry)?);
+        content.push('\n');
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)?;
+    Ok(())
+}
+
+pub fn previous_theme(config: &ResolvedConfig, current: Option<&str>) -> Result<String> {
+    let entries = read_entries(&config.history_file)?;
+    entries
+        .iter()
+        .rev()
+        .map(|entry| entry.theme.as_str())
+        .find(|theme| Some(*theme) != current)
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("no previous theme in history"))
+}
+
+pub fn undo_target(config: &ResolvedConfig) -> Result<HistoryEntry> {
+    let mut entries = read_entries(&config.history_file)?;
+    if entries.len() < 2 {
+        return Err(anyhow!(
+            "nothing to undo: history needs at least two theme switches"
+        ));
+    }
+    let index = entries.len() - 2;
+    Ok(entries.swap_remove(index))
+}
+
+pub fn cmd_history(config: &ResolvedConfig, limit: usize) -> Result<()> {
+    let entries = read_entries(&config.history_file)?;
+    let skip = entries.len().saturating_sub(limit);
+    for entry in &entries[skip..] {
+        println!("{}  {}", format_timestamp(entry.timestamp), entry.theme);
+    }
+    Ok(())
+}
+
+fn named_spec(auto: bool, named: bool, name: Option<&str>) -> String {
+    match name {
+        Some(name) if named => name.to_string(),
+        _ if auto => "auto".to_string(),
+        _ => "none".to_string(),
+    }
+}
+
+fn starship_spec(mode: &StarshipMode) -> String {
+    match mode {
+        StarshipMode::None => "none".to_string(),
+        StarshipMode::Preset { preset } => format!("preset:{preset}"),
+        StarshipMode::Named { name } => format!("named:{name}"),
+        StarshipMode::Theme { .. } => "theme".to_string(),
+    }
+}
+
+fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let secs = timestamp % 86_400;
+    // Civil-from-days conversion (proleptic Gregorian calendar, UTC).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+        secs / 3_600,
+        (secs % 3_600) / 60,
+        secs % 60
+    )
+}
@@ -6,10 +6,12 @@ use crate::config::ResolvedConfig;
 use crate::omarchy;
 use crate::omarchy_defaults;
 use crate::omarchy_defaults::SymlinkEnsureResult;
+use crate::output;
 use crate::paths::current_theme_name;
-use crate::theme_ops::{CommandContext, HyprlockMode};
+use crate::theme_ops::{self, CommandContext, HyprlockMode};
 
 const OMARCHY_DEFAULT_THEME_NAME: &str = "omarchy-default";
+const MANAGED_MARKER: &str = "# managed-by: theme-manager";
 const CURRENT_THEME_SOURCE_SUFFIX: &str = "/.config/omarchy/current/theme/hyprlock.conf";
 const MINIMAL_SOURCE_ONLY_HYPRLOCK: &str = r#"source = ~/.config/omarchy/current/theme/hyprlock.conf
 
@@ -84,12 +86,9 @@ fn apply_omarchy_default_theme_hyprlock(ctx: &CommandContext<'_>, theme_dir: &Pa
 
     // In standalone hyprlock flow, recover source from current theme name if possible.
     if let Some(theme_name) = current_theme_name(&ctx.config.current_theme_link)? {
-        candidates.push(
-            ctx.config
-                .theme_root_dir
-                .join(theme_name)
-                .join("hyprlock.conf"),
-        );
+        if let Ok(theme_path) = theme_ops::resolve_theme_source(ctx.config, &theme_name) {
+            candidates.push(theme_path.join("hyprlock.conf"));
+        }
     }
 
     let Some(source_config) = candidates.into_iter().find(|p| p.is_file()) else {
@@ -110,30 +109,40 @@ fn apply_omarchy_default_theme_hyprlock(ctx: &CommandContext<'_>, theme_dir: &Pa
 }
 
 fn ensure_main_hyprlock_mode(ctx: &CommandContext<'_>, source_config: &Path) -> Result<()> {
-    let hyprlock_main = ctx.config.hyprlock_dir.join("hyprlock.conf");
+    let hyprlock_main = ctx.config.hyprlock_host_path();
     if let Some(parent) = hyprlock_main.parent() {
         fs::create_dir_all(parent)?;
     }
 
-    // Only manage the host file when it participates in theme-manager source flow.
+    let wrapper = omarchy_base_hyprlock_wrapper(ctx.config);
+    let body = if is_style_only_hyprlock_config(source_config)? {
+        wrapper
+            .clone()
+            .unwrap_or_else(|| MINIMAL_SOURCE_ONLY_HYPRLOCK.to_string())
+    } else {
+        MINIMAL_SOURCE_ONLY_HYPRLOCK.to_string()
+    };
+    let desired = format!("{MANAGED_MARKER}\n{body}");
+
+    // Only manage the host file when theme-manager wrote it. Unmarked files that match
+    // what older versions wrote are adopted; anything else belongs to the user.
     let existing = fs::read_to_string(&hyprlock_main).unwrap_or_default();
-    if !existing.is_empty() && !existing.contains(CURRENT_THEME_SOURCE_SUFFIX) {
+    let managed = existing.is_empty()
+        || existing.lines().any(|line| line.trim() == MANAGED_MARKER)
+        || existing == MINIMAL_SOURCE_ONLY_HYPRLOCK
+        || wrapper.as_deref() == Some(existing.as_str());
+    if !managed {
         if !ctx.quiet {
             eprintln!(
-        "theme-manager: warning: preserving custom {}; it does not source current theme hyprlock config",
-        hyprlock_main.to_string_lossy()
-      );
+                "{} preserving custom {}; it is not marked `{}`",
+                output::warning_prefix(),
+                hyprlock_main.to_string_lossy(),
+                MANAGED_MARKER
+            );
         }
         return Ok(());
     }
 
-    let desired = if is_style_only_hyprlock_config(source_config)? {
-        omarchy_base_hyprlock_wrapper(ctx.config)
-            .unwrap_or_else(|| MINIMAL_SOURCE_ONLY_HYPRLOCK.to_string())
-    } else {
-        MINIMAL_SOURCE_ONLY_HYPRLOCK.to_string()
-    };
-
     if existing != desired {
         fs::write(&hyprlock_main, desired)?;
     }
@@ -194,9 +203,10 @@ pub fn ensure_omarchy_default_theme_link(config: &ResolvedConfig, quiet: bool) -
         SymlinkEnsureResult::SkippedNonSymlink => {
             if !quiet {
                 eprintln!(
-          "theme-manager: warning: preserving non-symlink path {}; cannot link Omarchy default Hyprlock theme",
-          link_path.to_string_lossy()
-        );
+                    "{} preserving non-symlink path {}; cannot link Omarchy default Hyprlock theme",
+                    output::warning_prefix(),
+                    link_path.to_string_lossy()
+                );
             }
         }
         SymlinkEnsureResult::Unchanged => {}
@@ -245,7 +255,7 @@ fn warn_if_hyprlock_source_mismatch(
     ctx: &CommandContext<'_>,
     expected_target: &Path,
 ) -> Result<()> {
-    let hyprlock_main = ctx.config.hyprlock_dir.join("hyprlock.conf");
+    let hyprlock_main = ctx.config.hyprlock_host_path();
     if !hyprlock_main.is_file() {
         return Ok(());
     }
@@ -256,10 +266,11 @@ fn warn_if_hyprlock_source_mismatch(
     let source_ok = content.contains(expected_abs.as_ref()) || content.contains(expected_suffix);
     if !source_ok && !ctx.quiet {
         eprintln!(
-      "theme-manager: warning: {} does not source current theme hyprlock config (expected {})",
-      hyprlock_main.to_string_lossy(),
-      expected_target.to_string_lossy()
-    );
+            "{} {} does not source current theme hyprlock config (expected {})",
+            output::warning_prefix(),
+            hyprlock_main.to_string_lossy(),
+            expected_target.to_string_lossy()
+        );
     }
     Ok(())
 }
@@ -274,3 +285,31 @@ fn remove_existing(path: &Path) -> Result<()> {
     }
     Ok(())
 }
+
+pub fn cmd_list(config: &ResolvedConfig) -> Result<()> {
+    let names = list_hyprlock_themes(&config.hyprlock_themes_dir)?;
+    theme_ops::print_component_options(
+        &config.hyprlock_themes_dir,
+        names,
+        omarchy_default_theme_available(config),
+    );
+    Ok(())
+}
+
+pub fn list_hyprlock_themes(hyprlock_themes_dir: &Path) -> Result<Vec<String>> {
+    if !hyprlock_themes_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(hyprlock_themes_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() && path.join("hyprlock.conf").is_file() {
+            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                entries.push(name.to_string());
+            }
+        }
+    }
+    entries.sort();
+    Ok(entries)
+}
@@ -1,22 +1,32 @@
 use anyhow::{anyhow, Result};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 pub mod cli;
 pub mod config;
+pub mod error;
 pub mod git_ops;
+pub mod history;
 pub mod hyprlock;
+pub mod lint;
+pub mod lock;
 pub mod omarchy;
 pub mod omarchy_defaults;
+pub mod output;
 pub mod paths;
 pub mod presets;
 pub mod preview;
+pub mod scaffold;
 pub mod starship;
+pub mod state;
 pub mod theme_ops;
 pub mod tui;
 pub mod walker;
+#[cfg(feature = "watch")]
+pub mod watch;
 pub mod waybar;
 
-use cli::{Command, PresetCommand};
+use cli::{Command, ConfigCommand, PresetCommand};
 use config::ResolvedConfig;
 use theme_ops::{
     hyprlock_from_defaults, starship_from_defaults, walker_from_defaults, waybar_from_defaults,
@@ -30,28 +40,112 @@ enum NamedMode {
 }
 
 pub fn run(cli: cli::Cli) -> Result<()> {
-    let config = ResolvedConfig::load()?;
+    output::set_color_choice(cli.color);
+    let command = cli.command.unwrap_or(Command::Browse(cli::BrowseArgs {
+        quiet: false,
+        no_mouse: false,
+        no_color_preview: false,
+    }));
+
+    // Config commands must work even when the config file itself is invalid.
+    if let Command::Config(args) = &command {
+        return match &args.command {
+            ConfigCommand::Edit => {
+                config::cmd_config_edit(cli.config.as_deref(), cli.profile.as_deref())
+            }
+            ConfigCommand::Path => {
+                config::cmd_config_path(cli.config.as_deref(), cli.profile.as_deref())
+            }
+            ConfigCommand::Explain { key } => config::cmd_config_explain(key.as_deref()),
+        };
+    }
+
+    let mut config = ResolvedConfig::load(
+        cli.config.as_deref(),
+        cli.profile.as_deref(),
+        cli.no_local_config,
+        cli.trust_local,
+    )?;
+    if cli.parallel_setters {
+        config.parallel_setters = true;
+    }
+    config.seed = cli.seed;
+    if let Some(path) = cli.hyprlock_host {
+        config.hyprlock_host = Some(path);
+    }
+    // Prepend in reverse so PATH reads omarchy_bin_dir, then extra_bin_dirs in order.
+    for bin_dir in config.extra_bin_dirs.iter().rev() {
+        config::prepend_to_path(bin_dir);
+    }
     if let Some(bin_dir) = &config.omarchy_bin_dir {
         config::prepend_to_path(bin_dir);
     }
 
     let skip_apps = std::env::var("THEME_MANAGER_SKIP_APPS").is_ok();
-    let skip_hook = std::env::var("THEME_MANAGER_SKIP_HOOK").is_ok();
+    let skip_hook = cli.no_hook || std::env::var("THEME_MANAGER_SKIP_HOOK").is_ok();
 
-    let command = cli
-        .command
-        .unwrap_or(Command::Browse(cli::BrowseArgs { quiet: false }));
     match command {
-        Command::List => {
-            theme_ops::cmd_list(&config)?;
+        Command::List(args) => {
+            theme_ops::cmd_list(&config, args.tag.as_deref(), args.tags, args.current)?;
         }
         Command::Set(args) => {
-            let (waybar_mode, waybar_name) = parse_waybar_flag(&config, args.waybar)?;
-            let (walker_mode, walker_name) = parse_walker_flag(&config, args.walker)?;
-            let (hyprlock_mode, hyprlock_name) = parse_hyprlock_flag(&config, args.hyprlock)?;
-            let starship_mode = starship_from_defaults(&config);
+            let config = apply_awww_overrides(&config, &args.awww)?;
+            let only = args
+                .only
+                .as_deref()
+                .map(|spec| {
+                    parse_only_components(
+                        spec,
+                        [
+                            ("waybar", args.waybar.is_some()),
+                            ("walker", args.walker.is_some()),
+                            ("hyprlock", args.hyprlock.is_some()),
+                        ],
+                    )
+                })
+                .transpose()?;
+            let listed = |component: &str| only.as_ref().map(|only| only.contains(&component));
+            let (mut waybar_mode, mut waybar_name) =
+                parse_waybar_flag(&config, args.waybar.clone())?;
+            let (mut walker_mode, mut walker_name) =
+                parse_walker_flag(&config, args.walker.clone())?;
+            let (mut hyprlock_mode, mut hyprlock_name) =
+                parse_hyprlock_flag(&config, args.hyprlock.clone())?;
+            let mut starship_mode = starship_from_defaults(&config);
+            // Listed components without a flag fall back to the theme's bundled config
+            // when the default would skip them; unlisted ones are not touched.
+            match listed("waybar") {
+                Some(false) => (waybar_mode, waybar_name) = (WaybarMode::None, None),
+                Some(true) if args.waybar.is_none() && matches!(waybar_mode, WaybarMode::None) => {
+                    waybar_mode = WaybarMode::Auto
+                }
+                _ => {}
+            }
+            match listed("walker") {
+                Some(false) => (walker_mode, walker_name) = (WalkerMode::None, None),
+                Some(true) if args.walker.is_none() && matches!(walker_mode, WalkerMode::None) => {
+                    walker_mode = WalkerMode::Auto
+                }
+                _ => {}
+            }
+            match listed("hyprlock") {
+                Some(false) => (hyprlock_mode, hyprlock_name) = (HyprlockMode::None, None),
+                Some(true)
+                    if args.hyprlock.is_none() && matches!(hyprlock_mode, HyprlockMode::None) =>
+                {
+                    hyprlock_mode = HyprlockMode::Auto
+                }
+                _ => {}
+            }
+            match listed("starship") {
+                Some(false) => starship_mode = StarshipMode::None,
+                Some(true) if matches!(starship_mode, StarshipMode::None) => {
+                    starship_mode = StarshipMode::Theme { path: None }
+                }
+                _ => {}
+            }
             let quiet = args.quiet || config.quiet_default;
-            let ctx = build_context(
+            let mut ctx = build_context(
                 &config,
                 quiet,
                 skip_apps,
@@ -61,16 +155,36 @@ pub fn run(cli: cli::Cli) -> Result<()> {
                 (hyprlock_mode, hyprlock_name),
                 starship_mode,
                 cli.debug_awww,
+                cli.print_awww_cmd,
             );
-            theme_ops::cmd_set(&ctx, &args.theme)?;
+            ctx.force_bg = args.force_bg;
+            ctx.timings = args.timings;
+            if let Some(background) = args.background.as_deref() {
+                ctx.background = theme_ops::BackgroundChoice::parse(background);
+            }
+            ctx.wait_for_waybar = args.wait;
+            let theme = match (args.theme, args.from_url) {
+                (Some(theme), _) => theme,
+                (None, Some(git_url)) => {
+                    let git_ctx = git_ops::GitContext { config: &config };
+                    git_ops::ensure_installed(&git_ctx, &git_url)?
+                }
+                (None, None) if args.stdin => read_theme_from_stdin()?,
+                (None, None) => {
+                    let current = paths::current_theme_name(&config.current_theme_link)?;
+                    history::previous_theme(&config, current.as_deref())?
+                }
+            };
+            theme_ops::cmd_set(&ctx, &theme)?;
         }
         Command::Next(args) => {
+            let config = apply_awww_overrides(&config, &args.awww)?;
             let (waybar_mode, waybar_name) = parse_waybar_flag(&config, args.waybar)?;
             let (walker_mode, walker_name) = parse_walker_flag(&config, args.walker)?;
             let (hyprlock_mode, hyprlock_name) = parse_hyprlock_flag(&config, args.hyprlock)?;
             let starship_mode = starship_from_defaults(&config);
             let quiet = args.quiet || config.quiet_default;
-            let ctx = build_context(
+            let mut ctx = build_context(
                 &config,
                 quiet,
                 skip_apps,
@@ -80,12 +194,26 @@ pub fn run(cli: cli::Cli) -> Result<()> {
                 (hyprlock_mode, hyprlock_name),
                 starship_mode,
                 cli.debug_awww,
+                cli.print_awww_cmd,
             );
-            theme_ops::cmd_next(&ctx)?;
+            ctx.wait_for_waybar = args.wait;
+            ctx.timings = args.timings;
+            let each_delay = args.each_delay.as_deref().map(parse_delay).transpose()?;
+            theme_ops::cmd_next(
+                &ctx,
+                args.filter.as_deref(),
+                args.tag.as_deref(),
+                !args.no_wrap,
+                args.count as usize,
+                each_delay,
+            )?;
         }
         Command::Browse(args) => {
             let quiet = args.quiet || config.quiet_default;
-            if let Some(selection) = tui::browse(&config, quiet)? {
+            if args.no_color_preview {
+                config.tui_color_preview = false;
+            }
+            if let Some(selection) = tui::browse(&config, quiet, !args.no_mouse)? {
                 let (waybar_mode, waybar_name) = match selection.waybar {
                     tui::WaybarSelection::NoChange => (WaybarMode::None, None),
                     tui::WaybarSelection::None => (WaybarMode::None, None),
@@ -121,56 +249,191 @@ pub fn run(cli: cli::Cli) -> Result<()> {
                     (hyprlock_mode, hyprlock_name),
                     starship_mode,
                     cli.debug_awww,
+                    cli.print_awww_cmd,
                 );
                 if selection.no_theme_change {
-                    if !skip_apps {
-                        let current_theme = paths::current_theme_dir(&config.current_theme_link)?;
-                        let waybar_restart = waybar::prepare_waybar(&ctx, &current_theme)?;
-                        walker::prepare_walker(&ctx, &current_theme)?;
-                        hyprlock::prepare_hyprlock(&ctx, &current_theme)?;
-                        starship::apply_starship(&ctx, &current_theme)?;
-                        omarchy::reload_components(
-                            quiet,
-                            waybar_restart,
-                            config.waybar_restart_logs,
-                        )?;
-                        omarchy::apply_theme_setters(quiet)?;
-                    }
+                    theme_ops::reapply_current(&ctx)?;
                 } else {
                     theme_ops::cmd_set(&ctx, &selection.theme)?;
                 }
             }
         }
-        Command::Current => {
-            theme_ops::cmd_current(&config)?;
+        Command::Reapply(args) => {
+            let (waybar_mode, waybar_name) = parse_waybar_flag(&config, args.waybar)?;
+            let (walker_mode, walker_name) = parse_walker_flag(&config, args.walker)?;
+            let (hyprlock_mode, hyprlock_name) = parse_hyprlock_flag(&config, args.hyprlock)?;
+            let starship_mode = starship_from_defaults(&config);
+            let quiet = args.quiet || config.quiet_default;
+            let ctx = build_context(
+                &config,
+                quiet,
+                skip_apps,
+                skip_hook,
+                (waybar_mode, waybar_name),
+                (walker_mode, walker_name),
+                (hyprlock_mode, hyprlock_name),
+                starship_mode,
+                cli.debug_awww,
+                cli.print_awww_cmd,
+            );
+            theme_ops::reapply_current(&ctx)?;
+        }
+        #[cfg(feature = "watch")]
+        Command::Watch(args) => {
+            let (waybar_mode, waybar_name) = parse_waybar_flag(&config, args.waybar)?;
+            let (walker_mode, walker_name) = parse_walker_flag(&config, args.walker)?;
+            let (hyprlock_mode, hyprlock_name) = parse_hyprlock_flag(&config, args.hyprlock)?;
+            let starship_mode = starship_from_defaults(&config);
+            let quiet = args.quiet || config.quiet_default;
+            let ctx = build_context(
+                &config,
+                quiet,
+                skip_apps,
+                skip_hook,
+                (waybar_mode, waybar_name),
+                (walker_mode, walker_name),
+                (hyprlock_mode, hyprlock_name),
+                starship_mode,
+                cli.debug_awww,
+                cli.print_awww_cmd,
+            );
+            watch::cmd_watch(&ctx, args.theme.as_deref())?;
+        }
+        Command::Current(args) => {
+            if args.variant {
+                theme_ops::cmd_current_variant(&config)?;
+            } else if args.path {
+                theme_ops::cmd_current_path(&config)?;
+            } else {
+                theme_ops::cmd_current(&config)?;
+            }
+        }
+        Command::BgNext(args) => {
+            let config = apply_awww_overrides(&config, &args.awww)?;
+            theme_ops::cmd_bg_next(&config, cli.debug_awww, cli.print_awww_cmd)?;
+        }
+        Command::BgList(args) => {
+            theme_ops::cmd_bg_list(&config, args.theme.as_deref())?;
+        }
+        Command::Reload(args) => {
+            if !skip_apps {
+                let quiet = args.quiet || config.quiet_default;
+                let selective = args.waybar || args.walker || args.hyprlock;
+                if args.all || !selective {
+                    omarchy::reload_components(&config, quiet, None)?;
+                    omarchy::apply_theme_setters(&config, quiet)?;
+                } else {
+                    if args.waybar {
+                        omarchy::restart_waybar_only(&config, quiet, None)?;
+                    }
+                    if args.walker {
+                        omarchy::restart_walker_only(&config, quiet)?;
+                    }
+                    if args.hyprlock {
+                        omarchy::restart_hyprlock_only(&config, quiet)?;
+                    }
+                }
+            }
+        }
+        Command::Preview(args) => {
+            let normalized = paths::normalize_theme_name(&args.theme);
+            let theme_path = theme_ops::resolve_theme_source(&config, &args.theme)?;
+            let title = paths::theme_display_name(&theme_path, &normalized);
+            preview::render_theme_to_stdout(&theme_path, &title)?;
+        }
+        Command::Palette(args) => {
+            theme_ops::cmd_palette(&config, &args.theme)?;
         }
-        Command::BgNext => {
-            theme_ops::cmd_bg_next(&config, cli.debug_awww)?;
+        Command::History(args) => {
+            history::cmd_history(&config, args.limit)?;
+        }
+        Command::Undo(args) => {
+            let entry = history::undo_target(&config)?;
+            let (waybar_mode, waybar_name) = match entry.waybar.as_deref() {
+                Some(spec) => named_mode_to_waybar(parse_named_mode_spec(spec, "waybar")?),
+                None => waybar_from_defaults(&config),
+            };
+            let (walker_mode, walker_name) = match entry.walker.as_deref() {
+                Some(spec) => named_mode_to_walker(parse_named_mode_spec(spec, "walker")?),
+                None => walker_from_defaults(&config),
+            };
+            let (hyprlock_mode, hyprlock_name) = match entry.hyprlock.as_deref() {
+                Some(spec) => named_mode_to_hyprlock(parse_named_mode_spec(spec, "hyprlock")?),
+                None => hyprlock_from_defaults(&config),
+            };
+            let starship_mode = match entry.starship.as_deref() {
+                Some(spec) => starship_value_to_mode(&parse_starship_spec(spec, &config)?),
+                None => starship_from_defaults(&config),
+            };
+            let quiet = args.quiet || config.quiet_default;
+            let ctx = build_context(
+                &config,
+                quiet,
+                skip_apps,
+                skip_hook,
+                (waybar_mode, waybar_name),
+                (walker_mode, walker_name),
+                (hyprlock_mode, hyprlock_name),
+                starship_mode,
+                cli.debug_awww,
+                cli.print_awww_cmd,
+            );
+            theme_ops::cmd_set(&ctx, &entry.theme)?;
         }
         Command::PrintConfig => {
             config::print_config(&config);
         }
+        Command::Config(_) => {}
         Command::Version => {
             theme_ops::cmd_version();
         }
         Command::Install(args) => {
             let ctx = git_ops::GitContext { config: &config };
-            git_ops::cmd_install(&ctx, &args.git_url)?;
+            git_ops::cmd_install(&ctx, &args.git_url, !args.no_apply, args.force)?;
         }
-        Command::Update => {
+        Command::Update(args) => {
             let ctx = git_ops::GitContext { config: &config };
-            git_ops::cmd_update(&ctx)?;
+            git_ops::cmd_update(&ctx, args.quiet || config.quiet_default)?;
+        }
+        Command::GenTheme(args) => {
+            scaffold::cmd_gen_theme(&config, &args.name)?;
+        }
+        Command::Lint(args) => {
+            lint::cmd_lint(&config, &args.theme)?;
+        }
+        Command::Backup(args) => {
+            state::cmd_backup(&config, &args.dir)?;
+        }
+        Command::Restore(args) => {
+            state::cmd_restore(&config, &args.dir, args.yes)?;
         }
         Command::Remove(args) => {
             let ctx = git_ops::GitContext { config: &config };
             git_ops::cmd_remove(&ctx, args.theme.as_deref())?;
         }
+        Command::Link(args) => {
+            let ctx = git_ops::GitContext { config: &config };
+            git_ops::cmd_link(&ctx, &args.name, &args.path)?;
+        }
+        Command::Unlink(args) => {
+            let ctx = git_ops::GitContext { config: &config };
+            git_ops::cmd_unlink(&ctx, &args.name)?;
+        }
         Command::Preset(args) => match args.command {
             PresetCommand::Save(save_args) => {
                 let entry = build_preset_entry(&config, &save_args)?;
-                presets::save_preset(&save_args.name, entry, &config)?;
+                presets::save_preset(&save_args.name, entry, &config, save_args.overwrite)?;
             }
             PresetCommand::Load(load_args) => {
+                if load_args.preview || load_args.dry_run {
+                    presets::print_load_plan(&config, &load_args.name)?;
+                    if load_args.dry_run {
+                        return Ok(());
+                    }
+                    if !presets::confirm_load()? {
+                        return Err(anyhow!("preset load cancelled"));
+                    }
+                }
                 let preset = presets::load_preset_definition(&config, &load_args.name)?;
                 let quiet = load_args.quiet || config.quiet_default;
 
@@ -201,59 +464,101 @@ pub fn run(cli: cli::Cli) -> Result<()> {
                     (hyprlock_mode, hyprlock_name),
                     starship_mode,
                     cli.debug_awww,
+                    cli.print_awww_cmd,
                 );
                 theme_ops::cmd_set(&ctx, &preset.theme)?;
             }
             PresetCommand::List => {
-                for name in presets::list_preset_names()? {
+                for name in presets::list_preset_names(&config)? {
                     println!("{name}");
                 }
             }
             PresetCommand::Remove(remove_args) => {
-                presets::remove_preset(&remove_args.name)?;
+                presets::remove_preset(&config, &remove_args.name)?;
             }
         },
         Command::Waybar(args) => {
-            let mode = parse_named_mode_spec(&args.mode, "--waybar")?;
+            let Some(mode) = args.mode.as_deref() else {
+                return waybar::cmd_list(&config);
+            };
+            let mode = parse_named_mode_spec(mode, "--waybar")?;
             let (waybar_mode, waybar_name) = named_mode_to_waybar(mode);
+            if args.check {
+                let target_theme = resolve_target_theme(&config, args.theme.as_deref())?;
+                let theme_dir = match (&waybar_mode, target_theme) {
+                    (WaybarMode::Auto, Some(dir)) => dir,
+                    (WaybarMode::Auto, None) => {
+                        paths::current_theme_dir(&config.current_theme_link)?
+                    }
+                    _ => PathBuf::new(),
+                };
+                let waybar_dir = waybar::resolve_waybar_dir(
+                    &config,
+                    &waybar_mode,
+                    waybar_name.as_deref(),
+                    &theme_dir,
+                )
+                .ok_or_else(|| anyhow!("--check requires a waybar theme name or auto"))?;
+                waybar::check_waybar_theme(&config, &waybar_dir)?;
+                return Ok(());
+            }
             let quiet = args.quiet || config.quiet_default;
+            let mut config = config.clone();
+            if let Some(apply_mode) = &args.apply_mode {
+                config.waybar_apply_mode = apply_mode.clone();
+            }
             apply_waybar_only(
                 &config,
                 waybar_mode,
                 waybar_name,
+                args.theme.as_deref(),
                 quiet,
                 skip_apps,
                 cli.debug_awww,
+                !args.no_restart,
             )?;
         }
         Command::Walker(args) => {
-            let mode = parse_named_mode_spec(&args.mode, "--walker")?;
+            let Some(mode) = args.mode.as_deref() else {
+                return walker::cmd_list(&config);
+            };
+            let mode = parse_named_mode_spec(mode, "--walker")?;
             let (walker_mode, walker_name) = named_mode_to_walker(mode);
             let quiet = args.quiet || config.quiet_default;
             apply_walker_only(
                 &config,
                 walker_mode,
                 walker_name,
+                args.theme.as_deref(),
                 quiet,
                 skip_apps,
                 cli.debug_awww,
+                !args.no_restart,
             )?;
         }
         Command::Hyprlock(args) => {
-            let mode = parse_named_mode_spec(&args.mode, "--hyprlock")?;
+            let Some(mode) = args.mode.as_deref() else {
+                return hyprlock::cmd_list(&config);
+            };
+            let mode = parse_named_mode_spec(mode, "--hyprlock")?;
             let (hyprlock_mode, hyprlock_name) = named_mode_to_hyprlock(mode);
             let quiet = args.quiet || config.quiet_default;
             apply_hyprlock_only(
                 &config,
                 hyprlock_mode,
                 hyprlock_name,
+                args.theme.as_deref(),
                 quiet,
                 skip_apps,
                 cli.debug_awww,
+                !args.no_restart,
             )?;
         }
         Command::Starship(args) => {
-            let mode = parse_starship_spec(&args.mode, &config)?;
+            let Some(mode) = args.mode.as_deref() else {
+                return starship::cmd_list(&config);
+            };
+            let mode = parse_starship_spec(mode, &config)?;
             let starship_mode = match mode {
                 presets::PresetStarshipValue::None => StarshipMode::None,
                 presets::PresetStarshipValue::Preset(preset) => StarshipMode::Preset { preset },
@@ -298,6 +603,7 @@ fn parse_hyprlock_flag(
     Ok(hyprlock_from_defaults(config))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn build_context<'a>(
     config: &'a ResolvedConfig,
     quiet: bool,
@@ -308,6 +614,7 @@ fn build_context<'a>(
     hyprlock: (HyprlockMode, Option<String>),
     starship_mode: StarshipMode,
     debug_awww: bool,
+    print_awww_cmd: bool,
 ) -> theme_ops::CommandContext<'a> {
     theme_ops::CommandContext {
         config,
@@ -322,7 +629,76 @@ fn build_context<'a>(
         hyprlock_name: hyprlock.1,
         starship_mode,
         debug_awww,
+        print_awww_cmd,
+        force_bg: false,
+        background: theme_ops::BackgroundChoice::Next,
+        wait_for_waybar: false,
+        timings: false,
+        rng: theme_ops::seeded_rng(config.seed),
+    }
+}
+
+fn apply_awww_overrides(config: &ResolvedConfig, args: &cli::AwwwArgs) -> Result<ResolvedConfig> {
+    let mut config = config.clone();
+    if let Some(transition_type) = &args.transition_type {
+        if transition_type.trim().is_empty() {
+            return Err(anyhow!("--transition-type requires a value"));
+        }
+        config.awww_transition_type = transition_type.trim().to_string();
     }
+    if let Some(duration) = args.duration {
+        if !duration.is_finite() || duration <= 0.0 {
+            return Err(anyhow!("--duration must be greater than 0"));
+        }
+        config.awww_transition_duration = duration;
+    }
+    if let Some(angle) = args.angle {
+        if !angle.is_finite() {
+            return Err(anyhow!("--angle must be a number"));
+        }
+        config.awww_transition_angle = angle;
+    }
+    if let Some(fps) = args.fps {
+        if fps == 0 {
+            return Err(anyhow!("--fps must be greater than 0"));
+        }
+        config.awww_transition_fps = fps;
+    }
+    if !args.outputs.is_empty() {
+        let outputs: Vec<String> = args
+            .outputs
+            .iter()
+            .map(|output| output.trim().to_string())
+            .filter(|output| !output.is_empty())
+            .collect();
+        if outputs.is_empty() {
+            return Err(anyhow!("--output requires a name"));
+        }
+        config.awww_outputs = outputs;
+    }
+    if let Some(path) = &args.awww_log {
+        config.awww_log_file = Some(path.clone());
+    }
+    config.awww_log_rotate = args.rotate_awww_log;
+    config.awww_no_transition = args.no_transition;
+    Ok(config)
+}
+
+// `500ms`, `2s`, `1.5s`, or a bare number of milliseconds.
+fn parse_delay(value: &str) -> Result<Duration> {
+    let value = value.trim();
+    let parsed = if let Some(ms) = value.strip_suffix("ms") {
+        ms.trim().parse::<u64>().ok().map(Duration::from_millis)
+    } else if let Some(secs) = value.strip_suffix('s') {
+        secs.trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|secs| secs.is_finite() && *secs >= 0.0)
+            .map(Duration::from_secs_f64)
+    } else {
+        value.parse::<u64>().ok().map(Duration::from_millis)
+    };
+    parsed.ok_or_else(|| anyhow!("invalid --each-delay: {value} (expected e.g. 500ms or 2s)"))
 }
 
 fn flag_to_named_mode(flag: Option<String>, arg_name: &str) -> Result<NamedMode> {
@@ -398,7 +774,11 @@ fn preset_hyprlock(preset: &presets::PresetDefinition) -> (HyprlockMode, Option<
 }
 
 fn preset_starship(preset: &presets::PresetDefinition) -> StarshipMode {
-    match &preset.starship {
+    starship_value_to_mode(&preset.starship)
+}
+
+fn starship_value_to_mode(value: &presets::PresetStarshipValue) -> StarshipMode {
+    match value {
         presets::PresetStarshipValue::None => StarshipMode::None,
         presets::PresetStarshipValue::Preset(preset) => StarshipMode::Preset {
             preset: preset.clone(),
@@ -414,32 +794,38 @@ fn build_preset_entry(
 ) -> Result<presets::PresetEntry> {
     let theme = match &args.theme {
         Some(theme) => {
-            let normalized = paths::normalize_theme_name(theme);
-            let theme_path = theme_ops::resolve_theme_path(config, &normalized)?;
-            if !theme_path.is_dir() && !paths::is_symlink(&theme_path)? {
-                return Err(anyhow!("theme not found: {normalized}"));
-            }
-            normalized
+            theme_ops::resolve_theme_source(config, theme)?;
+            paths::normalize_theme_name(theme)
         }
         None => paths::current_theme_name(&config.current_theme_link)?
             .ok_or_else(|| anyhow!("current theme not set: invalid link target"))?,
     };
 
-    let waybar_value = match args.waybar.as_deref() {
+    let live = if args.from_current {
+        Some(presets::detect_live_components(config)?)
+    } else {
+        None
+    };
+    let spec = |flag: &Option<String>, detected: fn(&presets::LiveComponents) -> &String| {
+        flag.clone()
+            .or_else(|| live.as_ref().map(|live| detected(live).clone()))
+    };
+
+    let waybar_value = match spec(&args.waybar, |live| &live.waybar).as_deref() {
         Some(spec) => parse_waybar_spec(spec)?,
         None => preset_waybar_defaults(config),
     };
 
-    let walker_value = match args.walker.as_deref() {
+    let walker_value = match spec(&args.walker, |live| &live.walker).as_deref() {
         Some(spec) => parse_walker_spec(spec)?,
         None => preset_walker_defaults(config),
     };
-    let hyprlock_value = match args.hyprlock.as_deref() {
+    let hyprlock_value = match spec(&args.hyprlock, |live| &live.hyprlock).as_deref() {
         Some(spec) => parse_hyprlock_spec(spec)?,
         None => preset_hyprlock_defaults(config),
     };
 
-    let starship_value = match args.starship.as_deref() {
+    let starship_value = match spec(&args.starship, |live| &live.starship).as_deref() {
         Some(spec) => parse_starship_spec(spec, config)?,
         None => preset_starship_defaults(config),
     };
@@ -597,18 +983,25 @@ fn parse_starship_spec(
     Ok(presets::PresetStarshipValue::Preset(cleaned.to_string()))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn apply_waybar_only(
     config: &ResolvedConfig,
     waybar_mode: WaybarMode,
     waybar_name: Option<String>,
+    theme: Option<&str>,
     quiet: bool,
     skip_apps: bool,
     debug_awww: bool,
+    restart: bool,
 ) -> Result<()> {
+    let theme_dir = resolve_target_theme(config, theme)?;
     if skip_apps {
         return Ok(());
     }
-    let theme_dir = paths::current_theme_dir(&config.current_theme_link)?;
+    let theme_dir = match theme_dir {
+        Some(dir) => dir,
+        None => paths::current_theme_dir(&config.current_theme_link)?,
+    };
     let ctx = build_context(
         config,
         quiet,
@@ -619,24 +1012,34 @@ fn apply_waybar_only(
         (HyprlockMode::None, None),
         StarshipMode::None,
         debug_awww,
+        false,
     );
-    let restart = waybar::prepare_waybar(&ctx, &theme_dir)?;
-    omarchy::restart_waybar_only(quiet, restart, config.waybar_restart_logs)?;
+    let waybar_restart = waybar::prepare_waybar(&ctx, &theme_dir)?;
+    if restart {
+        omarchy::restart_waybar_only(config, quiet, waybar_restart)?;
+    }
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn apply_walker_only(
     config: &ResolvedConfig,
     walker_mode: WalkerMode,
     walker_name: Option<String>,
+    theme: Option<&str>,
     quiet: bool,
     skip_apps: bool,
     debug_awww: bool,
+    restart: bool,
 ) -> Result<()> {
+    let theme_dir = resolve_target_theme(config, theme)?;
     if skip_apps {
         return Ok(());
     }
-    let theme_dir = paths::current_theme_dir(&config.current_theme_link)?;
+    let theme_dir = match theme_dir {
+        Some(dir) => dir,
+        None => paths::current_theme_dir(&config.current_theme_link)?,
+    };
     let ctx = build_context(
         config,
         quiet,
@@ -647,9 +1050,12 @@ fn apply_walker_only(
         (HyprlockMode::None, None),
         StarshipMode::None,
         debug_awww,
+        false,
     );
     walker::prepare_walker(&ctx, &theme_dir)?;
-    omarchy::restart_walker_only(quiet)?;
+    if restart {
+        omarchy::restart_walker_only(config, quiet)?;
+    }
     Ok(())
 }
 
@@ -671,6 +1077,57 @@ fn parse_hyprlock_spec(spec: &str) -> Result<presets::PresetHyprlockValue> {
     })
 }
 
+const ONLY_COMPONENTS: [&str; 4] = ["waybar", "walker", "hyprlock", "starship"];
+
+// Validates `--only` and rejects component flags for components it leaves out.
+fn parse_only_components(spec: &str, flagged: [(&str, bool); 3]) -> Result<Vec<&'static str>> {
+    let mut components = Vec::new();
+    for token in spec
+        .split(',')
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+    {
+        let Some(component) = ONLY_COMPONENTS
+            .iter()
+            .find(|component| component.eq_ignore_ascii_case(token))
+        else {
+            return Err(anyhow!(
+                "unknown component in --only: {token} (expected waybar, walker, hyprlock, or starship)"
+            ));
+        };
+        components.push(*component);
+    }
+    if components.is_empty() {
+        return Err(anyhow!("--only requires at least one component"));
+    }
+    for (component, given) in flagged {
+        if given && !components.contains(&component) {
+            return Err(anyhow!(
+                "--{component} conflicts with --only: {component} is not listed"
+            ));
+        }
+    }
+    Ok(components)
+}
+
+fn read_theme_from_stdin() -> Result<String> {
+    let mut line = String::new();
+    std::io::stdin().read_line(&mut line)?;
+    let theme = line.trim();
+    if theme.is_empty() {
+        return Err(anyhow!("no theme name on stdin"));
+    }
+    Ok(theme.to_string())
+}
+
+// `--theme` on the component commands resolves `auto` against that theme
+// instead of the current one.
+fn resolve_target_theme(config: &ResolvedConfig, theme: Option<&str>) -> Result<Option<PathBuf>> {
+    theme
+        .map(|theme| theme_ops::resolve_theme_source(config, theme))
+        .transpose()
+}
+
 fn apply_starship_only(
     config: &ResolvedConfig,
     starship_mode: StarshipMode,
@@ -692,23 +1149,31 @@ fn apply_starship_only(
         (HyprlockMode::None, None),
         starship_mode,
         debug_awww,
+        false,
     );
     starship::apply_starship(&ctx, &theme_dir)?;
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn apply_hyprlock_only(
     config: &ResolvedConfig,
     hyprlock_mode: HyprlockMode,
     hyprlock_name: Option<String>,
+    theme: Option<&str>,
     quiet: bool,
     skip_apps: bool,
     debug_awww: bool,
+    restart: bool,
 ) -> Result<()> {
+    let theme_dir = resolve_target_theme(config, theme)?;
     if skip_apps {
         return Ok(());
     }
-    let theme_dir = paths::current_theme_dir(&config.current_theme_link)?;
+    let theme_dir = match theme_dir {
+        Some(dir) => dir,
+        None => paths::current_theme_dir(&config.current_theme_link)?,
+    };
     let ctx = build_context(
         config,
         quiet,
@@ -719,9 +1184,12 @@ fn apply_hyprlock_only(
         (hyprlock_mode, hyprlock_name),
         StarshipMode::None,
         debug_awww,
+        false,
     );
     hyprlock::prepare_hyprlock(&ctx, &theme_dir)?;
-    omarchy::restart_hyprlock_only(quiet)?;
+    if restart {
+        omarchy::restart_hyprlock_only(config, quiet)?;
+    }
     Ok(())
 }
 
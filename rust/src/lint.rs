@@ -0,0 +1,155 @@
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::Path;
+
+use crate::config::ResolvedConfig;
+use crate::paths::normalize_theme_name;
+use crate::starship;
+use crate::theme_ops;
+use crate::waybar;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Level {
+    Ok,
+    Warn,
+    Fail,
+}
+
+// Paths that only exist on the author's machine; `~/` and `$HOME` stay portable.
+const HOST_PATH_PREFIXES: &[&str] = &["/home/", "/root/", "/Users/"];
+
+// Read-only pre-publish check of a theme directory. Missing optional components only
+// warn; components that are present but broken, and a theme without wallpapers, fail.
+pub fn cmd_lint(config: &ResolvedConfig, theme: &str) -> Result<()> {
+    let theme_name = normalize_theme_name(theme);
+    let theme_dir = theme_ops::resolve_theme_source(config, theme)?;
+    println!(
+        "theme-manager: linting {theme_name} ({})",
+        theme_dir.to_string_lossy()
+    );
+
+    let mut checks = Vec::new();
+    lint_waybar(config, &theme_dir, &mut checks)?;
+    lint_walker(&theme_dir, &mut checks);
+    lint_hyprlock(&theme_dir, &mut checks)?;
+    lint_starship(&theme_dir, &mut checks);
+    lint_backgrounds(config, &theme_dir, &mut checks)?;
+
+    let mut failures = 0;
+    for (level, message) in &checks {
+        let label = match level {
+            Level::Ok => "ok  ",
+            Level::Warn => "warn",
+            Level::Fail => {
+                failures += 1;
+                "FAIL"
+            }
+        };
+        println!("  {label} {message}");
+    }
+    if failures > 0 {
+        let plural = if failures == 1 { "" } else { "s" };
+        return Err(anyhow!(
+            "lint failed for {theme_name} ({failures} problem{plural})"
+        ));
+    }
+    println!("theme-manager: lint passed for {theme_name}");
+    Ok(())
+}
+
+fn lint_waybar(
+    config: &ResolvedConfig,
+    theme_dir: &Path,
+    checks: &mut Vec<(Level, String)>,
+) -> Result<()> {
+    let waybar_dir = theme_dir.join("waybar-theme");
+    if !waybar_dir.is_dir() {
+        checks.push((Level::Warn, "waybar: no waybar-theme/".to_string()));
+        return Ok(());
+    }
+    for (ok, message) in waybar::waybar_theme_checks(config, &waybar_dir)? {
+        let level = if ok { Level::Ok } else { Level::Fail };
+        checks.push((level, format!("waybar: {message}")));
+    }
+    Ok(())
+}
+
+fn lint_walker(theme_dir: &Path, checks: &mut Vec<(Level, String)>) {
+    let walker_dir = theme_dir.join("walker-theme");
+    if !walker_dir.is_dir() {
+        checks.push((Level::Warn, "walker: no walker-theme/".to_string()));
+        return;
+    }
+    let present = walker_dir.join("style.css").is_file();
+    let level = if present { Level::Ok } else { Level::Fail };
+    checks.push((level, "walker: style.css present".to_string()));
+}
+
+fn lint_hyprlock(theme_dir: &Path, checks: &mut Vec<(Level, String)>) -> Result<()> {
+    let hyprlock_dir = theme_dir.join("hyprlock-theme");
+    if !hyprlock_dir.is_dir() {
+        checks.push((Level::Warn, "hyprlock: no hyprlock-theme/".to_string()));
+        return Ok(());
+    }
+    let conf = hyprlock_dir.join("hyprlock.conf");
+    if !conf.is_file() {
+        checks.push((Level::Fail, "hyprlock: hyprlock.conf present".to_string()));
+        return Ok(());
+    }
+    checks.push((Level::Ok, "hyprlock: hyprlock.conf present".to_string()));
+    let content = fs::read_to_string(&conf)?;
+    let host_line = content.lines().enumerate().find(|(_, line)| {
+        let line = line.trim_start();
+        !line.starts_with('#')
+            && HOST_PATH_PREFIXES
+                .iter()
+                .any(|prefix| line.contains(prefix))
+    });
+    match host_line {
+        Some((idx, _)) => checks.push((
+            Level::Fail,
+            format!(
+                "hyprlock: hyprlock.conf line {} uses an absolute host path",
+                idx + 1
+            ),
+        )),
+        None => checks.push((Level::Ok, "hyprlock: no absolute host paths".to_string())),
+    }
+    Ok(())
+}
+
+fn lint_starship(theme_dir: &Path, checks: &mut Vec<(Level, String)>) {
+    let Some(path) = starship::find_theme_starship_config(theme_dir) else {
+        checks.push((Level::Warn, "starship: no starship.toml/.yaml".to_string()));
+        return;
+    };
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match starship::validate_theme_config(&path) {
+        Ok(()) => checks.push((Level::Ok, format!("starship: {file_name} parses"))),
+        Err(err) => checks.push((
+            Level::Fail,
+            format!("starship: {file_name} does not parse: {err}"),
+        )),
+    }
+}
+
+fn lint_backgrounds(
+    config: &ResolvedConfig,
+    theme_dir: &Path,
+    checks: &mut Vec<(Level, String)>,
+) -> Result<()> {
+    let count = theme_ops::collect_backgrounds(config, theme_dir, None)?.len();
+    if count == 0 {
+        checks.push((
+            Level::Fail,
+            "backgrounds: no images in backgrounds/".to_string(),
+        ));
+    } else {
+        let plural = if count == 1 { "" } else { "s" };
+        checks.push((Level::Ok, format!("backgrounds: {count} image{plural}")));
+    }
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anyhow::{anyhow, Result};
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::ResolvedConfig;
+
+const LOCK_WAIT: Duration = Duration::from_millis(500);
+const LOCK_POLL: Duration = Duration::from_millis(50);
+
+// Advisory lock held for the duration of a theme operation; released on drop.
+pub struct ThemeLock {
+    file: File,
+}
+
+impl Drop for ThemeLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+pub fn acquire(config: &ResolvedConfig) -> Result<ThemeLock> {
+    acquire_at(&config.lock_file)
+}
+
+pub fn acquire_at(path: &Path) -> Result<ThemeLock> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)
+        .map_err(|err| anyhow!("failed to open lock file {}: {err}", path.to_string_lossy()))?;
+
+    let started = Instant::now();
+    loop {
+        match file.try_lock() {
+            Ok(()) => return Ok(ThemeLock { file }),
+            Err(TryLockError::WouldBlock) => {
+                if started.elapsed() >= LOCK_WAIT {
+                    return Err(anyhow!("another theme operation is in progress"));
+                }
+                thread::sleep(LOCK_POLL);
+            }
+            Err(TryLockError::Error(err)) => {
+                return Err(anyhow!("failed to lock {}: {err}", path.to_string_lossy()));
+            }
+        }
+    }
+}
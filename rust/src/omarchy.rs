@@ -1,13 +1,16 @@
 use anyhow::{anyhow, Result};
 use std::env;
+use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
+use std::process::{Command, Output, Stdio};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use crate::config::ResolvedConfig;
+use crate::output;
 use crate::paths::resolve_link_target;
-use rand::random;
+use rand::Rng;
 
 #[derive(Debug, Clone)]
 pub struct RestartCommand {
@@ -108,23 +111,106 @@ pub fn run_command(cmd: &str, args: &[&str], quiet: bool) -> Result<()> {
     Ok(())
 }
 
+// `behavior.command_timeout_ms`; zero disables the limit.
+fn command_timeout(config: &ResolvedConfig) -> Option<Duration> {
+    (config.command_timeout_ms > 0).then(|| Duration::from_millis(config.command_timeout_ms))
+}
+
+pub fn run_optional_timeout(
+    cmd: &str,
+    args: &[&str],
+    quiet: bool,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    if !command_exists(cmd) {
+        if !quiet {
+            eprintln!("theme-manager: {cmd} not found in PATH");
+        }
+        return Ok(());
+    }
+    run_command_timeout(cmd, args, quiet, timeout)
+}
+
+// Like `run_command`, but kills the child once `timeout` elapses. A timeout only
+// warns, so one hung helper doesn't block the rest of the reload.
+pub fn run_command_timeout(
+    cmd: &str,
+    args: &[&str],
+    quiet: bool,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    let Some(timeout) = timeout else {
+        return run_command(cmd, args, quiet);
+    };
+    let mut command = Command::new(cmd);
+    command.args(args);
+    if quiet {
+        command.stdout(Stdio::null()).stderr(Stdio::null());
+    }
+    let mut child = command.spawn()?;
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            if !status.success() {
+                return Err(anyhow!("{cmd} exited with {status}"));
+            }
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            if !quiet {
+                eprintln!(
+                    "{} {cmd} timed out after {}ms and was killed",
+                    output::warning_prefix(),
+                    timeout.as_millis()
+                );
+            }
+            return Ok(());
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+}
+
 pub fn stop_swaybg() {
     if command_exists("pkill") {
         let _ = run_command("pkill", &["-x", "swaybg"], true);
     }
 }
 
+// Used when a background is chosen without awww, where omarchy-theme-bg-next would re-cycle it.
+pub fn restart_swaybg(background_link: &Path, quiet: bool) {
+    stop_swaybg();
+    if !command_exists("swaybg") {
+        return;
+    }
+    let mut command = Command::new("swaybg");
+    command
+        .arg("-i")
+        .arg(background_link)
+        .args(["-m", "fill"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    if let Err(err) = command.spawn() {
+        if !quiet {
+            eprintln!("theme-manager: failed to start swaybg: {err}");
+        }
+    }
+}
+
 pub fn reload_components(
+    config: &ResolvedConfig,
     quiet: bool,
     waybar_restart: Option<RestartAction>,
-    waybar_restart_logs: bool,
 ) -> Result<()> {
-    run_optional("omarchy-restart-terminal", &[], quiet)?;
-    restart_waybar_only(quiet, waybar_restart, waybar_restart_logs)?;
-    restart_walker_only(quiet)?;
-    restart_hyprlock_only(quiet)?;
-    restart_swayosd(quiet)?;
-    run_optional("hyprctl", &["reload"], quiet)?;
+    let timeout = command_timeout(config);
+    run_optional_timeout("omarchy-restart-terminal", &[], quiet, timeout)?;
+    restart_waybar_only(config, quiet, waybar_restart)?;
+    restart_walker_only(config, quiet)?;
+    restart_hyprlock_only(config, quiet)?;
+    restart_swayosd(quiet, timeout)?;
+    run_optional_timeout("hyprctl", &["reload"], quiet, timeout)?;
     reload_notifications(quiet);
     if command_exists("pkill") {
         let _ = run_command("pkill", &["-SIGUSR2", "btop"], true);
@@ -132,20 +218,49 @@ pub fn reload_components(
     Ok(())
 }
 
-pub fn restart_walker_only(quiet: bool) -> Result<()> {
+// Custom walker/hyprlock `restart_cmd` values go through `sh -c` like `on_apply_cmd`, so
+// quoting, `&&` and pipes work; the command timeout still applies.
+fn run_shell_timeout(cmd: &str, quiet: bool, timeout: Option<Duration>) -> Result<()> {
+    run_command_timeout("sh", &["-c", cmd], quiet, timeout)
+}
+
+// `walker.restart_cmd`: unset uses Omarchy's helper, anything else is run as a shell command.
+pub fn restart_walker_only(config: &ResolvedConfig, quiet: bool) -> Result<()> {
     if command_exists("pkill") {
         let _ = run_command("pkill", &["-f", "walker --gapplication-service"], true);
         let _ = run_command("pkill", &["-x", "walker"], true);
     }
-    run_optional("omarchy-restart-walker", &[], quiet)
+    let timeout = command_timeout(config);
+    match config.walker_restart_cmd.as_deref().map(str::trim) {
+        Some(cmd) if !cmd.is_empty() => run_shell_timeout(cmd, quiet, timeout),
+        _ => run_optional_timeout("omarchy-restart-walker", &[], quiet, timeout),
+    }
 }
 
-pub fn restart_hyprlock_only(quiet: bool) -> Result<()> {
+// `hyprlock.restart_cmd`: unset uses Omarchy's helper when present, "noop" leaves
+// hyprlock alone, anything else is run as a shell command.
+pub fn restart_hyprlock_only(config: &ResolvedConfig, quiet: bool) -> Result<()> {
+    let custom = config
+        .hyprlock_restart_cmd
+        .as_deref()
+        .map(str::trim)
+        .filter(|cmd| !cmd.is_empty());
+    if custom == Some("noop") {
+        return Ok(());
+    }
     if command_exists("pkill") {
         let _ = run_command("pkill", &["-x", "hyprlock"], true);
     }
+    if let Some(cmd) = custom {
+        return run_shell_timeout(cmd, quiet, command_timeout(config));
+    }
     if command_exists("omarchy-restart-hyprlock") {
-        return run_command("omarchy-restart-hyprlock", &[], quiet);
+        return run_command_timeout(
+            "omarchy-restart-hyprlock",
+            &[],
+            quiet,
+            command_timeout(config),
+        );
     }
 
     // Omarchy currently provides `omarchy-lock-screen` and launches hyprlock on demand,
@@ -154,17 +269,47 @@ pub fn restart_hyprlock_only(quiet: bool) -> Result<()> {
     Ok(())
 }
 
+// `waybar.restart_cmd`: unset uses Omarchy's helper, "builtin" kills and relaunches
+// waybar directly, anything else is run as a shell command.
+pub fn waybar_restart_action(config: &ResolvedConfig) -> RestartAction {
+    match config.waybar_restart_cmd.as_deref().map(str::trim) {
+        Some("builtin") => RestartAction::WaybarExec {
+            config_path: config.waybar_dir.join("config.jsonc"),
+            style_path: config.waybar_dir.join("style.css"),
+        },
+        Some(cmd) if !cmd.is_empty() => RestartAction::Command(RestartCommand {
+            cmd: "sh".to_string(),
+            args: vec!["-c".to_string(), cmd.to_string()],
+        }),
+        _ => RestartAction::Command(RestartCommand {
+            cmd: "omarchy-restart-waybar".to_string(),
+            args: Vec::new(),
+        }),
+    }
+}
+
 pub fn restart_waybar_only(
+    config: &ResolvedConfig,
     quiet: bool,
     waybar_restart: Option<RestartAction>,
-    waybar_restart_logs: bool,
 ) -> Result<()> {
+    let waybar_restart = waybar_restart.or_else(|| {
+        config
+            .waybar_restart_cmd
+            .is_some()
+            .then(|| waybar_restart_action(config))
+    });
     if let Some(restart) = waybar_restart {
-        let waybar_quiet = quiet || !waybar_restart_logs;
+        let waybar_quiet = quiet || !config.waybar_restart_logs;
         match restart {
             RestartAction::Command(restart) => {
                 let arg_refs: Vec<&str> = restart.args.iter().map(|arg| arg.as_str()).collect();
-                run_command(&restart.cmd, &arg_refs, waybar_quiet)?;
+                run_command_timeout(
+                    &restart.cmd,
+                    &arg_refs,
+                    waybar_quiet,
+                    command_timeout(config),
+                )?;
             }
             RestartAction::WaybarExec {
                 config_path,
@@ -174,11 +319,45 @@ pub fn restart_waybar_only(
             }
         }
     } else {
-        run_optional("omarchy-restart-waybar", &[], quiet)?;
+        run_optional_timeout(
+            "omarchy-restart-waybar",
+            &[],
+            quiet,
+            command_timeout(config),
+        )?;
     }
     Ok(())
 }
 
+// Polls `pgrep -x waybar` until a process shows up or `timeout` elapses; a timeout
+// only warns so callers still succeed.
+pub fn wait_for_waybar(timeout: Duration, quiet: bool) {
+    let deadline = Instant::now() + timeout;
+    loop {
+        match pgrep_pids("waybar") {
+            Some(pids) if !pids.is_empty() => return,
+            Some(_) => {}
+            None => {
+                if !quiet {
+                    eprintln!("theme-manager: pgrep not found; not waiting for waybar");
+                }
+                return;
+            }
+        }
+        if Instant::now() >= deadline {
+            if !quiet {
+                eprintln!(
+                    "{} waybar not running after {}ms",
+                    output::warning_prefix(),
+                    timeout.as_millis()
+                );
+            }
+            return;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+}
+
 fn pgrep_pids(name: &str) -> Option<Vec<String>> {
     if !command_exists("pgrep") {
         return None;
@@ -232,29 +411,26 @@ fn start_swayosd(quiet: bool) -> Result<()> {
         if quiet {
             command.stdout(Stdio::null()).stderr(Stdio::null());
         }
-        match command.spawn() {
-            Ok(mut child) => {
-                thread::sleep(Duration::from_millis(120));
-                match child.try_wait() {
-                    Ok(Some(status)) => {
-                        if status.success() {
-                            return Ok(());
-                        }
+        if let Ok(mut child) = command.spawn() {
+            thread::sleep(Duration::from_millis(120));
+            match child.try_wait() {
+                Ok(Some(status)) => {
+                    if status.success() {
+                        return Ok(());
                     }
-                    Ok(None) => return Ok(()),
-                    Err(_) => {}
                 }
+                Ok(None) => return Ok(()),
+                Err(_) => {}
             }
-            Err(_) => {}
         }
     }
 
     Ok(())
 }
 
-fn restart_swayosd(quiet: bool) -> Result<()> {
+fn restart_swayosd(quiet: bool, timeout: Option<Duration>) -> Result<()> {
     let before = pgrep_pids("swayosd-server");
-    if let Err(err) = run_optional("omarchy-restart-swayosd", &[], quiet) {
+    if let Err(err) = run_optional_timeout("omarchy-restart-swayosd", &[], quiet, timeout) {
         if !quiet {
             eprintln!("theme-manager: swayosd restart command failed: {err}");
         }
@@ -427,15 +603,48 @@ fn restart_waybar_exec(config_path: &Path, style_path: &Path, quiet: bool) -> Re
     Err(anyhow!("failed to restart waybar"))
 }
 
-pub fn apply_theme_setters(quiet: bool) -> Result<()> {
-    run_optional("omarchy-theme-set-gnome", &[], quiet)?;
-    run_optional("omarchy-theme-set-browser", &[], quiet)?;
-    run_optional("omarchy-theme-set-vscode", &[], quiet)?;
-    run_optional("omarchy-theme-set-obsidian", &[], quiet)?;
-    Ok(())
+const THEME_SETTERS: [&str; 4] = [
+    "omarchy-theme-set-gnome",
+    "omarchy-theme-set-browser",
+    "omarchy-theme-set-vscode",
+    "omarchy-theme-set-obsidian",
+];
+
+pub fn apply_theme_setters(config: &ResolvedConfig, quiet: bool) -> Result<()> {
+    let timeout = command_timeout(config);
+    if !config.parallel_setters {
+        for setter in THEME_SETTERS {
+            run_optional_timeout(setter, &[], quiet, timeout)?;
+        }
+        return Ok(());
+    }
+
+    // The setters touch different apps, so they can run side by side; every one is
+    // joined before the first failure (in setter order) is reported.
+    let results: Vec<Result<()>> = thread::scope(|scope| {
+        let handles: Vec<_> = THEME_SETTERS
+            .iter()
+            .map(|setter| scope.spawn(move || run_optional_timeout(setter, &[], quiet, timeout)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|_| Err(anyhow!("theme setter thread panicked")))
+            })
+            .collect()
+    });
+    results.into_iter().collect()
 }
 
-pub fn run_awww_transition(config: &ResolvedConfig, quiet: bool, debug_awww: bool) -> Result<()> {
+pub fn run_awww_transition(
+    config: &ResolvedConfig,
+    quiet: bool,
+    debug_awww: bool,
+    print_only: bool,
+    rng: &mut impl Rng,
+) -> Result<()> {
     if !config.awww_transition {
         return Ok(());
     }
@@ -451,52 +660,157 @@ pub fn run_awww_transition(config: &ResolvedConfig, quiet: bool, debug_awww: boo
         return Ok(());
     }
 
-    let angle = if random::<bool>() {
+    let angle = if rng.gen::<bool>() {
         config.awww_transition_angle
     } else {
         -config.awww_transition_angle
     };
-    let args = vec![
-        "img".to_string(),
-        background.to_string_lossy().to_string(),
-        "--transition-type".to_string(),
-        config.awww_transition_type.clone(),
-        "--transition-duration".to_string(),
-        format!("{}", config.awww_transition_duration),
-        format!("--transition-angle={}", angle),
-        "--transition-fps".to_string(),
-        format!("{}", config.awww_transition_fps),
-        "--transition-pos".to_string(),
-        config.awww_transition_pos.clone(),
-        "--transition-bezier".to_string(),
-        config.awww_transition_bezier.clone(),
-        "--transition-wave".to_string(),
-        config.awww_transition_wave.clone(),
-    ];
-
-    if debug_awww {
-        eprintln!("theme-manager: awww cmd: awww {}", args.join(" "));
-    }
-    match Command::new("awww").args(&args).output() {
-        Ok(output) if output.status.success() => Ok(()),
-        Ok(output) => {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let socket_error = stderr.contains("awww-daemon") || stderr.contains("Socket file");
-            if socket_error {
-                notify_awww_unavailable(quiet);
+    // A bare `awww img` still fades with awww's default transition.
+    let args = if config.awww_no_transition {
+        vec![
+            "img".to_string(),
+            background.to_string_lossy().to_string(),
+            "--transition-type".to_string(),
+            "none".to_string(),
+        ]
+    } else {
+        vec![
+            "img".to_string(),
+            background.to_string_lossy().to_string(),
+            "--transition-type".to_string(),
+            config.awww_transition_type.clone(),
+            "--transition-duration".to_string(),
+            format!("{}", config.awww_transition_duration),
+            format!("--transition-angle={}", angle),
+            "--transition-fps".to_string(),
+            format!("{}", config.awww_transition_fps),
+            "--transition-pos".to_string(),
+            config.awww_transition_pos.clone(),
+            "--transition-bezier".to_string(),
+            config.awww_transition_bezier.clone(),
+            "--transition-wave".to_string(),
+            config.awww_transition_wave.clone(),
+        ]
+    };
+
+    let commands = awww_commands(args, &config.awww_outputs);
+    if print_only {
+        for args in &commands {
+            println!("awww {}", args.join(" "));
+        }
+        return Ok(());
+    }
+    let mut log = open_awww_log(config, quiet);
+    for args in &commands {
+        if debug_awww {
+            eprintln!("theme-manager: awww cmd: awww {}", args.join(" "));
+        }
+        let result = Command::new("awww").args(args).output();
+        if let Some(log) = log.as_mut() {
+            write_awww_log(log, args, &result);
+        }
+        match result {
+            Ok(output) if output.status.success() => {}
+            Ok(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let socket_error = stderr.contains("awww-daemon") || stderr.contains("Socket file");
+                if socket_error {
+                    notify_awww_unavailable(quiet);
+                    if !quiet {
+                        eprintln!("theme-manager: awww-daemon not running; skipping transition");
+                    }
+                    return Ok(());
+                }
+                if !quiet {
+                    eprintln!("theme-manager: awww transition failed");
+                }
+            }
+            Err(err) => {
                 if !quiet {
-                    eprintln!("theme-manager: awww-daemon not running; skipping transition");
+                    eprintln!("theme-manager: awww transition failed: {err}");
                 }
-            } else if !quiet {
-                eprintln!("theme-manager: awww transition failed");
+                return Ok(());
             }
-            Ok(())
         }
+    }
+    Ok(())
+}
+
+// `behavior.awww_log_file` / `--verbose-awww-log`: appended to, or rotated to `<file>.1`
+// first with `--rotate-awww-log`. Logging problems only warn.
+fn open_awww_log(config: &ResolvedConfig, quiet: bool) -> Option<fs::File> {
+    let path = config.awww_log_file.as_ref()?;
+    if config.awww_log_rotate && path.is_file() {
+        let mut rotated = path.clone().into_os_string();
+        rotated.push(".1");
+        let _ = fs::rename(path, rotated);
+    }
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    match fs::OpenOptions::new().create(true).append(true).open(path) {
+        Ok(file) => Some(file),
         Err(err) => {
             if !quiet {
-                eprintln!("theme-manager: awww transition failed: {err}");
+                eprintln!(
+                    "theme-manager: cannot open awww log {}: {err}",
+                    path.to_string_lossy()
+                );
             }
-            Ok(())
+            None
+        }
+    }
+}
+
+fn write_awww_log(log: &mut fs::File, args: &[String], result: &std::io::Result<Output>) {
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    let mut entry = format!("[{stamp}] awww {}\n", args.join(" "));
+    match result {
+        Ok(output) => {
+            entry.push_str(&format!("status: {}\n", output.status));
+            entry.push_str("--- stdout ---\n");
+            entry.push_str(&String::from_utf8_lossy(&output.stdout));
+            entry.push_str("--- stderr ---\n");
+            entry.push_str(&String::from_utf8_lossy(&output.stderr));
+        }
+        Err(err) => entry.push_str(&format!("error: {err}\n")),
+    }
+    entry.push('\n');
+    let _ = log.write_all(entry.as_bytes());
+}
+
+// One invocation for all outputs by default; one per output when outputs are configured.
+fn awww_commands(args: Vec<String>, outputs: &[String]) -> Vec<Vec<String>> {
+    if outputs.is_empty() {
+        return vec![args];
+    }
+    outputs
+        .iter()
+        .map(|output| {
+            let mut per_output = args.clone();
+            per_output.push("--outputs".to_string());
+            per_output.push(output.clone());
+            per_output
+        })
+        .collect()
+}
+
+// Fire-and-forget: the command runs through `sh -c` with the theme name as $1 and is not awaited.
+pub fn spawn_on_apply(cmd: &str, theme: &str, quiet: bool) {
+    let mut command = Command::new("sh");
+    command
+        .args(["-c", cmd, "theme-manager", theme])
+        .env("THEME_MANAGER_THEME", theme)
+        .stdin(Stdio::null());
+    if quiet {
+        command.stdout(Stdio::null()).stderr(Stdio::null());
+    }
+    if let Err(err) = command.spawn() {
+        if !quiet {
+            eprintln!("theme-manager: failed to run on_apply_cmd: {err}");
         }
     }
 }
@@ -524,6 +838,22 @@ fn resolve_background(link_path: &Path) -> Result<Option<PathBuf>> {
     Ok(Some(link_path.to_path_buf()))
 }
 
+pub fn notify_theme_changed(title: &str, icon: Option<&Path>) {
+    if !command_exists("notify-send") {
+        return;
+    }
+    let mut command = Command::new("notify-send");
+    command.args(["--app-name=theme-manager", "--urgency=low"]);
+    if let Some(icon) = icon {
+        command.arg(format!("--icon={}", icon.to_string_lossy()));
+    }
+    command
+        .args(["Theme", title])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    let _ = command.status();
+}
+
 fn notify_awww_unavailable(quiet: bool) {
     if !command_exists("notify-send") {
         return;
@@ -0,0 +1,47 @@
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::cli::ColorChoice;
+
+const YELLOW: &str = "\x1b[33m";
+const RESET: &str = "\x1b[0m";
+
+static COLOR_CHOICE: AtomicU8 = AtomicU8::new(0);
+
+#[derive(Clone, Copy, Debug)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+pub fn set_color_choice(choice: ColorChoice) {
+    let value = match choice {
+        ColorChoice::Auto => 0,
+        ColorChoice::Always => 1,
+        ColorChoice::Never => 2,
+    };
+    COLOR_CHOICE.store(value, Ordering::Relaxed);
+}
+
+pub fn should_color(stream: Stream) -> bool {
+    match COLOR_CHOICE.load(Ordering::Relaxed) {
+        1 => return true,
+        2 => return false,
+        _ => {}
+    }
+    if std::env::var_os("NO_COLOR").is_some_and(|val| !val.is_empty()) {
+        return false;
+    }
+    match stream {
+        Stream::Stdout => std::io::stdout().is_terminal(),
+        Stream::Stderr => std::io::stderr().is_terminal(),
+    }
+}
+
+pub fn warning_prefix() -> String {
+    if should_color(Stream::Stderr) {
+        format!("theme-manager: {YELLOW}warning{RESET}:")
+    } else {
+        "theme-manager: warning:".to_string()
+    }
+}
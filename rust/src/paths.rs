@@ -1,7 +1,33 @@
 use anyhow::{anyhow, Result};
+use serde::Deserialize;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeVariant {
+    Light,
+    Dark,
+    Unknown,
+}
+
+impl ThemeVariant {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThemeVariant::Light => "light",
+            ThemeVariant::Dark => "dark",
+            ThemeVariant::Unknown => "unknown",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct ThemeMetadata {
+    pub variant: Option<String>,
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
 pub fn normalize_theme_name(input: &str) -> String {
     let mut out = String::new();
     let mut in_tag = false;
@@ -17,22 +43,49 @@ pub fn normalize_theme_name(input: &str) -> String {
     out
 }
 
+// Lowercase name parts that read better upper-cased.
+const TITLE_ACRONYMS: &[&str] = &[
+    "amoled", "crt", "gtk", "hc", "hd", "kde", "oled", "os", "rgb", "tty", "ui",
+];
+
 pub fn title_case_theme(name: &str) -> String {
     name.split('-')
-        .map(|part| {
-            let mut chars = part.chars();
-            match chars.next() {
-                Some(first) => {
-                    let rest = chars.as_str().to_lowercase();
-                    format!("{}{}", first.to_ascii_uppercase(), rest)
-                }
-                None => String::new(),
-            }
-        })
+        .map(title_case_part)
         .collect::<Vec<_>>()
         .join(" ")
 }
 
+// Known acronyms are upper-cased and version tokens (`v2`, `v1.5`) stay lowercase.
+// Otherwise only the first letter is raised, so `TokyoNight` or `NORD` keep their capitals.
+fn title_case_part(part: &str) -> String {
+    let lower = part.to_lowercase();
+    if TITLE_ACRONYMS.contains(&lower.as_str()) {
+        return part.to_uppercase();
+    }
+    let is_version = lower.strip_prefix('v').is_some_and(|rest| {
+        rest.starts_with(|c: char| c.is_ascii_digit())
+            && rest.chars().all(|c| c.is_ascii_digit() || c == '.')
+    });
+    if is_version {
+        return lower;
+    }
+    let mut chars = part.chars();
+    match chars.next() {
+        Some(first) => format!("{}{}", first.to_uppercase(), chars.as_str()),
+        None => String::new(),
+    }
+}
+
+// `display_name` from the theme's `theme.toml`, else the title-cased directory name.
+pub fn theme_display_name(theme_dir: &Path, name: &str) -> String {
+    load_theme_meta(theme_dir)
+        .ok()
+        .and_then(|meta| meta.display_name)
+        .map(|display| display.trim().to_string())
+        .filter(|display| !display.is_empty())
+        .unwrap_or_else(|| title_case_theme(name))
+}
+
 pub fn resolve_link_target(link_path: &Path) -> Result<PathBuf> {
     if !link_path.is_symlink() {
         return Ok(link_path.canonicalize()?);
@@ -73,7 +126,12 @@ pub fn current_theme_name(current_link: &Path) -> Result<Option<String>> {
     }
 
     if let Some(target_name) = link_target_name {
-        return Ok(Some(target_name));
+        // theme.name is missing: derive it from the link target and heal the state.
+        let name = normalize_theme_name(&target_name);
+        if let Some(parent) = current_link.parent() {
+            let _ = fs::write(parent.join("theme.name"), &name);
+        }
+        return Ok(Some(name));
     }
 
     if current_link.exists() {
@@ -102,6 +160,82 @@ pub fn current_theme_dir(current_link: &Path) -> Result<PathBuf> {
     resolve_link_target(current_link)
 }
 
+pub fn current_theme_variant(current_link: &Path, background_link: &Path) -> Result<ThemeVariant> {
+    let theme_dir = current_theme_dir(current_link)?;
+    if let Some(variant) = theme_variant_from_metadata(&theme_dir)? {
+        return Ok(variant);
+    }
+    Ok(theme_variant_from_background(background_link))
+}
+
+// Optional `theme.toml` metadata; themes without one get the defaults.
+pub fn load_theme_meta(theme_dir: &Path) -> Result<ThemeMetadata> {
+    let metadata_path = theme_dir.join("theme.toml");
+    if !metadata_path.is_file() {
+        return Ok(ThemeMetadata::default());
+    }
+    let content = fs::read_to_string(&metadata_path)?;
+    toml::from_str(&content)
+        .map_err(|err| anyhow!("invalid {}: {err}", metadata_path.to_string_lossy()))
+}
+
+pub fn theme_has_tag(theme_dir: &Path, tag: &str) -> bool {
+    load_theme_meta(theme_dir)
+        .map(|meta| {
+            meta.tags
+                .iter()
+                .any(|t| t.trim().eq_ignore_ascii_case(tag.trim()))
+        })
+        .unwrap_or(false)
+}
+
+fn theme_variant_from_metadata(theme_dir: &Path) -> Result<Option<ThemeVariant>> {
+    let metadata = load_theme_meta(theme_dir)?;
+    if let Some(variant) = metadata.variant.as_deref() {
+        match variant.trim().to_ascii_lowercase().as_str() {
+            "light" => return Ok(Some(ThemeVariant::Light)),
+            "dark" => return Ok(Some(ThemeVariant::Dark)),
+            _ => {}
+        }
+    }
+    // Omarchy marks light themes with an empty `light.mode` file.
+    if theme_dir.join("light.mode").is_file() {
+        return Ok(Some(ThemeVariant::Light));
+    }
+    Ok(None)
+}
+
+fn theme_variant_from_background(background_link: &Path) -> ThemeVariant {
+    if !background_link.exists() {
+        return ThemeVariant::Unknown;
+    }
+    let Ok(background) = resolve_link_target(background_link) else {
+        return ThemeVariant::Unknown;
+    };
+    let Ok(image) = image::open(&background) else {
+        return ThemeVariant::Unknown;
+    };
+
+    let thumb = image.thumbnail(64, 64).to_rgb8();
+    let pixels = thumb.pixels().len();
+    if pixels == 0 {
+        return ThemeVariant::Unknown;
+    }
+    let total: f64 = thumb
+        .pixels()
+        .map(|px| {
+            let [r, g, b] = px.0;
+            0.2126 * f64::from(r) + 0.7152 * f64::from(g) + 0.0722 * f64::from(b)
+        })
+        .sum();
+    let luminance = total / pixels as f64 / 255.0;
+    if luminance >= 0.5 {
+        ThemeVariant::Light
+    } else {
+        ThemeVariant::Dark
+    }
+}
+
 pub fn is_symlink(path: &Path) -> Result<bool> {
     match fs::symlink_metadata(path) {
         Ok(meta) => Ok(meta.file_type().is_symlink()),
@@ -109,3 +243,45 @@ pub fn is_symlink(path: &Path) -> Result<bool> {
         Err(err) => Err(err.into()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn title_case_theme_handles_real_theme_names() {
+        for (name, title) in [
+            ("tokyo-night", "Tokyo Night"),
+            ("catppuccin-latte", "Catppuccin Latte"),
+            ("gruvbox-v2", "Gruvbox v2"),
+            ("nord-v1.5", "Nord v1.5"),
+            ("gtk-dark", "GTK Dark"),
+            ("retro-crt-82", "Retro CRT 82"),
+            ("TokyoNight-storm", "TokyoNight Storm"),
+            ("NORD", "NORD"),
+            ("vantablack", "Vantablack"),
+            ("Tokyo Night", "Tokyo Night"),
+        ] {
+            assert_eq!(title_case_theme(name), title, "{name}");
+        }
+    }
+
+    #[test]
+    fn theme_display_name_prefers_theme_toml() {
+        let temp = TempDir::new().unwrap();
+        assert_eq!(
+            theme_display_name(temp.path(), "tokyonight-storm"),
+            "Tokyonight Storm"
+        );
+        fs::write(
+            temp.path().join("theme.toml"),
+            "display_name = \"Tokyo Night Storm\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            theme_display_name(temp.path(), "tokyonight-storm"),
+            "Tokyo Night Storm"
+        );
+    }
+}
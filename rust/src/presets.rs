@@ -1,12 +1,16 @@
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
-use std::env;
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use crate::config::ResolvedConfig;
-use crate::paths::{is_symlink, normalize_theme_name};
+use crate::history;
+use crate::paths::{current_theme_name, normalize_theme_name};
+use crate::starship;
+use crate::theme_ops;
+use crate::walker;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct PresetFile {
@@ -97,14 +101,8 @@ pub struct PresetSummary {
     pub errors: Vec<String>,
 }
 
-pub fn presets_path() -> Result<PathBuf> {
-    let home = env::var("HOME").map_err(|_| anyhow!("HOME is not set"))?;
-    Ok(PathBuf::from(home).join(".config/theme-manager/presets.toml"))
-}
-
-pub fn load_presets() -> Result<PresetFile> {
-    let path = presets_path()?;
-    load_presets_from_path(&path)
+pub fn load_presets(config: &ResolvedConfig) -> Result<PresetFile> {
+    load_presets_from_path(&config.presets_file)
 }
 
 pub fn load_presets_from_path(path: &Path) -> Result<PresetFile> {
@@ -116,9 +114,8 @@ pub fn load_presets_from_path(path: &Path) -> Result<PresetFile> {
     Ok(parsed)
 }
 
-pub fn write_presets(file: &PresetFile) -> Result<()> {
-    let path = presets_path()?;
-    write_presets_to_path(&path, file)
+pub fn write_presets(config: &ResolvedConfig, file: &PresetFile) -> Result<()> {
+    write_presets_to_path(&config.presets_file, file)
 }
 
 pub fn write_presets_to_path(path: &Path, file: &PresetFile) -> Result<()> {
@@ -130,18 +127,18 @@ pub fn write_presets_to_path(path: &Path, file: &PresetFile) -> Result<()> {
     Ok(())
 }
 
-pub fn list_preset_names() -> Result<Vec<String>> {
-    let mut names: Vec<String> = load_presets()?.preset.keys().cloned().collect();
+pub fn list_preset_names(config: &ResolvedConfig) -> Result<Vec<String>> {
+    let mut names: Vec<String> = load_presets(config)?.preset.keys().cloned().collect();
     names.sort();
     Ok(names)
 }
 
-pub fn get_preset_entry(name: &str) -> Result<PresetEntry> {
+pub fn get_preset_entry(config: &ResolvedConfig, name: &str) -> Result<PresetEntry> {
     let key = name.trim();
     if key.is_empty() {
         return Err(anyhow!("missing preset name"));
     }
-    let file = load_presets()?;
+    let file = load_presets(config)?;
     file.preset
         .get(key)
         .cloned()
@@ -166,16 +163,18 @@ pub fn summarize_preset(config: &ResolvedConfig, name: &str, entry: &PresetEntry
     let starship_value = parse_starship(entry.starship.as_ref(), &mut errors);
 
     if let Some(theme_name) = theme.as_ref() {
-        let normalized = normalize_theme_name(theme_name);
-        let theme_path = config.theme_root_dir.join(&normalized);
-        if is_broken_theme(&theme_path) {
-            errors.push(format!("theme not found: {normalized}"));
-        }
-        if matches!(starship_value, PresetStarshipValue::Theme) {
-            let starship_path = theme_path.join("starship.toml");
-            if !starship_path.is_file() {
-                errors.push("theme starship.toml not found".to_string());
+        match theme_ops::resolve_theme_source(config, theme_name) {
+            Ok(theme_path) => {
+                if matches!(starship_value, PresetStarshipValue::Theme)
+                    && starship::find_theme_starship_config(&theme_path).is_none()
+                {
+                    errors.push("theme starship.toml/starship.yaml not found".to_string());
+                }
             }
+            Err(_) => errors.push(format!(
+                "theme not found: {}",
+                normalize_theme_name(theme_name)
+            )),
         }
     }
 
@@ -190,7 +189,7 @@ pub fn summarize_preset(config: &ResolvedConfig, name: &str, entry: &PresetEntry
 }
 
 pub fn load_preset_definition(config: &ResolvedConfig, name: &str) -> Result<PresetDefinition> {
-    let entry = get_preset_entry(name)?;
+    let entry = get_preset_entry(config, name)?;
     let summary = summarize_preset(config, name, &entry);
     if !summary.errors.is_empty() {
         return Err(anyhow!(summary.errors.join("; ")));
@@ -213,7 +212,92 @@ pub fn load_preset_definition(config: &ResolvedConfig, name: &str) -> Result<Pre
     })
 }
 
-pub fn save_preset(name: &str, entry: PresetEntry, config: &ResolvedConfig) -> Result<()> {
+// Prints the preset next to the current theme and, when the last history entry
+// matches it, the current component selections.
+pub fn print_load_plan(config: &ResolvedConfig, name: &str) -> Result<()> {
+    let entry = get_preset_entry(config, name)?;
+    let summary = summarize_preset(config, name.trim(), &entry);
+    let current_theme = current_theme_name(&config.current_theme_link)?;
+    let last = history::read_entries(&config.history_file)?
+        .pop()
+        .filter(|last| Some(&last.theme) == current_theme.as_ref());
+    let current = |spec: Option<&Option<String>>| {
+        spec.and_then(|spec| spec.as_deref())
+            .map(format_history_spec)
+            .unwrap_or_else(|| "unknown".to_string())
+    };
+
+    let planned_theme = normalize_theme_name(&summary.theme);
+    let rows = [
+        (
+            "theme",
+            current_theme
+                .clone()
+                .unwrap_or_else(|| "(none)".to_string()),
+            planned_theme,
+        ),
+        (
+            "waybar",
+            current(last.as_ref().map(|e| &e.waybar)),
+            summary.waybar,
+        ),
+        (
+            "walker",
+            current(last.as_ref().map(|e| &e.walker)),
+            summary.walker,
+        ),
+        (
+            "hyprlock",
+            current(last.as_ref().map(|e| &e.hyprlock)),
+            summary.hyprlock,
+        ),
+        (
+            "starship",
+            current(last.as_ref().map(|e| &e.starship)),
+            summary.starship,
+        ),
+    ];
+
+    println!("Preset {}:", name.trim());
+    for (label, from, to) in rows {
+        if from == to {
+            println!("  {label:<9} {to} (unchanged)");
+        } else {
+            println!("  {label:<9} {from} -> {to}");
+        }
+    }
+    for error in &summary.errors {
+        println!("  error: {error}");
+    }
+    Ok(())
+}
+
+pub fn confirm_load() -> Result<bool> {
+    print!("Apply this preset? [y/N] ");
+    std::io::stdout().flush()?;
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    Ok(matches!(input.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+// History stores components in `preset save` flag syntax; show them like `PresetSummary`.
+fn format_history_spec(spec: &str) -> String {
+    match spec {
+        "none" | "auto" | "theme" => spec.to_string(),
+        _ => match spec.split_once(':') {
+            Some(("preset", name)) => format!("preset ({name})"),
+            Some(("named", name)) => format!("named ({name})"),
+            _ => format!("named ({spec})"),
+        },
+    }
+}
+
+pub fn save_preset(
+    name: &str,
+    entry: PresetEntry,
+    config: &ResolvedConfig,
+    overwrite: bool,
+) -> Result<()> {
     let trimmed = name.trim();
     if trimmed.is_empty() {
         return Err(anyhow!("missing preset name"));
@@ -224,22 +308,159 @@ pub fn save_preset(name: &str, entry: PresetEntry, config: &ResolvedConfig) -> R
         return Err(anyhow!(summary.errors.join("; ")));
     }
 
-    let mut file = load_presets()?;
+    let mut file = load_presets(config)?;
+    if !overwrite && file.preset.contains_key(trimmed) {
+        return Err(anyhow!("preset already exists: {trimmed}"));
+    }
     file.preset.insert(trimmed.to_string(), entry);
-    write_presets(&file)?;
+    write_presets(config, &file)?;
     Ok(())
 }
 
-pub fn remove_preset(name: &str) -> Result<()> {
+// What is applied right now, in `preset save` flag syntax, read from the files
+// theme-manager manages rather than from config defaults. Components that match no
+// known theme come back as `none`, which leaves them as they are on load.
+pub struct LiveComponents {
+    pub waybar: String,
+    pub walker: String,
+    pub hyprlock: String,
+    pub starship: String,
+}
+
+pub fn detect_live_components(config: &ResolvedConfig) -> Result<LiveComponents> {
+    let theme_name = current_theme_name(&config.current_theme_link)?
+        .ok_or_else(|| anyhow!("current theme not set: invalid link target"))?;
+    // `auto` may have been applied from the theme source or from the materialized copy.
+    let mut theme_dirs = vec![config.current_theme_link.clone()];
+    if let Ok(source) = theme_ops::resolve_theme_source(config, &theme_name) {
+        theme_dirs.push(source);
+    }
+    let auto_dirs =
+        |sub: &str| -> Vec<PathBuf> { theme_dirs.iter().map(|d| d.join(sub)).collect() };
+
+    let waybar = match_applied_files(
+        &config.waybar_dir,
+        &["config.jsonc", "style.css"],
+        &auto_dirs("waybar-theme"),
+        &config.waybar_themes_dir,
+    );
+    let hyprlock = match_applied_files(
+        &config.current_theme_link,
+        &["hyprlock.conf"],
+        &auto_dirs("hyprlock-theme"),
+        &config.hyprlock_themes_dir,
+    );
+    let walker = detect_walker(config);
+    let starship = detect_starship(config, &theme_dirs, &theme_name)?;
+    Ok(LiveComponents {
+        waybar,
+        walker,
+        hyprlock,
+        starship,
+    })
+}
+
+// Compares the applied files byte for byte, which covers symlink, copy, and hardlink modes.
+fn match_applied_files(
+    applied_dir: &Path,
+    files: &[&str],
+    auto_dirs: &[PathBuf],
+    named_root: &Path,
+) -> String {
+    let Some(applied) = read_all(applied_dir, files) else {
+        return "none".to_string();
+    };
+    if auto_dirs
+        .iter()
+        .any(|dir| read_all(dir, files).as_ref() == Some(&applied))
+    {
+        return "auto".to_string();
+    }
+    let mut names: Vec<String> = fs::read_dir(named_root)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .collect();
+    names.sort();
+    names
+        .into_iter()
+        .find(|name| read_all(&named_root.join(name), files).as_ref() == Some(&applied))
+        .unwrap_or_else(|| "none".to_string())
+}
+
+fn read_all(dir: &Path, files: &[&str]) -> Option<Vec<Vec<u8>>> {
+    files
+        .iter()
+        .map(|file| fs::read(dir.join(file)).ok())
+        .collect()
+}
+
+fn detect_walker(config: &ResolvedConfig) -> String {
+    let content = fs::read_to_string(config.walker_dir.join("config.toml")).unwrap_or_default();
+    let theme = content.lines().find_map(|line| {
+        let (lhs, rhs) = line.split_once('=')?;
+        (lhs.trim() == "theme").then(|| rhs.trim().trim_matches('"').to_string())
+    });
+    match theme {
+        Some(name) if name == walker::AUTO_THEME_NAME => "auto".to_string(),
+        Some(name) if config.walker_themes_dir.join(&name).is_dir() => name,
+        _ => "none".to_string(),
+    }
+}
+
+// Preset output can't be matched against a file, so a preset is taken from the last
+// history entry for this theme when nothing else matches.
+fn detect_starship(
+    config: &ResolvedConfig,
+    theme_dirs: &[PathBuf],
+    theme_name: &str,
+) -> Result<String> {
+    let Ok(applied) = fs::read(&config.starship_config) else {
+        return Ok("none".to_string());
+    };
+    if theme_dirs
+        .iter()
+        .filter_map(|dir| starship::find_theme_starship_config(dir))
+        .any(|path| starship::render_theme_config(&path).ok().as_ref() == Some(&applied))
+    {
+        return Ok("theme".to_string());
+    }
+    let mut named: Vec<PathBuf> = fs::read_dir(&config.starship_themes_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    named.sort();
+    if let Some(path) = named
+        .iter()
+        .find(|path| fs::read(path).ok().as_ref() == Some(&applied))
+    {
+        if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+            return Ok(format!("named:{stem}"));
+        }
+    }
+    let last_preset = history::read_entries(&config.history_file)?
+        .pop()
+        .filter(|last| last.theme == theme_name)
+        .and_then(|last| last.starship)
+        .filter(|spec| spec.starts_with("preset:"));
+    Ok(last_preset.unwrap_or_else(|| "none".to_string()))
+}
+
+pub fn remove_preset(config: &ResolvedConfig, name: &str) -> Result<()> {
     let trimmed = name.trim();
     if trimmed.is_empty() {
         return Err(anyhow!("missing preset name"));
     }
-    let mut file = load_presets()?;
+    let mut file = load_presets(config)?;
     if file.preset.remove(trimmed).is_none() {
         return Err(anyhow!("preset not found: {trimmed}"));
     }
-    write_presets(&file)?;
+    write_presets(config, &file)?;
     Ok(())
 }
 
@@ -297,16 +518,6 @@ fn parse_starship(
     }
 }
 
-fn is_broken_theme(path: &Path) -> bool {
-    if path.is_dir() {
-        return false;
-    }
-    if let Ok(true) = is_symlink(path) {
-        return fs::metadata(path).is_err();
-    }
-    true
-}
-
 fn format_waybar(value: &PresetWaybarValue) -> String {
     match value {
         PresetWaybarValue::None => "none".to_string(),
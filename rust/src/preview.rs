@@ -1,5 +1,181 @@
+use anyhow::Result;
 use std::fs;
+use std::io::{stdout, IsTerminal, Write};
 use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::omarchy::command_exists;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewBackendKind {
+    Kitty,
+    Sixel,
+    Chafa,
+    None,
+}
+
+pub fn detect_backend_kind() -> PreviewBackendKind {
+    detect_preview_backend_kind(
+        command_exists("kitty"),
+        command_exists("chafa"),
+        is_kitty_terminal(),
+        is_foot_terminal(),
+    )
+}
+
+fn detect_preview_backend_kind(
+    has_kitty: bool,
+    has_chafa: bool,
+    is_kitty_term: bool,
+    is_foot_term: bool,
+) -> PreviewBackendKind {
+    if has_kitty && is_kitty_term {
+        PreviewBackendKind::Kitty
+    } else if has_chafa && is_foot_term {
+        PreviewBackendKind::Sixel
+    } else if has_chafa {
+        PreviewBackendKind::Chafa
+    } else {
+        PreviewBackendKind::None
+    }
+}
+
+fn is_kitty_terminal() -> bool {
+    std::env::var("KITTY_WINDOW_ID").is_ok() || term_contains("kitty") || term_contains("ghostty")
+}
+
+fn is_foot_terminal() -> bool {
+    term_contains("foot") || term_program_contains("foot")
+}
+
+fn term_contains(value: &str) -> bool {
+    std::env::var("TERM")
+        .unwrap_or_default()
+        .to_lowercase()
+        .contains(value)
+}
+
+fn term_program_contains(value: &str) -> bool {
+    std::env::var("TERM_PROGRAM")
+        .unwrap_or_default()
+        .to_lowercase()
+        .contains(value)
+}
+
+pub fn render_theme_to_stdout(theme_dir: &Path, title: &str) -> Result<()> {
+    println!("{title}");
+    let image = find_theme_preview(theme_dir);
+    // Inline images only make sense on a terminal; pipes get the path instead.
+    let backend = if stdout().is_terminal() {
+        detect_backend_kind()
+    } else {
+        PreviewBackendKind::None
+    };
+    let rendered = match &image {
+        Some(path) => render_image_inline(backend, path),
+        None => false,
+    };
+    if !rendered {
+        match &image {
+            Some(path) => println!("Preview: {}", path.to_string_lossy()),
+            None => println!("Preview: none"),
+        }
+    }
+
+    let colors = theme_color_summary(theme_dir);
+    if colors.is_empty() {
+        println!("Colors: none found");
+    } else {
+        println!("Colors:");
+        for line in colors {
+            println!("  {line}");
+        }
+    }
+    Ok(())
+}
+
+fn render_image_inline(backend: PreviewBackendKind, path: &Path) -> bool {
+    let (cols, rows) = crossterm::terminal::size().unwrap_or((80, 24));
+    let size = format!("{}x{}", cols.max(1), (rows / 2).max(1));
+    let path_arg = path.to_string_lossy();
+    let output = match backend {
+        PreviewBackendKind::Kitty => {
+            return Command::new("kitty")
+                .args(["+kitten", "icat", "--stdin=no", path_arg.as_ref()])
+                .status()
+                .map(|status| status.success())
+                .unwrap_or(false);
+        }
+        PreviewBackendKind::Sixel => Command::new("chafa")
+            .args(["--format=sixels", "--size", &size, path_arg.as_ref()])
+            .output(),
+        PreviewBackendKind::Chafa => Command::new("chafa")
+            .args(["--format=symbols", "--size", &size, path_arg.as_ref()])
+            .output(),
+        PreviewBackendKind::None => return false,
+    };
+    match output {
+        Ok(output) if output.status.success() => {
+            let mut out = stdout();
+            out.write_all(&output.stdout).is_ok() && out.flush().is_ok()
+        }
+        _ => false,
+    }
+}
+
+// Color definitions from hyprland.conf, falling back to colors.toml.
+pub fn theme_color_summary(theme_dir: &Path) -> Vec<String> {
+    let hyprland = fs::read_to_string(theme_dir.join("hyprland.conf")).unwrap_or_default();
+    let lines: Vec<String> = hyprland
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#') && line.contains('='))
+        .filter(|line| {
+            let lower = line.to_lowercase();
+            lower.contains("rgb") || lower.contains("0x") || lower.contains('#')
+        })
+        .map(str::to_string)
+        .collect();
+    if !lines.is_empty() {
+        return lines;
+    }
+
+    let colors = fs::read_to_string(theme_dir.join("colors.toml")).unwrap_or_default();
+    colors
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && line.contains('='))
+        .map(str::to_string)
+        .collect()
+}
+
+// Config file shown in the TUI's theme code preview, as (title, path, syntax):
+// hyprland.conf, then theme.toml, colors.toml, or the first other `*.conf`. Themes
+// with none of these still get hyprland.conf so the preview reports it missing.
+pub fn primary_theme_doc(theme_dir: &Path) -> (String, PathBuf, &'static str) {
+    for (name, syntax) in [
+        ("hyprland.conf", "conf"),
+        ("theme.toml", "toml"),
+        ("colors.toml", "toml"),
+    ] {
+        let path = theme_dir.join(name);
+        if path.is_file() {
+            return (name.to_string(), path, syntax);
+        }
+    }
+    if let Some(path) = find_first_by_exts(theme_dir, &["conf"]) {
+        let title = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        return (title, path, "conf");
+    }
+    (
+        "hyprland.conf".to_string(),
+        theme_dir.join("hyprland.conf"),
+        "conf",
+    )
+}
 
 pub fn find_theme_preview(theme_dir: &Path) -> Option<PathBuf> {
     find_named_image(theme_dir, "preview")
@@ -8,8 +184,41 @@ pub fn find_theme_preview(theme_dir: &Path) -> Option<PathBuf> {
         .or_else(|| find_first_image(&theme_dir.join("backgrounds")))
 }
 
+const WAYBAR_PREVIEW_EXTS: &[&str] = &["png", "gif", "jpg", "jpeg", "webp"];
+
 pub fn find_waybar_preview(waybar_dir: &Path) -> Option<PathBuf> {
-    find_named_image(waybar_dir, "preview").or_else(|| find_first_image(waybar_dir))
+    waybar_preview_candidates(waybar_dir).into_iter().next()
+}
+
+// `preview.<ext>` in WAYBAR_PREVIEW_EXTS order, then any other image sorted by name.
+pub fn waybar_preview_candidates(waybar_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(waybar_dir) else {
+        return Vec::new();
+    };
+    let mut images: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && image_ext_rank(path, WAYBAR_PREVIEW_EXTS).is_some())
+        .collect();
+    images.sort_by_key(|path| {
+        let is_preview = path
+            .file_stem()
+            .and_then(|name| name.to_str())
+            .map(|name| name.eq_ignore_ascii_case("preview"))
+            .unwrap_or(false);
+        let rank = if is_preview {
+            image_ext_rank(path, WAYBAR_PREVIEW_EXTS).unwrap_or(usize::MAX)
+        } else {
+            usize::MAX
+        };
+        (rank, path.clone())
+    });
+    images
+}
+
+fn image_ext_rank(path: &Path, exts: &[&str]) -> Option<usize> {
+    let ext = path.extension().and_then(|ext| ext.to_str())?;
+    exts.iter()
+        .position(|wanted| ext.eq_ignore_ascii_case(wanted))
 }
 
 pub fn find_walker_preview(walker_dir: &Path) -> Option<PathBuf> {
@@ -25,10 +234,8 @@ fn find_named_file(dir: &Path, name: &str) -> Option<PathBuf> {
         let entry = entry.ok()?;
         let path = entry.path();
         if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
-            if file_name.to_lowercase() == name_lower {
-                if path.is_file() {
-                    return Some(path);
-                }
+            if file_name.to_lowercase() == name_lower && path.is_file() {
+                return Some(path);
             }
         }
     }
@@ -95,6 +302,26 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn preview_backend_detection_precedence() {
+        assert_eq!(
+            detect_preview_backend_kind(true, true, true, true),
+            PreviewBackendKind::Kitty
+        );
+        assert_eq!(
+            detect_preview_backend_kind(false, true, false, true),
+            PreviewBackendKind::Sixel
+        );
+        assert_eq!(
+            detect_preview_backend_kind(false, true, false, false),
+            PreviewBackendKind::Chafa
+        );
+        assert_eq!(
+            detect_preview_backend_kind(false, false, false, true),
+            PreviewBackendKind::None
+        );
+    }
+
     #[test]
     fn theme_preview_accepts_common_image_extensions() {
         let temp = TempDir::new().unwrap();
@@ -106,6 +333,44 @@ mod tests {
         assert_eq!(find_theme_preview(&theme_dir), Some(preview));
     }
 
+    #[test]
+    fn primary_theme_doc_falls_back_when_hyprland_conf_is_missing() {
+        let temp = TempDir::new().unwrap();
+        let theme_dir = temp.path().join("theme");
+        fs::create_dir_all(&theme_dir).unwrap();
+        let doc = |dir: &Path| {
+            let (title, path, syntax) = primary_theme_doc(dir);
+            (
+                title,
+                path.file_name().unwrap().to_string_lossy().to_string(),
+                syntax,
+            )
+        };
+
+        let hyprland = (
+            "hyprland.conf".to_string(),
+            "hyprland.conf".to_string(),
+            "conf",
+        );
+        assert_eq!(doc(&theme_dir), hyprland);
+
+        fs::write(theme_dir.join("waybar.conf"), "bar").unwrap();
+        fs::write(theme_dir.join("kitty.conf"), "term").unwrap();
+        let first_conf = ("kitty.conf".to_string(), "kitty.conf".to_string(), "conf");
+        assert_eq!(doc(&theme_dir), first_conf);
+
+        fs::write(theme_dir.join("colors.toml"), "accent = \"#fff\"").unwrap();
+        let colors = ("colors.toml".to_string(), "colors.toml".to_string(), "toml");
+        assert_eq!(doc(&theme_dir), colors);
+
+        fs::write(theme_dir.join("theme.toml"), "variant = \"dark\"").unwrap();
+        let theme_toml = ("theme.toml".to_string(), "theme.toml".to_string(), "toml");
+        assert_eq!(doc(&theme_dir), theme_toml);
+
+        fs::write(theme_dir.join("hyprland.conf"), "$accent = rgb(ffffff)").unwrap();
+        assert_eq!(doc(&theme_dir), hyprland);
+    }
+
     #[test]
     fn walker_preview_prefers_named_image_before_fallback() {
         let temp = TempDir::new().unwrap();
@@ -118,4 +383,62 @@ mod tests {
 
         assert_eq!(find_walker_preview(&walker_dir), Some(preferred));
     }
+
+    #[test]
+    fn waybar_preview_candidates_follow_preview_then_name_order() {
+        let temp = TempDir::new().unwrap();
+        let waybar_dir = temp.path().join("waybar-theme");
+        fs::create_dir_all(&waybar_dir).unwrap();
+        for name in [
+            "zeta.jpg",
+            "alpha.webp",
+            "preview.jpg",
+            "preview.gif",
+            "preview.png",
+            "config.jsonc",
+            "style.css",
+        ] {
+            fs::write(waybar_dir.join(name), b"test").unwrap();
+        }
+
+        let names: Vec<String> = waybar_preview_candidates(&waybar_dir)
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "preview.png",
+                "preview.gif",
+                "preview.jpg",
+                "alpha.webp",
+                "zeta.jpg"
+            ]
+        );
+    }
+
+    #[test]
+    fn waybar_preview_accepts_gif() {
+        let temp = TempDir::new().unwrap();
+        let waybar_dir = temp.path().join("waybar-theme");
+        fs::create_dir_all(&waybar_dir).unwrap();
+        let fallback = waybar_dir.join("aaa.png");
+        let preview = waybar_dir.join("preview.gif");
+        fs::write(&fallback, b"test").unwrap();
+        fs::write(&preview, b"test").unwrap();
+
+        assert_eq!(find_waybar_preview(&waybar_dir), Some(preview));
+    }
+
+    #[test]
+    fn waybar_preview_is_none_without_images() {
+        let temp = TempDir::new().unwrap();
+        let waybar_dir = temp.path().join("waybar-theme");
+        fs::create_dir_all(&waybar_dir).unwrap();
+        fs::write(waybar_dir.join("config.jsonc"), b"{}").unwrap();
+        fs::write(waybar_dir.join("style.css"), b"").unwrap();
+
+        assert_eq!(find_waybar_preview(&waybar_dir), None);
+        assert_eq!(find_waybar_preview(&temp.path().join("missing")), None);
+    }
 }
@@ -0,0 +1,138 @@
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::Path;
+
+use crate::config::ResolvedConfig;
+use crate::paths::{normalize_theme_name, title_case_theme};
+
+// Every file the skeleton contains, relative to the theme directory. `{title}` is
+// replaced with the title-cased theme name.
+const FILES: &[(&str, &str)] = &[
+    ("theme.toml", THEME_TOML),
+    ("hyprland.conf", HYPRLAND_CONF),
+    ("waybar-theme/config.jsonc", WAYBAR_CONFIG),
+    ("waybar-theme/style.css", WAYBAR_STYLE),
+    ("walker-theme/style.css", WALKER_STYLE),
+    ("hyprlock-theme/hyprlock.conf", HYPRLOCK_CONF),
+    ("starship.yaml", STARSHIP_CONFIG),
+];
+
+const THEME_TOML: &str = r#"# {title}
+variant = "dark"
+tags = []
+"#;
+
+const HYPRLAND_CONF: &str = r#"# {title} palette; `theme-manager palette` reads the $variables below.
+$background = rgb(1e1e2e)
+$foreground = rgb(cdd6f4)
+$accent = rgb(89b4fa)
+$muted = rgb(6c7086)
+$urgent = rgb(f38ba8)
+
+general {
+    col.active_border = $accent
+    col.inactive_border = $muted
+}
+"#;
+
+const WAYBAR_CONFIG: &str = r#"// {title} Waybar layout
+{
+  "layer": "top",
+  "position": "top",
+  "modules-left": ["hyprland/workspaces"],
+  "modules-center": ["clock"],
+  "modules-right": ["network", "pulseaudio", "battery"]
+}
+"#;
+
+const WAYBAR_STYLE: &str = r#"/* {title} Waybar style */
+@define-color background #1e1e2e;
+@define-color foreground #cdd6f4;
+@define-color accent #89b4fa;
+
+* {
+  color: @foreground;
+}
+
+window#waybar {
+  background: @background;
+}
+
+#workspaces button.active {
+  color: @accent;
+}
+"#;
+
+const WALKER_STYLE: &str = r#"/* {title} Walker style */
+@define-color selected-text #89b4fa;
+@define-color text #cdd6f4;
+@define-color base #1e1e2e;
+@define-color border #6c7086;
+@define-color foreground #cdd6f4;
+@define-color background #1e1e2e;
+"#;
+
+const HYPRLOCK_CONF: &str = r#"# {title} Hyprlock colors
+$color = rgba(1e1e2eff)
+$inner_color = rgba(1e1e2ecc)
+$outer_color = rgba(89b4faff)
+$font_color = rgba(cdd6f4ff)
+$check_color = rgba(a6e3a1ff)
+"#;
+
+const STARSHIP_CONFIG: &str = r#"# {title} Starship prompt (YAML, converted to starship.toml on apply)
+format: "$directory$git_branch$character"
+character:
+  success_symbol: "[❯](bold #89b4fa)"
+  error_symbol: "[❯](bold #f38ba8)"
+"#;
+
+// A 1x1 PNG in the $background color, so the skeleton has a wallpaper and passes `lint`.
+const PLACEHOLDER_BACKGROUND: &str = "backgrounds/1-placeholder.png";
+const PLACEHOLDER_PNG: &[u8] = &[
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x00, 0x00, 0x00, 0x90, 0x77, 0x53,
+    0xde, 0x00, 0x00, 0x00, 0x0c, 0x49, 0x44, 0x41, 0x54, 0x78, 0xda, 0x63, 0x90, 0x93, 0xd3, 0x03,
+    0x00, 0x00, 0xc8, 0x00, 0x6b, 0x8d, 0x23, 0xfb, 0x3b, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e,
+    0x44, 0xae, 0x42, 0x60, 0x82,
+];
+
+pub fn cmd_gen_theme(config: &ResolvedConfig, name: &str) -> Result<()> {
+    let theme_name = normalize_theme_name(name);
+    if theme_name.is_empty() {
+        return Err(anyhow!("missing theme name"));
+    }
+    if theme_name.contains('/') || theme_name == "." || theme_name == ".." {
+        return Err(anyhow!("invalid theme name: {name}"));
+    }
+    let theme_path = config.theme_root_dir.join(&theme_name);
+    if fs::symlink_metadata(&theme_path).is_ok() {
+        return Err(anyhow!("theme already exists: {theme_name}"));
+    }
+
+    fs::create_dir_all(&config.theme_root_dir)?;
+    if let Err(err) = write_skeleton(&theme_path, &title_case_theme(&theme_name)) {
+        let _ = fs::remove_dir_all(&theme_path);
+        return Err(err);
+    }
+    println!(
+        "theme-manager: created {theme_name} at {}",
+        theme_path.to_string_lossy()
+    );
+    println!("theme-manager: replace {PLACEHOLDER_BACKGROUND} with your own wallpaper");
+    Ok(())
+}
+
+fn write_skeleton(theme_path: &Path, title: &str) -> Result<()> {
+    fs::create_dir(theme_path)?;
+    fs::create_dir(theme_path.join("backgrounds"))?;
+    fs::write(theme_path.join(PLACEHOLDER_BACKGROUND), PLACEHOLDER_PNG)?;
+    for (rel, template) in FILES {
+        let path = theme_path.join(rel);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, template.replace("{title}", title))?;
+    }
+    Ok(())
+}
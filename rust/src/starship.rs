@@ -1,11 +1,14 @@
 use anyhow::{anyhow, Result};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
 use crate::config::ResolvedConfig;
+use crate::error::ThemeError;
 use crate::omarchy;
 use crate::omarchy_defaults;
 use crate::omarchy_defaults::SymlinkEnsureResult;
+use crate::output;
 use crate::theme_ops::{CommandContext, StarshipMode};
 
 const OMARCHY_DEFAULT_THEME_NAME: &str = "omarchy-default";
@@ -30,7 +33,8 @@ pub fn apply_starship(ctx: &CommandContext<'_>, theme_dir: &Path) -> Result<()>
         StarshipMode::Theme { path } => {
             let theme_path = match path {
                 Some(path) => path.clone(),
-                None => theme_dir.join("starship.toml"),
+                None => find_theme_starship_config(theme_dir)
+                    .unwrap_or_else(|| theme_dir.join("starship.toml")),
             };
             copy_theme(ctx, config_path, &theme_path)
         }
@@ -38,8 +42,16 @@ pub fn apply_starship(ctx: &CommandContext<'_>, theme_dir: &Path) -> Result<()>
 }
 
 fn apply_preset(ctx: &CommandContext<'_>, config_path: &Path, preset: &str) -> Result<()> {
+    // Presets come from the starship binary itself; named and theme configs are plain
+    // file copies and still work without it.
     if !omarchy::command_exists("starship") {
-        return Err(anyhow!("starship not found in PATH"));
+        if !ctx.quiet {
+            eprintln!(
+                "{} starship not installed; install it to use presets (skipping preset {preset})",
+                output::warning_prefix()
+            );
+        }
+        return Ok(());
     }
     if !ctx.quiet {
         println!("theme-manager: applying starship preset {preset}");
@@ -65,10 +77,11 @@ fn apply_named(
         theme_path.set_extension("toml");
     }
     if !theme_path.is_file() {
-        return Err(anyhow!(
-            "starship theme not found: {}",
-            theme_path.to_string_lossy()
-        ));
+        return Err(ThemeError::ComponentMissing {
+            component: "starship",
+            path: theme_path,
+        }
+        .into());
     }
     if !ctx.quiet {
         println!(
@@ -80,12 +93,22 @@ fn apply_named(
     Ok(())
 }
 
+// Themes ship their Starship config as either `starship.toml` or `starship.yaml`;
+// the TOML file wins when both exist.
+pub fn find_theme_starship_config(theme_dir: &Path) -> Option<PathBuf> {
+    ["starship.toml", "starship.yaml"]
+        .iter()
+        .map(|name| theme_dir.join(name))
+        .find(|path| path.is_file())
+}
+
 fn copy_theme(ctx: &CommandContext<'_>, config_path: &Path, theme_path: &Path) -> Result<()> {
     if !theme_path.is_file() {
-        return Err(anyhow!(
-            "starship theme file not found: {}",
-            theme_path.to_string_lossy()
-        ));
+        return Err(ThemeError::ComponentMissing {
+            component: "starship",
+            path: theme_path.to_path_buf(),
+        }
+        .into());
     }
     if !ctx.quiet {
         println!(
@@ -93,10 +116,52 @@ fn copy_theme(ctx: &CommandContext<'_>, config_path: &Path, theme_path: &Path) -
             theme_path.to_string_lossy()
         );
     }
-    fs::copy(theme_path, config_path)?;
+    fs::write(config_path, render_theme_config(theme_path)?)?;
+    Ok(())
+}
+
+// The starship.toml content applying `theme_path` produces: TOML files as-is, YAML
+// converted. `preset save --from-current` compares the applied file against this.
+pub fn render_theme_config(theme_path: &Path) -> Result<Vec<u8>> {
+    if !is_yaml(theme_path) {
+        return Ok(fs::read(theme_path)?);
+    }
+    let content = fs::read_to_string(theme_path)?;
+    let converted = yaml_to_toml(&content).map_err(|err| {
+        anyhow!(
+            "cannot convert {} to TOML: {err}",
+            theme_path.to_string_lossy()
+        )
+    })?;
+    Ok(converted.into_bytes())
+}
+
+// Parses a theme's starship.toml/.yaml the way `copy_theme` would read it.
+pub fn validate_theme_config(path: &Path) -> Result<()> {
+    let content = fs::read_to_string(path)?;
+    if is_yaml(path) {
+        yaml_to_toml(&content)?;
+    } else {
+        content.parse::<toml::Table>()?;
+    }
     Ok(())
 }
 
+fn is_yaml(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml"))
+}
+
+// Starship only reads TOML, so YAML theme configs are re-serialized on apply.
+fn yaml_to_toml(content: &str) -> Result<String> {
+    let value: toml::Value = serde_yaml::from_str(content)?;
+    if !value.is_table() {
+        return Err(anyhow!("top level must be a mapping"));
+    }
+    Ok(toml::to_string(&value)?)
+}
+
 pub fn ensure_omarchy_default_theme_link(config: &ResolvedConfig, quiet: bool) -> Result<()> {
     let Some(default_theme_file) =
         omarchy_defaults::resolve_starship_default(config).map(|d| d.path)
@@ -129,12 +194,126 @@ pub fn ensure_omarchy_default_theme_link(config: &ResolvedConfig, quiet: bool) -
         SymlinkEnsureResult::SkippedNonSymlink => {
             if !quiet {
                 eprintln!(
-          "theme-manager: warning: preserving non-symlink path {}; cannot link Omarchy default Starship theme",
-          link_path.to_string_lossy()
-        );
+                    "{} preserving non-symlink path {}; cannot link Omarchy default Starship theme",
+                    output::warning_prefix(),
+                    link_path.to_string_lossy()
+                );
             }
         }
         SymlinkEnsureResult::Unchanged => {}
     }
     Ok(())
 }
+
+pub fn cmd_list(config: &ResolvedConfig) -> Result<()> {
+    println!("Presets (preset:<name>):");
+    print_names(&list_starship_presets());
+    println!(
+        "Named themes (named:<name>) in {}:",
+        config.starship_themes_dir.to_string_lossy()
+    );
+    print_names(&list_starship_themes(&config.starship_themes_dir)?);
+    Ok(())
+}
+
+fn print_names(names: &[String]) {
+    if names.is_empty() {
+        println!("  (none)");
+    }
+    for name in names {
+        println!("  {name}");
+    }
+}
+
+pub fn list_starship_presets() -> Vec<String> {
+    if !omarchy::command_exists("starship") {
+        return Vec::new();
+    }
+    if let Ok(output) = Command::new("starship").args(["preset", "--list"]).output() {
+        if output.status.success() {
+            return parse_lines(&output.stdout);
+        }
+    }
+    if let Ok(output) = Command::new("starship").args(["preset", "-l"]).output() {
+        if output.status.success() {
+            return parse_lines(&output.stdout);
+        }
+    }
+    Vec::new()
+}
+
+pub fn list_starship_themes(dir: &Path) -> Result<Vec<String>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut themes = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_file() {
+            if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+                if ext.eq_ignore_ascii_case("toml") {
+                    if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                        themes.push(stem.to_string());
+                    }
+                }
+            }
+        }
+    }
+    themes.sort();
+    Ok(themes)
+}
+
+fn parse_lines(output: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(output)
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| line.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn find_theme_starship_config_accepts_either_extension() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        assert_eq!(find_theme_starship_config(dir), None);
+
+        fs::write(dir.join("starship.yaml"), "").unwrap();
+        assert_eq!(
+            find_theme_starship_config(dir),
+            Some(dir.join("starship.yaml"))
+        );
+
+        fs::write(dir.join("starship.toml"), "").unwrap();
+        assert_eq!(
+            find_theme_starship_config(dir),
+            Some(dir.join("starship.toml"))
+        );
+
+        fs::remove_file(dir.join("starship.yaml")).unwrap();
+        assert_eq!(
+            find_theme_starship_config(dir),
+            Some(dir.join("starship.toml"))
+        );
+    }
+
+    #[test]
+    fn yaml_to_toml_converts_nested_mappings() {
+        let yaml = "format: \"$directory$character\"\nadd_newline: false\ncharacter:\n  success_symbol: \"[>](green)\"\n";
+        let converted: toml::Value = toml::from_str(&yaml_to_toml(yaml).unwrap()).unwrap();
+        assert_eq!(converted["format"].as_str(), Some("$directory$character"));
+        assert_eq!(converted["add_newline"].as_bool(), Some(false));
+        assert_eq!(
+            converted["character"]["success_symbol"].as_str(),
+            Some("[>](green)")
+        );
+
+        assert!(yaml_to_toml("- just\n- a list\n").is_err());
+    }
+}
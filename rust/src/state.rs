@@ -0,0 +1,135 @@
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use walkdir::WalkDir;
+
+use crate::config::ResolvedConfig;
+use crate::lock;
+
+// Everything theme-manager writes outside the theme directories, keyed by its name in a backup.
+fn managed_paths(config: &ResolvedConfig) -> Vec<(&'static str, PathBuf)> {
+    let mut paths = Vec::new();
+    if let Some(current_dir) = config.current_theme_link.parent() {
+        paths.push(("current", current_dir.to_path_buf()));
+    }
+    paths.push((
+        "waybar/config.jsonc",
+        config.waybar_dir.join("config.jsonc"),
+    ));
+    paths.push(("waybar/style.css", config.waybar_dir.join("style.css")));
+    paths.push(("walker/config.toml", config.walker_dir.join("config.toml")));
+    paths.push(("hyprlock/hyprlock.conf", config.hyprlock_host_path()));
+    paths.push(("starship.toml", config.starship_config.clone()));
+    paths.push(("presets.toml", config.presets_file.clone()));
+    paths
+}
+
+pub fn cmd_backup(config: &ResolvedConfig, dir: &Path) -> Result<()> {
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|err| anyhow!("time error: {err}"))?
+        .as_secs();
+    let backup_dir = dir.join(format!("theme-manager-backup-{stamp}"));
+    if backup_dir.exists() {
+        return Err(anyhow!(
+            "backup already exists: {}",
+            backup_dir.to_string_lossy()
+        ));
+    }
+    fs::create_dir_all(&backup_dir)?;
+
+    let mut saved = 0;
+    for (name, source) in managed_paths(config) {
+        if fs::symlink_metadata(&source).is_err() {
+            continue;
+        }
+        copy_path(&source, &backup_dir.join(name))?;
+        saved += 1;
+    }
+    if saved == 0 {
+        let _ = fs::remove_dir(&backup_dir);
+        return Err(anyhow!("nothing to back up"));
+    }
+    println!("{}", backup_dir.to_string_lossy());
+    Ok(())
+}
+
+pub fn cmd_restore(config: &ResolvedConfig, dir: &Path, yes: bool) -> Result<()> {
+    let entries: Vec<(&str, PathBuf)> = managed_paths(config)
+        .into_iter()
+        .filter(|(name, _)| fs::symlink_metadata(dir.join(name)).is_ok())
+        .collect();
+    if entries.is_empty() {
+        return Err(anyhow!(
+            "not a theme-manager backup: {}",
+            dir.to_string_lossy()
+        ));
+    }
+
+    if !yes && !confirm_restore(dir, &entries)? {
+        return Err(anyhow!("restore cancelled"));
+    }
+
+    let _lock = lock::acquire(config)?;
+    for (name, dest) in &entries {
+        remove_path(dest)?;
+        copy_path(&dir.join(name), dest)?;
+    }
+    println!(
+        "theme-manager: restored {} item(s) from {}",
+        entries.len(),
+        dir.to_string_lossy()
+    );
+    Ok(())
+}
+
+fn confirm_restore(dir: &Path, entries: &[(&str, PathBuf)]) -> Result<bool> {
+    println!("Restore from {}:", dir.to_string_lossy());
+    for (_, dest) in entries {
+        println!("  {}", dest.to_string_lossy());
+    }
+    print!("Overwrite these paths? [y/N] ");
+    std::io::stdout().flush()?;
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    Ok(matches!(input.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+// Copies files and directories, keeping symlinks as symlinks.
+fn copy_path(source: &Path, dest: &Path) -> Result<()> {
+    for entry in WalkDir::new(source).follow_links(false) {
+        let entry = entry?;
+        let entry_path = entry.path();
+        let rel = entry_path.strip_prefix(source)?;
+        let target_path = if rel.as_os_str().is_empty() {
+            dest.to_path_buf()
+        } else {
+            dest.join(rel)
+        };
+        if let Some(parent) = target_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file_type = entry.file_type();
+        if file_type.is_dir() {
+            fs::create_dir_all(&target_path)?;
+        } else if file_type.is_symlink() {
+            let link_target = fs::read_link(entry_path)?;
+            std::os::unix::fs::symlink(link_target, &target_path)?;
+        } else {
+            fs::copy(entry_path, &target_path)?;
+        }
+    }
+    Ok(())
+}
+
+fn remove_path(path: &Path) -> Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_dir() => fs::remove_dir_all(path)?,
+        Ok(_) => fs::remove_file(path)?,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+    Ok(())
+}
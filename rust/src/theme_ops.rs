@@ -1,17 +1,27 @@
 use anyhow::{anyhow, Result};
-use std::collections::HashSet;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashSet};
 use std::env;
+use std::ffi::OsString;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use walkdir::WalkDir;
 
 use crate::config::ResolvedConfig;
+use crate::error::ThemeError;
+use crate::history;
 use crate::hyprlock;
+use crate::lock;
 use crate::omarchy;
+use crate::output;
 use crate::paths::{
-    current_theme_dir, current_theme_name, normalize_theme_name, resolve_link_target,
-    title_case_theme,
+    current_theme_dir, current_theme_name, current_theme_variant, load_theme_meta,
+    normalize_theme_name, resolve_link_target, theme_display_name, theme_has_tag,
 };
+use crate::preview;
 use crate::starship;
 use crate::walker;
 use crate::waybar;
@@ -45,6 +55,43 @@ pub enum StarshipMode {
     Theme { path: Option<PathBuf> },
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackgroundChoice {
+    Next,
+    Random,
+    Keep,
+    Named(String),
+}
+
+impl BackgroundChoice {
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "next" => BackgroundChoice::Next,
+            "random" => BackgroundChoice::Random,
+            "keep" => BackgroundChoice::Keep,
+            name => BackgroundChoice::Named(name.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub fn hex(&self) -> String {
+        if self.a == 0xff {
+            format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+        } else {
+            format!("#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
+        }
+    }
+}
+
 pub struct CommandContext<'a> {
     pub config: &'a ResolvedConfig,
     pub quiet: bool,
@@ -58,6 +105,66 @@ pub struct CommandContext<'a> {
     pub hyprlock_name: Option<String>,
     pub starship_mode: StarshipMode,
     pub debug_awww: bool,
+    pub print_awww_cmd: bool,
+    pub force_bg: bool,
+    pub background: BackgroundChoice,
+    pub wait_for_waybar: bool,
+    pub timings: bool,
+    pub rng: RefCell<StdRng>,
+}
+
+// `--seed` replays the same random choices; without it every run draws fresh entropy.
+pub fn seeded_rng(seed: Option<u64>) -> RefCell<StdRng> {
+    RefCell::new(match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    })
+}
+
+// `--timings`: wall-clock time of each `set` phase, printed to stderr at the end.
+struct PhaseTimer {
+    enabled: bool,
+    started: Instant,
+    last: Instant,
+    phases: Vec<(&'static str, Duration)>,
+}
+
+impl PhaseTimer {
+    fn new(enabled: bool) -> Self {
+        let now = Instant::now();
+        Self {
+            enabled,
+            started: now,
+            last: now,
+            phases: Vec::new(),
+        }
+    }
+
+    // Records the time since the previous mark as `phase`.
+    fn mark(&mut self, phase: &'static str) {
+        if !self.enabled {
+            return;
+        }
+        let now = Instant::now();
+        self.phases.push((phase, now - self.last));
+        self.last = now;
+    }
+
+    fn report(&self, theme_name: &str) {
+        if !self.enabled {
+            return;
+        }
+        let millis = |elapsed: Duration| elapsed.as_secs_f64() * 1000.0;
+        eprintln!("theme-manager: timings for {theme_name}:");
+        for (phase, elapsed) in &self.phases {
+            eprintln!("  {phase:<10} {:>9.1}ms", millis(*elapsed));
+        }
+        eprintln!(
+            "  {:<10} {:>9.1}ms",
+            "total",
+            millis(self.started.elapsed())
+        );
+    }
 }
 
 pub fn waybar_from_defaults(config: &ResolvedConfig) -> (WaybarMode, Option<String>) {
@@ -94,6 +201,7 @@ pub fn starship_from_defaults(config: &ResolvedConfig) -> StarshipMode {
                 StarshipMode::None
             }
         }
+        Some("theme") => StarshipMode::Theme { path: None },
         _ => StarshipMode::None,
     }
 }
@@ -106,61 +214,149 @@ pub fn hyprlock_from_defaults(config: &ResolvedConfig) -> (HyprlockMode, Option<
     }
 }
 
-pub fn cmd_list(config: &ResolvedConfig) -> Result<()> {
+pub fn cmd_list(
+    config: &ResolvedConfig,
+    tag: Option<&str>,
+    show_tags: bool,
+    mark_current: bool,
+) -> Result<()> {
     let entries = sorted_theme_entries_for_config(config)?;
+    let current = if mark_current {
+        current_theme_name(&config.current_theme_link)?
+    } else {
+        None
+    };
     for name in entries {
-        println!("{}", title_case_theme(&name));
+        let theme_path = resolve_theme_path(config, &name)?;
+        if tag.is_some_and(|tag| !theme_has_tag(&theme_path, tag)) {
+            continue;
+        }
+        let title = theme_display_name(&theme_path, &name);
+        let tags = if show_tags {
+            load_theme_meta(&theme_path)
+                .map(|meta| meta.tags)
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let marker = if current.as_deref() == Some(name.as_str()) {
+            " *"
+        } else {
+            ""
+        };
+        if tags.is_empty() {
+            println!("{title}{marker}");
+        } else {
+            println!("{title} [{}]{marker}", tags.join(", "));
+        }
     }
     Ok(())
 }
 
+// Prints the values accepted by `waybar`/`walker`/`hyprlock <mode>`, with the
+// Omarchy default pinned first among the named themes.
+pub fn print_component_options(themes_dir: &Path, mut names: Vec<String>, default_available: bool) {
+    let has_default = names.iter().any(|name| name == "omarchy-default");
+    names.retain(|name| name != "omarchy-default");
+    if has_default || default_available {
+        names.insert(0, "omarchy-default".to_string());
+    }
+    println!("Modes:");
+    println!("  auto");
+    println!("  none");
+    println!("Named themes in {}:", themes_dir.to_string_lossy());
+    if names.is_empty() {
+        println!("  (none)");
+    }
+    for name in names {
+        println!("  {name}");
+    }
+}
+
 pub fn cmd_set(ctx: &CommandContext<'_>, theme_name: &str) -> Result<()> {
-    let normalized = normalize_theme_name(theme_name);
-    let theme_path = resolve_theme_path(ctx.config, &normalized)?;
+    let _lock = lock::acquire(ctx.config)?;
+    set_theme(ctx, theme_name)
+}
 
-    if is_broken_symlink(&theme_path)? {
-        return Err(anyhow!(
-            "theme symlink is broken: {}",
-            theme_path.to_string_lossy()
-        ));
-    }
-    if !theme_path.is_dir() && !is_symlink(&theme_path)? {
-        if normalized != theme_name {
-            return Err(anyhow!(
-                "theme not found: {normalized} (from '{theme_name}')"
-            ));
+fn set_theme(ctx: &CommandContext<'_>, theme_name: &str) -> Result<()> {
+    let mut timer = PhaseTimer::new(ctx.timings);
+    let normalized = normalize_theme_name(theme_name);
+    let theme_source = resolve_theme_source(ctx.config, theme_name)?;
+    let previous_theme = current_theme_name(&ctx.config.current_theme_link)?;
+    if let BackgroundChoice::Named(name) = &ctx.background {
+        let images = collect_backgrounds(ctx.config, &theme_source, Some(&normalized))?;
+        if !images.iter().any(|image| background_matches(image, name)) {
+            return Err(anyhow!("background not found for {normalized}: {name}"));
         }
-        return Err(anyhow!("theme not found: {normalized}"));
+    }
+    if ctx.background == BackgroundChoice::Keep
+        && previous_theme.as_deref() != Some(normalized.as_str())
+    {
+        keep_background_across_switch(ctx.config, previous_theme.as_deref())?;
     }
 
     omarchy::ensure_awww_daemon(ctx.config, ctx.quiet);
 
-    let theme_source = resolve_link_target(&theme_path)?;
     let staging_dir = prepare_staging_dir(&theme_source, &ctx.config.current_theme_link)?;
+    // Older layouts made current/theme a symlink into the themes dir; drop the link
+    // itself so neither templates nor the rename below touch the theme it points at.
+    detach_current_theme_link(&ctx.config.current_theme_link)?;
     omarchy::run_optional("omarchy-theme-set-templates", &[], ctx.quiet)?;
     replace_theme_dir(&staging_dir, &ctx.config.current_theme_link)?;
     write_theme_name(&ctx.config.current_theme_link, &normalized)?;
+    timer.mark("staging");
 
     let current_theme_dir = current_theme_dir(&ctx.config.current_theme_link)?;
 
     let mut waybar_restart = None;
     if !ctx.skip_apps {
         waybar_restart = waybar::prepare_waybar(ctx, &theme_source)?;
+        timer.mark("waybar");
         walker::prepare_walker(ctx, &theme_source)?;
+        timer.mark("walker");
         hyprlock::prepare_hyprlock(ctx, &theme_source)?;
+        timer.mark("hyprlock");
         starship::apply_starship(ctx, &theme_source)?;
+        timer.mark("starship");
     }
 
     if !ctx.skip_apps {
         if ctx.config.awww_transition && omarchy::command_exists("awww") {
             omarchy::stop_swaybg();
-            cycle_background(ctx, &current_theme_dir)?;
-            let _ = omarchy::run_awww_transition(ctx.config, ctx.quiet, ctx.debug_awww);
+            // Every theme's images live under current/theme/backgrounds, so the same link
+            // target after a theme switch can still be a different wallpaper.
+            let changed = select_background(ctx, &current_theme_dir)?
+                || previous_theme.as_deref() != Some(normalized.as_str());
+            if changed || ctx.force_bg {
+                let _ = omarchy::run_awww_transition(
+                    ctx.config,
+                    ctx.quiet,
+                    ctx.debug_awww,
+                    ctx.print_awww_cmd,
+                    &mut *ctx.rng.borrow_mut(),
+                );
+            } else if !ctx.quiet {
+                eprintln!("theme-manager: background unchanged; skipping transition");
+            }
         } else {
-            omarchy::run_required("omarchy-theme-bg-next", &[], ctx.quiet)?;
+            match ctx.background {
+                BackgroundChoice::Next => {
+                    omarchy::run_required("omarchy-theme-bg-next", &[], ctx.quiet)?
+                }
+                BackgroundChoice::Keep => {}
+                _ => {
+                    select_background(ctx, &current_theme_dir)?;
+                    omarchy::restart_swaybg(&ctx.config.current_background_link, ctx.quiet);
+                }
+            }
         }
-        omarchy::reload_components(ctx.quiet, waybar_restart, ctx.config.waybar_restart_logs)?;
-        omarchy::apply_theme_setters(ctx.quiet)?;
+        timer.mark("background");
+        omarchy::reload_components(ctx.config, ctx.quiet, waybar_restart)?;
+        omarchy::apply_theme_setters(ctx.config, ctx.quiet)?;
+        if ctx.wait_for_waybar {
+            omarchy::wait_for_waybar(Duration::from_millis(ctx.config.restart_wait_ms), ctx.quiet);
+        }
+        timer.mark("reload");
     }
 
     if !ctx.skip_hook {
@@ -169,21 +365,109 @@ pub fn cmd_set(ctx: &CommandContext<'_>, theme_name: &str) -> Result<()> {
             std::env::var("HOME").unwrap_or_default()
         ));
         let _ = omarchy::run_hook(&hook_path, &[&normalized], ctx.quiet);
+        if ctx.config.run_theme_hooks {
+            run_theme_hook(ctx, &theme_source, &normalized);
+        }
+    }
+
+    if ctx.config.notify_on_change && !ctx.quiet {
+        let icon = preview::find_theme_preview(&theme_source);
+        omarchy::notify_theme_changed(
+            &theme_display_name(&theme_source, &normalized),
+            icon.as_deref(),
+        );
+    }
+
+    if !ctx.skip_apps {
+        if let Some(cmd) = &ctx.config.on_apply_cmd {
+            omarchy::spawn_on_apply(cmd, &normalized, ctx.quiet);
+        }
+    }
+    timer.report(&normalized);
+
+    if let Err(err) = history::record(ctx, &normalized) {
+        eprintln!(
+            "{} failed to record theme history: {err}",
+            crate::output::warning_prefix()
+        );
     }
 
     Ok(())
 }
 
-pub fn cmd_next(ctx: &CommandContext<'_>) -> Result<()> {
-    let entries = sorted_theme_entries_for_config(ctx.config)?;
+pub fn cmd_next(
+    ctx: &CommandContext<'_>,
+    filter: Option<&str>,
+    tag: Option<&str>,
+    wrap: bool,
+    count: usize,
+    each_delay: Option<Duration>,
+) -> Result<()> {
+    let _lock = lock::acquire(ctx.config)?;
+    let mut entries = sorted_theme_entries_for_config(ctx.config)?;
     if entries.is_empty() {
         return Err(anyhow!("no themes available"));
     }
+    if let Some(filter) = filter {
+        let needle = filter.to_lowercase();
+        entries.retain(|name| name.to_lowercase().contains(&needle));
+        if entries.is_empty() {
+            return Err(anyhow!("no themes match filter: {filter}"));
+        }
+    }
+    if let Some(tag) = tag {
+        entries.retain(|name| {
+            resolve_theme_path(ctx.config, name)
+                .map(|path| theme_has_tag(&path, tag))
+                .unwrap_or(false)
+        });
+        if entries.is_empty() {
+            return Err(anyhow!("no themes tagged: {tag}"));
+        }
+    }
 
     let current_name = current_theme_name(&ctx.config.current_theme_link)?;
 
-    let next = next_theme(&entries, current_name.as_deref());
-    cmd_set(ctx, &next)
+    let Some(delay) = each_delay else {
+        let Some(next) = next_theme(&entries, current_name.as_deref(), wrap, count) else {
+            println!("theme-manager: already at last theme");
+            return Ok(());
+        };
+        return set_theme(ctx, &next);
+    };
+
+    let mut current = current_name;
+    for step in 0..count {
+        let Some(next) = next_theme(&entries, current.as_deref(), wrap, 1) else {
+            println!("theme-manager: already at last theme");
+            break;
+        };
+        if step > 0 {
+            std::thread::sleep(delay);
+        }
+        set_theme(ctx, &next)?;
+        current = Some(next);
+    }
+    Ok(())
+}
+
+pub fn reapply_current(ctx: &CommandContext<'_>) -> Result<()> {
+    if ctx.skip_apps {
+        return Ok(());
+    }
+    let current_theme = current_theme_dir(&ctx.config.current_theme_link)?;
+    reapply_theme_dir(ctx, &current_theme)
+}
+
+// Applies the components bundled in `theme_dir` and reloads apps, leaving the current theme link alone.
+pub fn reapply_theme_dir(ctx: &CommandContext<'_>, theme_dir: &Path) -> Result<()> {
+    let waybar_restart = waybar::prepare_waybar(ctx, theme_dir)?;
+    walker::prepare_walker(ctx, theme_dir)?;
+    hyprlock::prepare_hyprlock(ctx, theme_dir)?;
+    starship::apply_starship(ctx, theme_dir)?;
+    omarchy::reload_components(ctx.config, ctx.quiet, waybar_restart)?;
+    omarchy::apply_theme_setters(ctx.config, ctx.quiet)?;
+    Ok(())
 }
 
 pub fn cmd_current(config: &ResolvedConfig) -> Result<()> {
@@ -193,11 +477,164 @@ pub fn cmd_current(config: &ResolvedConfig) -> Result<()> {
             config.current_theme_link.to_string_lossy()
         )
     })?;
-    println!("{}", title_case_theme(&name));
+    println!("{}", theme_display_name(&config.current_theme_link, &name));
+    Ok(())
+}
+
+pub fn cmd_current_path(config: &ResolvedConfig) -> Result<()> {
+    let link = &config.current_theme_link;
+    if fs::symlink_metadata(link).is_err() {
+        return Err(anyhow!("current theme not set: {}", link.to_string_lossy()));
+    }
+    let path = resolve_link_target(link)?;
+    let path = path
+        .canonicalize()
+        .map_err(|_| ThemeError::BrokenSymlink(link.to_path_buf()))?;
+    println!("{}", path.to_string_lossy());
+    Ok(())
+}
+
+pub fn cmd_current_variant(config: &ResolvedConfig) -> Result<()> {
+    let variant =
+        current_theme_variant(&config.current_theme_link, &config.current_background_link)?;
+    println!("{}", variant.as_str());
+    Ok(())
+}
+
+pub fn cmd_palette(config: &ResolvedConfig, theme_name: &str) -> Result<()> {
+    let theme_path = resolve_theme_source(config, theme_name)?;
+    let palette = extract_palette(&theme_path);
+    if palette.is_empty() {
+        return Err(anyhow!(
+            "no color variables found in {}",
+            theme_path.join("hyprland.conf").to_string_lossy()
+        ));
+    }
+    let swatches = output::should_color(output::Stream::Stdout);
+    let width = palette
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    for (name, color) in &palette {
+        let hex = color.hex();
+        if swatches {
+            println!(
+                "\x1b[48;2;{};{};{}m    \x1b[0m  {name:<width$}  {hex}",
+                color.r, color.g, color.b
+            );
+        } else {
+            println!("{name:<width$}  {hex}");
+        }
+    }
     Ok(())
 }
 
-pub fn cmd_bg_next(config: &ResolvedConfig, debug_awww: bool) -> Result<()> {
+// `$name = <color>` variables from hyprland.conf, in file order.
+pub fn extract_palette(theme_dir: &Path) -> Vec<(String, Color)> {
+    let content = fs::read_to_string(theme_dir.join("hyprland.conf")).unwrap_or_default();
+    let mut palette = Vec::new();
+    for line in content.lines() {
+        let Some(rest) = line.trim().strip_prefix('$') else {
+            continue;
+        };
+        let Some((name, value)) = rest.split_once('=') else {
+            continue;
+        };
+        let name = name.trim();
+        if name.is_empty() {
+            continue;
+        }
+        // A leading `#` is a hex color; anywhere else it starts a comment.
+        let value = value.trim();
+        let value = match value.strip_prefix('#') {
+            Some(hex) => format!("#{}", hex.split_whitespace().next().unwrap_or("")),
+            None => value.split('#').next().unwrap_or("").trim().to_string(),
+        };
+        if let Some(color) = parse_color(&value) {
+            palette.push((name.to_string(), color));
+        }
+    }
+    palette
+}
+
+fn parse_color(value: &str) -> Option<Color> {
+    let lower = value.to_ascii_lowercase();
+    if let Some(inner) = strip_call(&lower, "rgba") {
+        return parse_color_args(inner, true);
+    }
+    if let Some(inner) = strip_call(&lower, "rgb") {
+        return parse_color_args(inner, false);
+    }
+    if let Some(hex) = lower.strip_prefix("0x") {
+        // Hyprland's legacy form is 0xAARRGGBB.
+        if hex.len() != 8 {
+            return None;
+        }
+        let argb = u32::from_str_radix(hex, 16).ok()?;
+        let [a, r, g, b] = argb.to_be_bytes();
+        return Some(Color { r, g, b, a });
+    }
+    lower.strip_prefix('#').and_then(parse_hex_rgba)
+}
+
+fn strip_call<'a>(value: &'a str, func: &str) -> Option<&'a str> {
+    value
+        .strip_prefix(func)?
+        .trim_start()
+        .strip_prefix('(')?
+        .strip_suffix(')')
+        .map(str::trim)
+}
+
+fn parse_color_args(inner: &str, with_alpha: bool) -> Option<Color> {
+    if !inner.contains(',') {
+        // Hyprland also accepts rgb(rrggbb) / rgba(rrggbbaa).
+        let expected = if with_alpha { 8 } else { 6 };
+        return if inner.len() == expected {
+            parse_hex_rgba(inner)
+        } else {
+            None
+        };
+    }
+    let parts: Vec<&str> = inner.split(',').map(str::trim).collect();
+    let expected = if with_alpha { 4 } else { 3 };
+    if parts.len() != expected {
+        return None;
+    }
+    let r = parts[0].parse::<u8>().ok()?;
+    let g = parts[1].parse::<u8>().ok()?;
+    let b = parts[2].parse::<u8>().ok()?;
+    let a = match parts.get(3) {
+        Some(alpha) => parse_alpha(alpha)?,
+        None => 0xff,
+    };
+    Some(Color { r, g, b, a })
+}
+
+fn parse_alpha(value: &str) -> Option<u8> {
+    let alpha = value.parse::<f32>().ok()?;
+    if !(0.0..=1.0).contains(&alpha) {
+        return None;
+    }
+    Some((alpha * 255.0).round() as u8)
+}
+
+fn parse_hex_rgba(hex: &str) -> Option<Color> {
+    if !matches!(hex.len(), 6 | 8) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let byte = |idx: usize| u8::from_str_radix(&hex[idx..idx + 2], 16).ok();
+    let a = if hex.len() == 8 { byte(6)? } else { 0xff };
+    Some(Color {
+        r: byte(0)?,
+        g: byte(2)?,
+        b: byte(4)?,
+        a,
+    })
+}
+
+pub fn cmd_bg_next(config: &ResolvedConfig, debug_awww: bool, print_awww_cmd: bool) -> Result<()> {
     let theme_path = current_theme_dir(&config.current_theme_link)?;
 
     let ctx = CommandContext {
@@ -213,13 +650,25 @@ pub fn cmd_bg_next(config: &ResolvedConfig, debug_awww: bool) -> Result<()> {
         hyprlock_name: None,
         starship_mode: StarshipMode::None,
         debug_awww,
+        print_awww_cmd,
+        force_bg: false,
+        background: BackgroundChoice::Next,
+        wait_for_waybar: false,
+        timings: false,
+        rng: seeded_rng(config.seed),
     };
 
     if config.awww_transition && omarchy::command_exists("awww") {
         omarchy::ensure_awww_daemon(config, false);
         omarchy::stop_swaybg();
-        cycle_background(&ctx, &theme_path)?;
-        let _ = omarchy::run_awww_transition(config, false, debug_awww);
+        select_background(&ctx, &theme_path)?;
+        let _ = omarchy::run_awww_transition(
+            config,
+            false,
+            debug_awww,
+            print_awww_cmd,
+            &mut *ctx.rng.borrow_mut(),
+        );
     } else {
         omarchy::run_required("omarchy-theme-bg-next", &[], false)?;
     }
@@ -282,6 +731,25 @@ fn sorted_theme_entries_for_config(config: &ResolvedConfig) -> Result<Vec<String
     Ok(entries)
 }
 
+// The one place that decides where a theme's files live: finds the entry across all
+// theme roots and follows it if it is a symlink. `set`, presets, previews, and the
+// TUI all go through here so they never disagree.
+pub fn resolve_theme_source(config: &ResolvedConfig, theme_name: &str) -> Result<PathBuf> {
+    let normalized = normalize_theme_name(theme_name);
+    let not_found = || ThemeError::ThemeNotFound {
+        name: normalized.clone(),
+        requested: (normalized != theme_name).then(|| theme_name.to_string()),
+    };
+    let theme_path = resolve_theme_path(config, &normalized).map_err(|_| not_found())?;
+    if is_broken_symlink(&theme_path)? {
+        return Err(ThemeError::BrokenSymlink(theme_path).into());
+    }
+    if !theme_path.is_dir() {
+        return Err(not_found().into());
+    }
+    resolve_link_target(&theme_path)
+}
+
 pub fn resolve_theme_path(config: &ResolvedConfig, normalized: &str) -> Result<PathBuf> {
     for root in theme_roots(config) {
         let candidate = root.join(normalized);
@@ -289,12 +757,16 @@ pub fn resolve_theme_path(config: &ResolvedConfig, normalized: &str) -> Result<P
             return Ok(candidate);
         }
     }
-    Err(anyhow!("theme not found: {normalized}"))
+    Err(ThemeError::theme_not_found(normalized).into())
 }
 
 fn theme_roots(config: &ResolvedConfig) -> Vec<PathBuf> {
-    let mut roots = Vec::new();
-    roots.push(config.theme_root_dir.clone());
+    let mut roots = vec![config.theme_root_dir.clone()];
+    for dir in &config.theme_root_dirs {
+        if !roots.contains(dir) {
+            roots.push(dir.clone());
+        }
+    }
 
     let mut omarchy_path = env::var("OMARCHY_PATH").ok().map(PathBuf::from);
     if omarchy_path.is_none() {
@@ -311,7 +783,7 @@ fn theme_roots(config: &ResolvedConfig) -> Vec<PathBuf> {
     }
     if let Some(omarchy_path) = omarchy_path {
         let omarchy_themes = omarchy_path.join("themes");
-        if omarchy_themes != config.theme_root_dir {
+        if !roots.contains(&omarchy_themes) {
             roots.push(omarchy_themes);
         }
     }
@@ -319,14 +791,28 @@ fn theme_roots(config: &ResolvedConfig) -> Vec<PathBuf> {
     roots
 }
 
-fn next_theme(entries: &[String], current: Option<&str>) -> String {
-    if let Some(current) = current {
-        if let Some(idx) = entries.iter().position(|name| name == current) {
-            let next_idx = (idx + 1) % entries.len();
-            return entries[next_idx].clone();
-        }
+// `None` only when `wrap` is off and the current theme is the last entry.
+// Moves `steps` positions past `current` in one go; without wrapping it stops at the
+// last theme. An unknown current theme counts the first entry as the first step.
+fn next_theme(
+    entries: &[String],
+    current: Option<&str>,
+    wrap: bool,
+    steps: usize,
+) -> Option<String> {
+    let len = entries.len();
+    if len == 0 || steps == 0 {
+        return None;
     }
-    entries[0].clone()
+    let idx = current.and_then(|current| entries.iter().position(|name| name == current));
+    let next_idx = match idx {
+        Some(idx) if wrap => (idx + steps) % len,
+        Some(idx) if idx + 1 == len => return None,
+        Some(idx) => (idx + steps).min(len - 1),
+        None if wrap => (steps - 1) % len,
+        None => (steps - 1).min(len - 1),
+    };
+    Some(entries[next_idx].clone())
 }
 
 fn replace_theme_dir(staging_dir: &Path, current_dir: &Path) -> Result<()> {
@@ -341,6 +827,13 @@ fn replace_theme_dir(staging_dir: &Path, current_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+fn detach_current_theme_link(current_link: &Path) -> Result<()> {
+    if is_symlink(current_link)? {
+        fs::remove_file(current_link)?;
+    }
+    Ok(())
+}
+
 fn ensure_parent_dir(path: &Path) -> Result<()> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
@@ -356,31 +849,32 @@ fn is_symlink(path: &Path) -> Result<bool> {
     }
 }
 
-fn cycle_background(ctx: &CommandContext<'_>, theme_path: &Path) -> Result<()> {
-    let mut background_dirs = Vec::new();
-    let theme_backgrounds = theme_path.join("backgrounds");
-    if theme_backgrounds.is_dir() {
-        background_dirs.push(theme_backgrounds);
-    }
-    if let Some(theme_name) = current_theme_name(&ctx.config.current_theme_link)? {
-        if let Some(omarchy_dir) = ctx
-            .config
-            .current_theme_link
-            .parent()
-            .and_then(|p| p.parent())
-        {
-            let user_backgrounds = omarchy_dir.join("backgrounds").join(theme_name);
-            if user_backgrounds.is_dir() {
-                background_dirs.push(user_backgrounds);
-            }
+// The theme's own backgrounds/ first, then the user's ~/.config/omarchy/backgrounds/<theme>.
+fn background_dirs(
+    config: &ResolvedConfig,
+    theme_dir: &Path,
+    theme_name: Option<&str>,
+) -> Vec<PathBuf> {
+    let mut background_dirs = vec![theme_dir.join("backgrounds")];
+    if let Some(theme_name) = theme_name {
+        if let Some(omarchy_dir) = config.current_theme_link.parent().and_then(|p| p.parent()) {
+            background_dirs.push(omarchy_dir.join("backgrounds").join(theme_name));
         }
     }
-    if background_dirs.is_empty() {
-        return Ok(());
-    }
+    background_dirs
+}
 
-    let mut images: Vec<PathBuf> = Vec::new();
-    for dir in &background_dirs {
+// Wallpaper candidates in the order `bg-next` visits them: the theme's `backgrounds/`
+// plus the user's `backgrounds/<theme>`, keyed by file name so a user image that
+// shadows a theme image of the same name is skipped (the theme dir is read first).
+pub fn collect_backgrounds(
+    config: &ResolvedConfig,
+    theme_dir: &Path,
+    theme_name: Option<&str>,
+) -> Result<Vec<PathBuf>> {
+    let background_dirs = background_dirs(config, theme_dir, theme_name);
+    let mut images_by_name: BTreeMap<OsString, PathBuf> = BTreeMap::new();
+    for dir in background_dirs.iter().filter(|dir| dir.is_dir()) {
         for entry in fs::read_dir(dir)? {
             let path = entry?.path();
             if path.is_file()
@@ -395,34 +889,153 @@ fn cycle_background(ctx: &CommandContext<'_>, theme_path: &Path) -> Result<()> {
                     })
                     .unwrap_or(false)
             {
-                images.push(path);
+                if let Some(name) = path.file_name() {
+                    images_by_name.entry(name.to_os_string()).or_insert(path);
+                }
             }
         }
     }
-    images.sort();
-    images.dedup();
-    if images.is_empty() {
+    Ok(images_by_name.into_values().collect())
+}
+
+// A switch replaces current/theme, so a kept background that lives there is re-pointed
+// at the same image in the previous theme's source before the swap.
+fn keep_background_across_switch(
+    config: &ResolvedConfig,
+    previous_theme: Option<&str>,
+) -> Result<()> {
+    let Some(target) = current_background_target(config)? else {
         return Ok(());
+    };
+    let Ok(relative) = target.strip_prefix(&config.current_theme_link) else {
+        return Ok(());
+    };
+    let source = previous_theme
+        .and_then(|name| resolve_theme_source(config, name).ok())
+        .map(|dir| dir.join(relative))
+        .filter(|path| path.is_file());
+    match source {
+        Some(source) => link_background(config, &source),
+        None => Err(anyhow!(
+            "cannot keep background {}: not found in the previous theme; pick one with --background",
+            target.to_string_lossy()
+        )),
     }
+}
 
-    let current_link = &ctx.config.current_background_link;
-    let current_target = if current_link.exists() {
-        if current_link.is_symlink() {
-            Some(resolve_link_target(current_link)?)
-        } else {
-            Some(current_link.to_path_buf())
-        }
+fn current_background_target(config: &ResolvedConfig) -> Result<Option<PathBuf>> {
+    let current_link = &config.current_background_link;
+    if !current_link.exists() {
+        return Ok(None);
+    }
+    if current_link.is_symlink() {
+        Ok(Some(resolve_link_target(current_link)?))
     } else {
-        None
+        Ok(Some(current_link.to_path_buf()))
+    }
+}
+
+// Falls back to a file-name match so a link to a shadowed duplicate still has a position,
+// but only for targets inside `dirs`; another theme's image of the same name is no match.
+fn background_index(images: &[PathBuf], dirs: &[PathBuf], target: &Path) -> Option<usize> {
+    images.iter().position(|img| img == target).or_else(|| {
+        let parent = target.parent()?;
+        if !dirs.iter().any(|dir| dir == parent) {
+            return None;
+        }
+        images
+            .iter()
+            .position(|img| img.file_name() == target.file_name())
+    })
+}
+
+pub fn cmd_bg_list(config: &ResolvedConfig, theme: Option<&str>) -> Result<()> {
+    let (theme_dir, theme_name) = match theme {
+        Some(theme) => (
+            resolve_theme_source(config, theme)?,
+            Some(normalize_theme_name(theme)),
+        ),
+        None => (
+            current_theme_dir(&config.current_theme_link)?,
+            current_theme_name(&config.current_theme_link)?,
+        ),
     };
+    let images = collect_backgrounds(config, &theme_dir, theme_name.as_deref())?;
+    let dirs = background_dirs(config, &theme_dir, theme_name.as_deref());
+    if images.is_empty() {
+        return Err(anyhow!(
+            "no backgrounds found for {}",
+            theme_name.as_deref().unwrap_or("current theme")
+        ));
+    }
+    let current = current_background_target(config)?
+        .and_then(|target| background_index(&images, &dirs, &target));
+    for (idx, image) in images.iter().enumerate() {
+        let marker = if current == Some(idx) { " *" } else { "" };
+        println!("{}{marker}", image.to_string_lossy());
+    }
+    Ok(())
+}
 
-    let next_index = current_target
+// Points the background link at the image picked by `ctx.background`.
+// Returns whether the background link now points at a different image.
+fn select_background(ctx: &CommandContext<'_>, theme_path: &Path) -> Result<bool> {
+    if ctx.background == BackgroundChoice::Keep {
+        return Ok(false);
+    }
+    let theme_name = current_theme_name(&ctx.config.current_theme_link)?;
+    let images = collect_backgrounds(ctx.config, theme_path, theme_name.as_deref())?;
+    if images.is_empty() {
+        return Ok(false);
+    }
+
+    let dirs = background_dirs(ctx.config, theme_path, theme_name.as_deref());
+    let current_target = current_background_target(ctx.config)?;
+    let current_index = current_target
         .as_ref()
-        .and_then(|target| images.iter().position(|img| img == target))
-        .map(|idx| (idx + 1) % images.len())
-        .unwrap_or(0);
+        .and_then(|target| background_index(&images, &dirs, target));
+    let next_index = match &ctx.background {
+        BackgroundChoice::Random => {
+            random_background_index(images.len(), current_index, &mut *ctx.rng.borrow_mut())
+        }
+        BackgroundChoice::Named(name) => images
+            .iter()
+            .position(|image| background_matches(image, name))
+            .unwrap_or(0),
+        _ => current_index
+            .map(|idx| (idx + 1) % images.len())
+            .unwrap_or(0),
+    };
 
     let next_image = &images[next_index];
+    let changed = current_target.as_ref() != Some(next_image);
+    link_background(ctx.config, next_image)?;
+    Ok(changed)
+}
+
+// A pinned background matches by file name, with or without its extension.
+fn background_matches(image: &Path, name: &str) -> bool {
+    image.file_name().is_some_and(|file| file == name)
+        || image.file_stem().is_some_and(|stem| stem == name)
+}
+
+// Avoids re-picking the current image whenever there is another one to choose.
+fn random_background_index(len: usize, current: Option<usize>, rng: &mut impl Rng) -> usize {
+    match current {
+        Some(current) if len > 1 => {
+            let idx = rng.gen_range(0..len - 1);
+            if idx >= current {
+                idx + 1
+            } else {
+                idx
+            }
+        }
+        _ => rng.gen_range(0..len),
+    }
+}
+
+fn link_background(config: &ResolvedConfig, image: &Path) -> Result<()> {
+    let current_link = &config.current_background_link;
     if let Some(parent) = current_link.parent() {
         fs::create_dir_all(parent)?;
     }
@@ -435,11 +1048,37 @@ fn cycle_background(ctx: &CommandContext<'_>, theme_path: &Path) -> Result<()> {
     }
     #[cfg(unix)]
     {
-        std::os::unix::fs::symlink(next_image, current_link)?;
+        std::os::unix::fs::symlink(image, current_link)?;
     }
     Ok(())
 }
 
+// Themes may ship `hooks/theme-set` to generate derived configs; it gets the theme name.
+fn run_theme_hook(ctx: &CommandContext<'_>, theme_source: &Path, theme_name: &str) {
+    let hook_path = theme_source.join("hooks").join("theme-set");
+    if !hook_path.is_file() {
+        return;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let executable = fs::metadata(&hook_path)
+            .map(|meta| meta.permissions().mode() & 0o111 != 0)
+            .unwrap_or(false);
+        if !executable {
+            if !ctx.quiet {
+                eprintln!(
+                    "{} skipping theme hook that is not executable: {}",
+                    output::warning_prefix(),
+                    hook_path.to_string_lossy()
+                );
+            }
+            return;
+        }
+    }
+    let _ = omarchy::run_hook(&hook_path, &[theme_name], ctx.quiet);
+}
+
 fn write_theme_name(current_link: &Path, theme_name: &str) -> Result<()> {
     let Some(parent) = current_link.parent() else {
         return Ok(());
@@ -505,3 +1144,98 @@ fn copy_theme_dir(source: &Path, dest: &Path) -> Result<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_theme_advances_count_positions_at_once() {
+        let entries: Vec<String> = ["a", "b", "c", "d"].map(String::from).to_vec();
+        let next = |current, wrap, steps| next_theme(&entries, current, wrap, steps);
+        assert_eq!(next(Some("a"), true, 1).as_deref(), Some("b"));
+        assert_eq!(next(Some("b"), true, 3).as_deref(), Some("a"));
+        assert_eq!(next(Some("d"), true, 9).as_deref(), Some("a"));
+        assert_eq!(next(None, true, 6).as_deref(), Some("b"));
+        assert_eq!(next(Some("b"), false, 5).as_deref(), Some("d"));
+        assert_eq!(next(Some("d"), false, 2), None);
+        assert_eq!(next(None, false, 9).as_deref(), Some("d"));
+    }
+
+    #[test]
+    fn random_background_index_skips_current_image() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let picks: Vec<usize> = (0..32)
+            .map(|_| random_background_index(3, Some(1), &mut rng))
+            .collect();
+        assert!(picks.iter().all(|&idx| idx == 0 || idx == 2));
+        assert!(picks.contains(&0) && picks.contains(&2));
+
+        let mut again = StdRng::seed_from_u64(7);
+        let repeat: Vec<usize> = (0..32)
+            .map(|_| random_background_index(3, Some(1), &mut again))
+            .collect();
+        assert_eq!(picks, repeat);
+
+        assert_eq!(random_background_index(1, Some(0), &mut rng), 0);
+    }
+
+    #[test]
+    fn parse_color_handles_rgba_components() {
+        assert_eq!(
+            parse_color("rgba(192, 202, 245, 0.5)"),
+            Some(Color {
+                r: 192,
+                g: 202,
+                b: 245,
+                a: 128
+            })
+        );
+        assert_eq!(
+            parse_color("rgba(c0caf5ee)"),
+            Some(Color {
+                r: 0xc0,
+                g: 0xca,
+                b: 0xf5,
+                a: 0xee
+            })
+        );
+        assert_eq!(parse_color("rgba(192, 202, 245)"), None);
+    }
+
+    #[test]
+    fn parse_color_handles_rgb_components() {
+        assert_eq!(
+            parse_color("rgb(26,27,38)"),
+            Some(Color {
+                r: 26,
+                g: 27,
+                b: 38,
+                a: 0xff
+            })
+        );
+        assert_eq!(
+            parse_color("rgb(1a1b26)").map(|c| c.hex()),
+            Some("#1a1b26".into())
+        );
+        assert_eq!(parse_color("rgb(300, 0, 0)"), None);
+    }
+
+    #[test]
+    fn parse_color_handles_argb_hex() {
+        assert_eq!(
+            parse_color("0xff7aa2f7"),
+            Some(Color {
+                r: 0x7a,
+                g: 0xa2,
+                b: 0xf7,
+                a: 0xff
+            })
+        );
+        assert_eq!(
+            parse_color("0x807aa2f7").map(|c| c.hex()),
+            Some("#7aa2f780".into())
+        );
+        assert_eq!(parse_color("0x7aa2f7"), None);
+    }
+}
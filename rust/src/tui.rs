@@ -11,15 +11,18 @@ use ratatui::backend::CrosstermBackend;
 use ratatui::layout::{Constraint, Direction, Layout, Position, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span, Text};
-use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap};
 use ratatui::{Frame, Terminal};
 use ratatui_core::layout::Alignment as CoreAlignment;
 use ratatui_core::style::{Color as CoreColor, Modifier as CoreModifier, Style as CoreStyle};
 use ratatui_core::text::{Line as CoreLine, Span as CoreSpan, Text as CoreText};
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::{stdout, Stdout, Write};
+use std::panic::{self, PanicHookInfo};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::{Arc, OnceLock};
 use std::time::{Duration, Instant};
 use syntect::easy::HighlightLines;
 use syntect::highlighting::ThemeSet;
@@ -29,9 +32,9 @@ use tempfile::TempDir;
 
 use crate::config::ResolvedConfig;
 use crate::hyprlock;
-use crate::paths::{normalize_theme_name, title_case_theme};
+use crate::paths::{normalize_theme_name, theme_display_name, title_case_theme};
 use crate::presets;
-use crate::preview;
+use crate::preview::{self, PreviewBackendKind};
 use crate::starship;
 use crate::theme_ops;
 use crate::walker;
@@ -40,6 +43,47 @@ use crate::waybar;
 const APP_TITLE: &str = concat!("Theme Manager+ v", env!("THEME_MANAGER_VERSION"));
 const NO_THEME_CHANGE_VALUE: &str = "__no_theme_change__";
 const NO_THEME_CHANGE_LABEL: &str = "No theme change";
+const APPLY_KEY_PLACEHOLDER: &str = "{apply}";
+
+// (section, keys, action); sections render in order of first appearance.
+const HELP_BINDINGS: &[(&str, &str, &str)] = &[
+    ("Global", "? / F1", "Toggle this help"),
+    ("Global", "Tab / Shift+Tab", "Next / previous tab"),
+    ("Global", "Esc", "Quit without applying"),
+    ("Global", "q", "Quit (Review tab or focused preview)"),
+    (
+        "Global",
+        "Mouse click",
+        "Switch tab, select item, or focus preview",
+    ),
+    ("Lists", "Type", "Filter the list"),
+    ("Lists", "Backspace", "Delete last search character"),
+    ("Lists", "Ctrl+U", "Clear search"),
+    (
+        "Lists",
+        "Up / Down",
+        "Move selection (scrolls preview when focused)",
+    ),
+    ("Lists", "PgUp / PgDn", "Scroll preview"),
+    ("Lists", "Home / End", "Jump to first / last item"),
+    ("Lists", "Mouse wheel", "Move selection or scroll preview"),
+    ("Lists", "Enter", "Confirm selection and go to the next tab"),
+    (
+        "Presets",
+        "Enter",
+        "Load preset into all tabs and open Review",
+    ),
+    ("Review", APPLY_KEY_PLACEHOLDER, "Apply selection"),
+    (
+        "Review",
+        "Ctrl+R",
+        "Apply components to the current theme without switching",
+    ),
+    ("Review", "Ctrl+S", "Save selection as a preset"),
+    ("Help", "Type / Backspace / Ctrl+U", "Search bindings"),
+    ("Help", "Up / Down / PgUp / PgDn / Home", "Scroll help"),
+    ("Help", "? / F1 / Esc", "Close help"),
+];
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum FocusArea {
@@ -58,6 +102,12 @@ enum BrowseTab {
     Review,
 }
 
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+struct TuiState {
+    last_tab: Option<String>,
+    last_theme: Option<String>,
+}
+
 #[derive(Debug)]
 pub struct BrowseSelection {
     pub theme: String,
@@ -149,23 +199,10 @@ struct PreviewBackend {
     kind: PreviewBackendKind,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum PreviewBackendKind {
-    Kitty,
-    Sixel,
-    Chafa,
-    None,
-}
-
 impl PreviewBackend {
     fn detect() -> Self {
         PreviewBackend {
-            kind: detect_preview_backend_kind(
-                command_exists("kitty"),
-                command_exists("chafa"),
-                is_kitty_terminal(),
-                is_foot_terminal(),
-            ),
+            kind: preview::detect_backend_kind(),
         }
     }
 
@@ -248,31 +285,6 @@ impl PreviewBackend {
     }
 }
 
-fn detect_preview_backend_kind(
-    has_kitty: bool,
-    has_chafa: bool,
-    is_kitty_term: bool,
-    is_foot_term: bool,
-) -> PreviewBackendKind {
-    if has_kitty && is_kitty_term {
-        PreviewBackendKind::Kitty
-    } else if has_chafa && is_foot_term {
-        PreviewBackendKind::Sixel
-    } else if has_chafa {
-        PreviewBackendKind::Chafa
-    } else {
-        PreviewBackendKind::None
-    }
-}
-
-fn is_kitty_terminal() -> bool {
-    std::env::var("KITTY_WINDOW_ID").is_ok() || term_contains("kitty") || term_contains("ghostty")
-}
-
-fn is_foot_terminal() -> bool {
-    term_contains("foot") || term_program_contains("foot")
-}
-
 fn render_sixel_preview(path: &Path, rect: Rect) {
     let size = format!("{}x{}", rect.width.max(1), rect.height.max(1));
     if let Ok(output) = Command::new("chafa")
@@ -310,7 +322,11 @@ fn clear_preview_rect(rect: Rect) {
     let _ = out.flush();
 }
 
-pub fn browse(config: &ResolvedConfig, quiet: bool) -> Result<Option<BrowseSelection>> {
+pub fn browse(
+    config: &ResolvedConfig,
+    quiet: bool,
+    mouse_capture: bool,
+) -> Result<Option<BrowseSelection>> {
     if quiet {
         // currently unused, but reserved for future use
     }
@@ -331,9 +347,12 @@ pub fn browse(config: &ResolvedConfig, quiet: bool) -> Result<Option<BrowseSelec
                     preview: None,
                 });
             }
-            let label = title_case_theme(&name);
-            let theme_path = theme_ops::resolve_theme_path(config, &name)?;
-            let preview_path = preview::find_theme_preview(&theme_path);
+            let theme_path = theme_ops::resolve_theme_source(config, &name).ok();
+            let label = match &theme_path {
+                Some(theme_path) => theme_display_name(theme_path, &name),
+                None => title_case_theme(&name),
+            };
+            let preview_path = theme_path.as_deref().and_then(preview::find_theme_preview);
             Ok(OptionItem {
                 label,
                 value: name,
@@ -343,8 +362,20 @@ pub fn browse(config: &ResolvedConfig, quiet: bool) -> Result<Option<BrowseSelec
         .collect::<Result<Vec<_>>>()?;
 
     let backend = PreviewBackend::detect();
-    let mut terminal = setup_terminal()?;
-    let mut tab = BrowseTab::Theme;
+    warn_unknown_code_theme(config);
+    let mut restore_guard = RestoreGuard::new(|| restore_terminal(mouse_capture));
+    let _panic_hook = PanicHookGuard::install(move || restore_terminal(mouse_capture));
+    let mut terminal = setup_terminal(mouse_capture)?;
+    let tui_state_file = tui_state_path();
+    let saved_state = tui_state_file
+        .as_deref()
+        .map(load_tui_state)
+        .unwrap_or_default();
+    let mut tab = saved_state
+        .last_tab
+        .as_deref()
+        .and_then(tab_from_name)
+        .unwrap_or(BrowseTab::Theme);
     let tab_titles = [
         "Theme", "Waybar", "Walker", "Hyprlock", "Starship", "Review", "Presets",
     ];
@@ -363,14 +394,23 @@ pub fn browse(config: &ResolvedConfig, quiet: bool) -> Result<Option<BrowseSelec
     let mut status_at = Instant::now();
     let mut preset_save_active = false;
     let mut preset_save_input = String::new();
+    // Set after Enter on an existing preset name; a second Enter overwrites it.
+    let mut preset_overwrite_armed = false;
+    let mut show_help = false;
+    let mut help_query = String::new();
+    let mut help_scroll: u16 = 0;
 
     let mut theme_state = PickerState::new();
     rebuild_filtered(&mut theme_state, &theme_items);
-    if let Ok(Some(current)) = crate::paths::current_theme_name(&config.current_theme_link) {
-        select_option_by_value(&mut theme_state, &theme_items, &current);
-    } else {
-        select_option_by_value(&mut theme_state, &theme_items, NO_THEME_CHANGE_VALUE);
-    }
+    let current_theme = crate::paths::current_theme_name(&config.current_theme_link)
+        .ok()
+        .flatten();
+    select_initial_theme(
+        &mut theme_state,
+        &theme_items,
+        current_theme.as_deref(),
+        saved_state.last_theme.as_deref(),
+    );
     let mut selected_theme = current_theme_value(&theme_items, &theme_state)
         .ok_or_else(|| anyhow!("no themes available"))?;
     let mut theme_path = resolve_theme_path_for_selection(config, &selected_theme)?;
@@ -388,7 +428,7 @@ pub fn browse(config: &ResolvedConfig, quiet: bool) -> Result<Option<BrowseSelec
     rebuild_filtered(&mut hyprlock_state, &hyprlock_items);
     rebuild_filtered(&mut starship_state, &starship_items);
 
-    let mut preset_file = presets::load_presets()?;
+    let mut preset_file = presets::load_presets(config)?;
     let mut preset_items = build_preset_items(&preset_file);
     let mut preset_state = PickerState::new();
     rebuild_filtered(&mut preset_state, &preset_items);
@@ -412,181 +452,206 @@ pub fn browse(config: &ResolvedConfig, quiet: bool) -> Result<Option<BrowseSelec
             let status_active =
                 !status_message.is_empty() && status_at.elapsed() < Duration::from_millis(1200);
 
-            match tab {
-                BrowseTab::Theme => {
-                    let areas = render_picker(
-                        frame,
-                        content_area,
-                        "Select theme",
-                        "Image Preview",
-                        &theme_items,
-                        &mut theme_state,
-                        &backend,
-                        |idx| {
-                            if theme_items[idx].value == NO_THEME_CHANGE_VALUE {
-                                return Text::from("Keeping current theme.");
-                            }
-                            match theme_ops::resolve_theme_path(config, &theme_items[idx].value) {
-                                Ok(theme_path) => load_code_preview(
-                                    "hyprland.conf",
-                                    theme_path.join("hyprland.conf"),
-                                    "conf",
-                                ),
-                                Err(_) => Text::from("Theme preview unavailable."),
-                            }
-                        },
-                        |idx| theme_items[idx].preview.clone(),
-                        |_idx| None,
-                        true,
-                        if status_active && status_tab == BrowseTab::Theme {
-                            Some(status_message.as_str())
-                        } else {
-                            None
-                        },
-                    );
-                    active_search_area = areas.search_area;
-                    active_list_inner = areas.list_inner;
-                    active_code_inner = areas.code_inner;
-                    active_code_area = areas.code_area;
-                }
-                BrowseTab::Waybar => {
-                    let areas = render_picker(
-                        frame,
-                        content_area,
-                        "Select Waybar",
-                        "Image Preview",
-                        &waybar_items,
-                        &mut waybar_state,
-                        &backend,
-                        |idx| build_waybar_code_preview(config, &theme_path, &waybar_items[idx]),
-                        |idx| waybar_items[idx].preview.clone(),
-                        |_idx| None,
-                        true,
-                        if status_active && status_tab == BrowseTab::Waybar {
-                            Some(status_message.as_str())
-                        } else {
-                            None
-                        },
-                    );
-                    active_search_area = areas.search_area;
-                    active_list_inner = areas.list_inner;
-                    active_code_inner = areas.code_inner;
-                    active_code_area = areas.code_area;
-                }
-                BrowseTab::Walker => {
-                    let areas = render_picker(
-                        frame,
-                        content_area,
-                        "Select Walker",
-                        "Image Preview",
-                        &walker_items,
-                        &mut walker_state,
-                        &backend,
-                        |idx| build_walker_code_preview(config, &theme_path, &walker_items[idx]),
-                        |idx| walker_items[idx].preview.clone(),
-                        |_idx| None,
-                        true,
-                        if status_active && status_tab == BrowseTab::Walker {
-                            Some(status_message.as_str())
-                        } else {
-                            None
-                        },
-                    );
-                    active_search_area = areas.search_area;
-                    active_list_inner = areas.list_inner;
-                    active_code_inner = areas.code_inner;
-                    active_code_area = areas.code_area;
-                }
-                BrowseTab::Hyprlock => {
-                    let areas = render_picker(
-                        frame,
-                        content_area,
-                        "Select Hyprlock",
-                        "Image Preview",
-                        &hyprlock_items,
-                        &mut hyprlock_state,
-                        &backend,
-                        |idx| {
-                            build_hyprlock_code_preview(config, &theme_path, &hyprlock_items[idx])
-                        },
-                        |idx| hyprlock_items[idx].preview.clone(),
-                        |_idx| None,
-                        true,
-                        if status_active && status_tab == BrowseTab::Hyprlock {
-                            Some(status_message.as_str())
-                        } else {
-                            None
-                        },
-                    );
-                    active_search_area = areas.search_area;
-                    active_list_inner = areas.list_inner;
-                    active_code_inner = areas.code_inner;
-                    active_code_area = areas.code_area;
-                }
-                BrowseTab::Starship => {
-                    let areas = render_picker(
-                        frame,
-                        content_area,
-                        "Select Starship",
-                        "Prompt Preview",
-                        &starship_items,
-                        &mut starship_state,
-                        &backend,
-                        |idx| {
-                            build_starship_code_preview(config, &theme_path, &starship_items[idx])
-                        },
-                        |_idx| None,
-                        |idx| {
-                            Some(build_starship_prompt_preview(
-                                config,
-                                &theme_path,
-                                &starship_items[idx],
-                            ))
-                        },
-                        false,
-                        if status_active && status_tab == BrowseTab::Starship {
-                            Some(status_message.as_str())
-                        } else {
-                            None
-                        },
-                    );
-                    active_search_area = areas.search_area;
-                    active_list_inner = areas.list_inner;
-                    active_code_inner = areas.code_inner;
-                    active_code_area = areas.code_area;
-                }
-                BrowseTab::Presets => {
-                    let areas = render_preset_picker(
-                        frame,
-                        content_area,
-                        &preset_items,
-                        &mut preset_state,
-                        |idx| preset_summary_text(config, &preset_file, &preset_items[idx]),
-                        if status_active && status_tab == BrowseTab::Presets {
-                            Some(status_message.as_str())
-                        } else {
-                            None
-                        },
-                    );
-                    active_search_area = areas.search_area;
-                    active_list_inner = areas.list_inner;
-                    active_code_inner = areas.code_inner;
-                    active_code_area = areas.code_area;
-                }
-                BrowseTab::Review => {
-                    active_search_area = Rect::ZERO;
-                    active_list_inner = Rect::ZERO;
-                    active_code_inner = Rect::ZERO;
-                    active_code_area = Rect::ZERO;
-                    render_review(
-                        frame,
-                        content_area,
-                        &selected_theme,
-                        current_waybar_label(&waybar_items, &waybar_state),
-                        current_walker_label(&walker_items, &walker_state),
-                        current_hyprlock_label(&hyprlock_items, &hyprlock_state),
-                        current_starship_label(&starship_items, &starship_state),
-                    );
+            if show_help {
+                active_search_area = Rect::ZERO;
+                active_list_inner = Rect::ZERO;
+                active_code_inner = Rect::ZERO;
+                active_code_area = Rect::ZERO;
+                let apply_label = config.tui_apply_key.as_deref().unwrap_or("Ctrl+Enter");
+                let lines = help_lines(apply_label, &help_query);
+                render_help(frame, content_area, lines, &help_query, &mut help_scroll);
+            } else {
+                match tab {
+                    BrowseTab::Theme => {
+                        let areas = render_picker(
+                            frame,
+                            content_area,
+                            "Select theme",
+                            "Image Preview",
+                            &theme_items,
+                            &mut theme_state,
+                            &backend,
+                            |idx| {
+                                if theme_items[idx].value == NO_THEME_CHANGE_VALUE {
+                                    return Text::from("Keeping current theme.");
+                                }
+                                match theme_ops::resolve_theme_source(
+                                    config,
+                                    &theme_items[idx].value,
+                                ) {
+                                    Ok(theme_path) => {
+                                        let (title, path, syntax) =
+                                            preview::primary_theme_doc(&theme_path);
+                                        load_code_preview(&title, path, syntax, code_theme(config))
+                                    }
+                                    Err(_) => Text::from("Theme preview unavailable."),
+                                }
+                            },
+                            |idx| theme_items[idx].preview.clone(),
+                            |_idx| None,
+                            true,
+                            if status_active && status_tab == BrowseTab::Theme {
+                                Some(status_message.as_str())
+                            } else {
+                                None
+                            },
+                        );
+                        active_search_area = areas.search_area;
+                        active_list_inner = areas.list_inner;
+                        active_code_inner = areas.code_inner;
+                        active_code_area = areas.code_area;
+                    }
+                    BrowseTab::Waybar => {
+                        let areas = render_picker(
+                            frame,
+                            content_area,
+                            "Select Waybar",
+                            "Image Preview",
+                            &waybar_items,
+                            &mut waybar_state,
+                            &backend,
+                            |idx| {
+                                build_waybar_code_preview(config, &theme_path, &waybar_items[idx])
+                            },
+                            |idx| waybar_items[idx].preview.clone(),
+                            |_idx| None,
+                            true,
+                            if status_active && status_tab == BrowseTab::Waybar {
+                                Some(status_message.as_str())
+                            } else {
+                                None
+                            },
+                        );
+                        active_search_area = areas.search_area;
+                        active_list_inner = areas.list_inner;
+                        active_code_inner = areas.code_inner;
+                        active_code_area = areas.code_area;
+                    }
+                    BrowseTab::Walker => {
+                        let areas = render_picker(
+                            frame,
+                            content_area,
+                            "Select Walker",
+                            "Image Preview",
+                            &walker_items,
+                            &mut walker_state,
+                            &backend,
+                            |idx| {
+                                build_walker_code_preview(config, &theme_path, &walker_items[idx])
+                            },
+                            |idx| walker_items[idx].preview.clone(),
+                            |_idx| None,
+                            true,
+                            if status_active && status_tab == BrowseTab::Walker {
+                                Some(status_message.as_str())
+                            } else {
+                                None
+                            },
+                        );
+                        active_search_area = areas.search_area;
+                        active_list_inner = areas.list_inner;
+                        active_code_inner = areas.code_inner;
+                        active_code_area = areas.code_area;
+                    }
+                    BrowseTab::Hyprlock => {
+                        let areas = render_picker(
+                            frame,
+                            content_area,
+                            "Select Hyprlock",
+                            "Image Preview",
+                            &hyprlock_items,
+                            &mut hyprlock_state,
+                            &backend,
+                            |idx| {
+                                build_hyprlock_code_preview(
+                                    config,
+                                    &theme_path,
+                                    &hyprlock_items[idx],
+                                )
+                            },
+                            |idx| hyprlock_items[idx].preview.clone(),
+                            |_idx| None,
+                            true,
+                            if status_active && status_tab == BrowseTab::Hyprlock {
+                                Some(status_message.as_str())
+                            } else {
+                                None
+                            },
+                        );
+                        active_search_area = areas.search_area;
+                        active_list_inner = areas.list_inner;
+                        active_code_inner = areas.code_inner;
+                        active_code_area = areas.code_area;
+                    }
+                    BrowseTab::Starship => {
+                        let areas = render_picker(
+                            frame,
+                            content_area,
+                            "Select Starship",
+                            "Prompt Preview",
+                            &starship_items,
+                            &mut starship_state,
+                            &backend,
+                            |idx| {
+                                build_starship_code_preview(
+                                    config,
+                                    &theme_path,
+                                    &starship_items[idx],
+                                )
+                            },
+                            |_idx| None,
+                            |idx| {
+                                Some(build_starship_prompt_preview(
+                                    config,
+                                    &theme_path,
+                                    &starship_items[idx],
+                                ))
+                            },
+                            false,
+                            if status_active && status_tab == BrowseTab::Starship {
+                                Some(status_message.as_str())
+                            } else {
+                                None
+                            },
+                        );
+                        active_search_area = areas.search_area;
+                        active_list_inner = areas.list_inner;
+                        active_code_inner = areas.code_inner;
+                        active_code_area = areas.code_area;
+                    }
+                    BrowseTab::Presets => {
+                        let areas = render_preset_picker(
+                            frame,
+                            content_area,
+                            &preset_items,
+                            &mut preset_state,
+                            |idx| preset_summary_text(config, &preset_file, &preset_items[idx]),
+                            if status_active && status_tab == BrowseTab::Presets {
+                                Some(status_message.as_str())
+                            } else {
+                                None
+                            },
+                        );
+                        active_search_area = areas.search_area;
+                        active_list_inner = areas.list_inner;
+                        active_code_inner = areas.code_inner;
+                        active_code_area = areas.code_area;
+                    }
+                    BrowseTab::Review => {
+                        active_search_area = Rect::ZERO;
+                        active_list_inner = Rect::ZERO;
+                        active_code_inner = Rect::ZERO;
+                        active_code_area = Rect::ZERO;
+                        render_review(
+                            frame,
+                            content_area,
+                            &selected_theme,
+                            current_waybar_label(&waybar_items, &waybar_state),
+                            current_walker_label(&walker_items, &walker_state),
+                            current_hyprlock_label(&hyprlock_items, &hyprlock_state),
+                            current_starship_label(&starship_items, &starship_state),
+                        );
+                    }
                 }
             }
 
@@ -647,6 +712,7 @@ pub fn browse(config: &ResolvedConfig, quiet: bool) -> Result<Option<BrowseSelec
                                 KeyCode::Esc => {
                                     preset_save_active = false;
                                     preset_save_input.clear();
+                                    preset_overwrite_armed = false;
                                     status_tab = BrowseTab::Review;
                                     status_at = Instant::now();
                                     status_message = "Preset save canceled".to_string();
@@ -657,6 +723,17 @@ pub fn browse(config: &ResolvedConfig, quiet: bool) -> Result<Option<BrowseSelec
                                     status_at = Instant::now();
                                     if name.is_empty() {
                                         status_message = "Preset name required".to_string();
+                                    } else if !preset_overwrite_armed
+                                        && preset_file.preset.contains_key(name)
+                                    {
+                                        preset_overwrite_armed = true;
+                                        status_message = format!(
+                                            "Preset {name} exists; press Enter again to overwrite"
+                                        );
+                                        if !event::poll(Duration::from_millis(0))? {
+                                            break 'event_loop;
+                                        }
+                                        continue 'event_loop;
                                     } else {
                                         let entry = build_preset_entry_from_selection(
                                             &selected_theme,
@@ -672,10 +749,15 @@ pub fn browse(config: &ResolvedConfig, quiet: bool) -> Result<Option<BrowseSelec
                                                 &theme_path,
                                             ),
                                         );
-                                        match presets::save_preset(name, entry, config) {
+                                        match presets::save_preset(
+                                            name,
+                                            entry,
+                                            config,
+                                            preset_overwrite_armed,
+                                        ) {
                                             Ok(()) => {
                                                 status_message = "Preset saved".to_string();
-                                                preset_file = presets::load_presets()?;
+                                                preset_file = presets::load_presets(config)?;
                                                 preset_items = build_preset_items(&preset_file);
                                                 reset_picker_cache(&mut preset_state);
                                                 rebuild_filtered(&mut preset_state, &preset_items);
@@ -692,21 +774,69 @@ pub fn browse(config: &ResolvedConfig, quiet: bool) -> Result<Option<BrowseSelec
                                     }
                                     preset_save_active = false;
                                     preset_save_input.clear();
+                                    preset_overwrite_armed = false;
                                 }
                                 KeyCode::Backspace => {
                                     preset_save_input.pop();
+                                    preset_overwrite_armed = false;
                                 }
                                 KeyCode::Char('u')
                                     if key.modifiers.contains(KeyModifiers::CONTROL) =>
                                 {
                                     preset_save_input.clear();
+                                    preset_overwrite_armed = false;
                                 }
-                                KeyCode::Char(ch) => {
+                                KeyCode::Char(ch)
                                     if !key.modifiers.contains(KeyModifiers::CONTROL)
-                                        && !key.modifiers.contains(KeyModifiers::ALT)
-                                    {
-                                        preset_save_input.push(ch);
-                                    }
+                                        && !key.modifiers.contains(KeyModifiers::ALT) =>
+                                {
+                                    preset_save_input.push(ch);
+                                    preset_overwrite_armed = false;
+                                }
+                                _ => {}
+                            }
+                            if !event::poll(Duration::from_millis(0))? {
+                                break 'event_loop;
+                            }
+                            continue 'event_loop;
+                        }
+                        if show_help {
+                            match key.code {
+                                KeyCode::Esc | KeyCode::Char('?') | KeyCode::F(1) => {
+                                    show_help = false;
+                                    help_query.clear();
+                                    help_scroll = 0;
+                                    mark_force_clear(
+                                        &mut theme_state,
+                                        &mut waybar_state,
+                                        &mut walker_state,
+                                        &mut hyprlock_state,
+                                        &mut starship_state,
+                                        &mut preset_state,
+                                    );
+                                }
+                                KeyCode::Up => help_scroll = help_scroll.saturating_sub(1),
+                                KeyCode::Down => help_scroll = help_scroll.saturating_add(1),
+                                KeyCode::PageUp => help_scroll = help_scroll.saturating_sub(10),
+                                KeyCode::PageDown => help_scroll = help_scroll.saturating_add(10),
+                                KeyCode::Home => help_scroll = 0,
+                                KeyCode::End => help_scroll = u16::MAX,
+                                KeyCode::Backspace => {
+                                    help_query.pop();
+                                    help_scroll = 0;
+                                }
+                                KeyCode::Char('u')
+                                    if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                                {
+                                    help_query.clear();
+                                    help_scroll = 0;
+                                }
+                                KeyCode::Char(ch)
+                                    if !key.modifiers.contains(KeyModifiers::CONTROL)
+                                        && !key.modifiers.contains(KeyModifiers::ALT) =>
+                                {
+                                    help_query.push(ch);
+                                    help_scroll = 0;
                                 }
                                 _ => {}
                             }
@@ -715,28 +845,37 @@ pub fn browse(config: &ResolvedConfig, quiet: bool) -> Result<Option<BrowseSelec
                             }
                             continue 'event_loop;
                         }
+                        if key.code == KeyCode::Char('?') || key.code == KeyCode::F(1) {
+                            show_help = true;
+                            clear_kitty_preview(&backend);
+                            if !event::poll(Duration::from_millis(0))? {
+                                break 'event_loop;
+                            }
+                            continue 'event_loop;
+                        }
                         let is_repeat = key.kind == event::KeyEventKind::Repeat;
                         if is_repeat {
                             if let Some((last_code, last_mod, last_at)) = last_press_key {
-                                if last_code == key.code && last_mod == key.modifiers {
-                                    if now.duration_since(last_at) < Duration::from_millis(150) {
-                                        if !event::poll(Duration::from_millis(0))? {
-                                            break 'event_loop;
-                                        }
-                                        continue 'event_loop;
+                                if last_code == key.code
+                                    && last_mod == key.modifiers
+                                    && now.duration_since(last_at) < Duration::from_millis(150)
+                                {
+                                    if !event::poll(Duration::from_millis(0))? {
+                                        break 'event_loop;
                                     }
+                                    continue 'event_loop;
                                 }
                             }
                             if let Some((last_code, last_mod)) = last_repeat_key {
-                                if last_code == key.code && last_mod == key.modifiers {
-                                    if now.duration_since(last_repeat_at)
+                                if last_code == key.code
+                                    && last_mod == key.modifiers
+                                    && now.duration_since(last_repeat_at)
                                         < Duration::from_millis(35)
-                                    {
-                                        if !event::poll(Duration::from_millis(0))? {
-                                            break 'event_loop;
-                                        }
-                                        continue 'event_loop;
+                                {
+                                    if !event::poll(Duration::from_millis(0))? {
+                                        break 'event_loop;
                                     }
+                                    continue 'event_loop;
                                 }
                             }
                             last_repeat_key = Some((key.code, key.modifiers));
@@ -766,13 +905,12 @@ pub fn browse(config: &ResolvedConfig, quiet: bool) -> Result<Option<BrowseSelec
                                         state.search_query.clear();
                                         handled = true;
                                     }
-                                    KeyCode::Char(ch) => {
+                                    KeyCode::Char(ch)
                                         if !key.modifiers.contains(KeyModifiers::CONTROL)
-                                            && !key.modifiers.contains(KeyModifiers::ALT)
-                                        {
-                                            state.search_query.push(ch);
-                                            handled = true;
-                                        }
+                                            && !key.modifiers.contains(KeyModifiers::ALT) =>
+                                    {
+                                        state.search_query.push(ch);
+                                        handled = true;
                                     }
                                     _ => {}
                                 }
@@ -800,7 +938,9 @@ pub fn browse(config: &ResolvedConfig, quiet: bool) -> Result<Option<BrowseSelec
                             }
                         }
                         if key.code == KeyCode::Char('q') || key.code == KeyCode::Esc {
-                            cleanup_terminal(&mut terminal)?;
+                            save_browse_state(tui_state_file.as_deref(), tab, &selected_theme);
+                            restore_guard.disarm();
+                            cleanup_terminal(&mut terminal, mouse_capture)?;
                             return Ok(None);
                         }
                         if key.code == KeyCode::Tab {
@@ -846,16 +986,19 @@ pub fn browse(config: &ResolvedConfig, quiet: bool) -> Result<Option<BrowseSelec
                             }
                             continue 'event_loop;
                         }
-                        if tab == BrowseTab::Review && apply_key_matches(config, key) {
-                            let selection_theme = if selected_theme == NO_THEME_CHANGE_VALUE {
-                                crate::paths::current_theme_name(&config.current_theme_link)?
-                                    .ok_or_else(|| anyhow!("current theme not set"))?
-                            } else {
-                                selected_theme.clone()
-                            };
+                        let keep_current = key.modifiers.contains(KeyModifiers::CONTROL)
+                            && key.code == KeyCode::Char('r');
+                        if tab == BrowseTab::Review
+                            && (keep_current || apply_key_matches(config, key))
+                        {
+                            let (selection_theme, no_theme_change) = selection_target(
+                                &selected_theme,
+                                keep_current,
+                                crate::paths::current_theme_name(&config.current_theme_link)?,
+                            )?;
                             let selection = BrowseSelection {
                                 theme: selection_theme,
-                                no_theme_change: selected_theme == NO_THEME_CHANGE_VALUE,
+                                no_theme_change,
                                 waybar: current_waybar_selection(&waybar_items, &waybar_state),
                                 walker: current_walker_selection(&walker_items, &walker_state),
                                 hyprlock: current_hyprlock_selection(
@@ -868,7 +1011,9 @@ pub fn browse(config: &ResolvedConfig, quiet: bool) -> Result<Option<BrowseSelec
                                     &theme_path,
                                 ),
                             };
-                            cleanup_terminal(&mut terminal)?;
+                            save_browse_state(tui_state_file.as_deref(), tab, &selected_theme);
+                            restore_guard.disarm();
+                            cleanup_terminal(&mut terminal, mouse_capture)?;
                             return Ok(Some(selection));
                         }
                         if key.code == KeyCode::Enter && tab == BrowseTab::Presets {
@@ -1038,6 +1183,12 @@ pub fn browse(config: &ResolvedConfig, quiet: bool) -> Result<Option<BrowseSelec
                             }
                         }
                     }
+                    Event::Mouse(_) if !mouse_capture => {}
+                    Event::Mouse(mouse) if show_help => match mouse.kind {
+                        MouseEventKind::ScrollUp => help_scroll = help_scroll.saturating_sub(1),
+                        MouseEventKind::ScrollDown => help_scroll = help_scroll.saturating_add(1),
+                        _ => {}
+                    },
                     Event::Mouse(mouse) => match mouse.kind {
                         MouseEventKind::Down(MouseButton::Left) => {
                             if tab_area.contains(Position {
@@ -1226,11 +1377,109 @@ pub fn browse(config: &ResolvedConfig, quiet: bool) -> Result<Option<BrowseSelec
     }
 }
 
+// Single-list theme picker for commands outside `browse` (e.g. `remove` without a name).
+// Returns the chosen theme directory name, or `None` when cancelled.
+pub fn pick_theme(
+    config: &ResolvedConfig,
+    title: &str,
+    theme_root: &Path,
+    names: &[String],
+) -> Result<Option<String>> {
+    if names.is_empty() {
+        return Err(anyhow!("no themes available"));
+    }
+    let items: Vec<OptionItem> = names
+        .iter()
+        .map(|name| OptionItem {
+            label: theme_display_name(&theme_root.join(name), name),
+            value: name.clone(),
+            preview: preview::find_theme_preview(&theme_root.join(name)),
+        })
+        .collect();
+
+    let backend = PreviewBackend::detect();
+    warn_unknown_code_theme(config);
+    let mut restore_guard = RestoreGuard::new(|| restore_terminal(true));
+    let _panic_hook = PanicHookGuard::install(|| restore_terminal(true));
+    let mut terminal = setup_terminal(true)?;
+    let mut state = PickerState::new();
+    rebuild_filtered(&mut state, &items);
+
+    let selection = loop {
+        terminal.draw(|frame| {
+            render_picker(
+                frame,
+                frame.area(),
+                title,
+                "Image Preview",
+                &items,
+                &mut state,
+                &backend,
+                |idx| {
+                    let (title, path, syntax) =
+                        preview::primary_theme_doc(&theme_root.join(&items[idx].value));
+                    load_code_preview(&title, path, syntax, code_theme(config))
+                },
+                |idx| items[idx].preview.clone(),
+                |_idx| None,
+                true,
+                None,
+            );
+        })?;
+
+        if !event::poll(Duration::from_millis(200))? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind == KeyEventKind::Release {
+            continue;
+        }
+        let items_len = state.filtered_indices.len();
+        match key.code {
+            KeyCode::Esc => break None,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break None,
+            KeyCode::Enter => {
+                break selected_item_index(&state, items.len()).map(|idx| items[idx].value.clone());
+            }
+            KeyCode::Up => {
+                let new_index = previous_index(state.list_state.selected(), items_len);
+                state.list_state.select(Some(new_index));
+            }
+            KeyCode::Down => {
+                let new_index = next_index(state.list_state.selected(), items_len);
+                state.list_state.select(Some(new_index));
+            }
+            KeyCode::Home => state.list_state.select(Some(0)),
+            KeyCode::End => state.list_state.select(Some(items_len.saturating_sub(1))),
+            KeyCode::Backspace => {
+                state.search_query.pop();
+                rebuild_filtered(&mut state, &items);
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                state.search_query.clear();
+                rebuild_filtered(&mut state, &items);
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                state.search_query.push(c);
+                rebuild_filtered(&mut state, &items);
+            }
+            _ => {}
+        }
+    };
+
+    clear_kitty_preview(&backend);
+    restore_guard.disarm();
+    cleanup_terminal(&mut terminal, true)?;
+    Ok(selection)
+}
+
 fn resolve_theme_path_for_selection(config: &ResolvedConfig, value: &str) -> Result<PathBuf> {
     if value == NO_THEME_CHANGE_VALUE {
         return crate::paths::current_theme_dir(&config.current_theme_link);
     }
-    theme_ops::resolve_theme_path(config, value)
+    theme_ops::resolve_theme_source(config, value)
 }
 
 fn theme_label_for_display(value: &str) -> String {
@@ -1298,7 +1547,7 @@ fn build_waybar_items(config: &ResolvedConfig, theme_path: &Path) -> Result<Vec<
         ));
     }
 
-    let mut names = list_waybar_themes(&config.waybar_themes_dir)?;
+    let mut names = waybar::list_waybar_themes(&config.waybar_themes_dir)?;
     pin_omarchy_default_first(&mut names);
     for name in names {
         let preview_path = preview::find_waybar_preview(&config.waybar_themes_dir.join(&name));
@@ -1324,7 +1573,7 @@ fn build_starship_items(config: &ResolvedConfig, theme_path: &Path) -> Result<Ve
         None,
     ));
 
-    if theme_path.join("starship.toml").is_file() {
+    if starship::find_theme_starship_config(theme_path).is_some() {
         items.push(OptionItem::with_kind(
             "Use theme starship".to_string(),
             "theme".to_string(),
@@ -1333,7 +1582,7 @@ fn build_starship_items(config: &ResolvedConfig, theme_path: &Path) -> Result<Ve
         ));
     }
 
-    for preset in list_starship_presets() {
+    for preset in starship::list_starship_presets() {
         items.push(OptionItem::with_kind(
             format!("Preset: {preset}"),
             preset,
@@ -1342,7 +1591,7 @@ fn build_starship_items(config: &ResolvedConfig, theme_path: &Path) -> Result<Ve
         ));
     }
 
-    let mut themes = list_starship_themes(&config.starship_themes_dir)?;
+    let mut themes = starship::list_starship_themes(&config.starship_themes_dir)?;
     pin_omarchy_default_first(&mut themes);
     for theme in themes {
         items.push(OptionItem::with_kind(
@@ -1390,7 +1639,7 @@ fn build_walker_items(config: &ResolvedConfig, theme_path: &Path) -> Result<Vec<
         ));
     }
 
-    let mut names = list_walker_themes(&config.walker_themes_dir)?;
+    let mut names = walker::list_walker_themes(&config.walker_themes_dir)?;
     pin_omarchy_default_first(&mut names);
     for name in names {
         let preview_path =
@@ -1428,7 +1677,7 @@ fn build_hyprlock_items(config: &ResolvedConfig, theme_path: &Path) -> Result<Ve
         ));
     }
 
-    let mut names = list_hyprlock_themes(&config.hyprlock_themes_dir)?;
+    let mut names = hyprlock::list_hyprlock_themes(&config.hyprlock_themes_dir)?;
     if hyprlock::omarchy_default_theme_available(config)
         && !names.iter().any(|name| name == "omarchy-default")
     {
@@ -1463,7 +1712,7 @@ fn build_walker_code_preview(
             if layout.is_file() {
                 parts.insert(0, ("layout.xml", layout, "xml"));
             }
-            load_multi_code_preview(&parts)
+            load_multi_code_preview(&parts, code_theme(config))
         }
         _ => {
             let base = config.walker_themes_dir.join(&item.value);
@@ -1472,7 +1721,7 @@ fn build_walker_code_preview(
             if layout.is_file() {
                 parts.insert(0, ("layout.xml", layout, "xml"));
             }
-            load_multi_code_preview(&parts)
+            load_multi_code_preview(&parts, code_theme(config))
         }
     }
 }
@@ -1488,6 +1737,7 @@ fn build_hyprlock_code_preview(
             "hyprlock.conf",
             theme_path.join("hyprlock-theme/hyprlock.conf"),
             "conf",
+            code_theme(config),
         ),
         _ => load_code_preview(
             "hyprlock.conf",
@@ -1496,6 +1746,7 @@ fn build_hyprlock_code_preview(
                 .join(&item.value)
                 .join("hyprlock.conf"),
             "conf",
+            code_theme(config),
         ),
     }
 }
@@ -1513,7 +1764,7 @@ fn build_waybar_code_preview(
                 ("config.jsonc", base.join("config.jsonc"), "json"),
                 ("style.css", base.join("style.css"), "css"),
             ];
-            load_multi_code_preview(&parts)
+            load_multi_code_preview(&parts, code_theme(config))
         }
         _ => {
             let base = config.waybar_themes_dir.join(&item.value);
@@ -1521,7 +1772,7 @@ fn build_waybar_code_preview(
                 ("config.jsonc", base.join("config.jsonc"), "json"),
                 ("style.css", base.join("style.css"), "css"),
             ];
-            load_multi_code_preview(&parts)
+            load_multi_code_preview(&parts, code_theme(config))
         }
     }
 }
@@ -1533,7 +1784,16 @@ fn build_starship_code_preview(
 ) -> Text<'static> {
     match item.kind.as_str() {
         "none" => Text::from("No Starship change."),
-        "theme" => load_code_preview("starship.toml", theme_path.join("starship.toml"), "yaml"),
+        "theme" => match starship::find_theme_starship_config(theme_path) {
+            Some(path) => {
+                let label = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                load_code_preview(&label, path, "yaml", code_theme(config))
+            }
+            None => Text::from("Theme-specific Starship config not found."),
+        },
         "preset" => {
             let preset = item.value.as_str();
             let output = Command::new("starship").args(["preset", preset]).output();
@@ -1541,7 +1801,12 @@ fn build_starship_code_preview(
                 Ok(output) if output.status.success() => output.stdout,
                 _ => return Text::from(format!("Failed to load preset: {preset}")),
             };
-            load_code_preview_from_string("preset.toml", &String::from_utf8_lossy(&output), "toml")
+            load_code_preview_from_string(
+                "preset.toml",
+                &String::from_utf8_lossy(&output),
+                "toml",
+                code_theme(config),
+            )
         }
         _ => load_code_preview(
             &format!("{}.toml", item.value),
@@ -1549,6 +1814,7 @@ fn build_starship_code_preview(
                 .starship_themes_dir
                 .join(format!("{}.toml", item.value)),
             "toml",
+            code_theme(config),
         ),
     }
 }
@@ -1561,7 +1827,10 @@ fn build_starship_prompt_preview(
     render_starship_prompt_preview(config, theme_path, item)
 }
 
-fn load_multi_code_preview(parts: &[(&str, PathBuf, &str)]) -> Text<'static> {
+fn load_multi_code_preview(
+    parts: &[(&str, PathBuf, &str)],
+    code_theme: Option<&str>,
+) -> Text<'static> {
     let mut combined = Text::from("");
     let mut first = true;
     for (title, path, syntax) in parts {
@@ -1574,37 +1843,92 @@ fn load_multi_code_preview(parts: &[(&str, PathBuf, &str)]) -> Text<'static> {
             Line::from(""),
         ]);
         combined.lines.append(&mut header.lines);
-        let block = load_code_preview(title, path.clone(), syntax);
+        let block = load_code_preview(title, path.clone(), syntax, code_theme);
         combined.lines.extend(block.lines);
     }
     combined
 }
 
-fn load_code_preview(title: &str, path: PathBuf, syntax: &str) -> Text<'static> {
+fn load_code_preview(
+    title: &str,
+    path: PathBuf,
+    syntax: &str,
+    code_theme: Option<&str>,
+) -> Text<'static> {
     if !path.is_file() {
         return Text::from(format!("Missing {} at {}", title, path.to_string_lossy()));
     }
     match fs::read_to_string(&path) {
-        Ok(content) => load_code_preview_from_string(title, &content, syntax),
+        Ok(content) => load_code_preview_from_string(title, &content, syntax, code_theme),
         Err(_) => Text::from(format!("Failed to read {}", title)),
     }
 }
 
-fn load_code_preview_from_string(title: &str, content: &str, syntax: &str) -> Text<'static> {
+fn load_code_preview_from_string(
+    title: &str,
+    content: &str,
+    syntax: &str,
+    code_theme: Option<&str>,
+) -> Text<'static> {
     let mut lines = Vec::new();
     lines.push(Line::from(format!("=== {} ===", title)));
     lines.push(Line::from(""));
-    let highlighted = highlight_code(content, syntax);
-    lines.extend(highlighted.lines);
+    let body = match code_theme {
+        Some(code_theme) => highlight_code(content, syntax, code_theme),
+        None => Text::from(content.to_string()),
+    };
+    lines.extend(body.lines);
     Text::from(lines)
 }
 
-fn highlight_code(content: &str, syntax: &str) -> Text<'static> {
-    let ps = SyntaxSet::load_defaults_newlines();
-    let ts = ThemeSet::load_defaults();
+const DEFAULT_CODE_THEME: &str = "base16-ocean.dark";
+
+// Loading syntect's bundled sets takes long enough to stall a selection change, so
+// they are built on first use and shared by every later preview.
+fn syntax_set() -> &'static SyntaxSet {
+    static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAX_SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn theme_set() -> &'static ThemeSet {
+    static THEME_SET: OnceLock<ThemeSet> = OnceLock::new();
+    THEME_SET.get_or_init(ThemeSet::load_defaults)
+}
+
+// `tui.code_theme`, or the built-in default when it is unset. `None` when
+// `tui.color_preview` is off: previews are then shown as plain text.
+fn code_theme(config: &ResolvedConfig) -> Option<&str> {
+    if !config.tui_color_preview {
+        return None;
+    }
+    Some(
+        config
+            .tui_code_theme
+            .as_deref()
+            .unwrap_or(DEFAULT_CODE_THEME),
+    )
+}
+
+// Called before the terminal switches to the TUI so the warning stays readable.
+fn warn_unknown_code_theme(config: &ResolvedConfig) {
+    let Some(name) = code_theme(config) else {
+        return;
+    };
+    if !theme_set().themes.contains_key(name) {
+        eprintln!(
+            "{} unknown tui.code_theme {name:?}; using {DEFAULT_CODE_THEME}",
+            crate::output::warning_prefix()
+        );
+    }
+}
+
+fn highlight_code(content: &str, syntax: &str, code_theme: &str) -> Text<'static> {
+    let ps = syntax_set();
+    let ts = theme_set();
     let theme = ts
         .themes
-        .get("base16-ocean.dark")
+        .get(code_theme)
+        .or_else(|| ts.themes.get(DEFAULT_CODE_THEME))
         .or_else(|| ts.themes.values().next())
         .expect("theme");
     let syntax_ref = ps
@@ -1613,7 +1937,7 @@ fn highlight_code(content: &str, syntax: &str) -> Text<'static> {
     let mut h = HighlightLines::new(syntax_ref, theme);
     let mut out = String::new();
     for line in content.lines() {
-        let ranges = h.highlight_line(line, &ps).unwrap_or_default();
+        let ranges = h.highlight_line(line, ps).unwrap_or_default();
         out.push_str(&as_24_bit_terminal_escaped(&ranges[..], false));
         out.push('\n');
     }
@@ -1654,13 +1978,10 @@ fn render_starship_prompt_preview(
         .status();
 
     let config_path = match item.kind.as_str() {
-        "theme" => {
-            let path = theme_path.join("starship.toml");
-            if !path.is_file() {
-                return Text::from("Theme-specific Starship config not found.");
-            }
-            path
-        }
+        "theme" => match starship::find_theme_starship_config(theme_path) {
+            Some(path) => path,
+            None => return Text::from("Theme-specific Starship config not found."),
+        },
         "preset" => {
             let preset_name = item.value.as_str();
             let output = Command::new("starship")
@@ -1857,6 +2178,7 @@ fn preview_debug_enabled() -> bool {
     std::env::var("THEME_MANAGER_DEBUG_PREVIEW").is_ok()
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_picker<T: ItemView>(
     frame: &mut Frame,
     area: Rect,
@@ -1912,7 +2234,7 @@ fn render_picker<T: ItemView>(
     let list_items: Vec<ListItem> = state
         .filtered_indices
         .iter()
-        .map(|&idx| ListItem::new(Line::from(items[idx].label())))
+        .map(|&idx| ListItem::new(highlighted_label(items[idx].label(), &state.search_query)))
         .collect();
     let list_title = build_list_title(title, status);
     let list_block = Block::default()
@@ -1939,7 +2261,7 @@ fn render_picker<T: ItemView>(
 
     let selected = selected_index(&state.list_state, state.filtered_indices.len());
     let selected_item = state.filtered_indices.get(selected).copied();
-    let preview_path = selected_item.and_then(|idx| image_preview(idx));
+    let preview_path = selected_item.and_then(image_preview);
     let previous_preview_index = state.last_preview_index;
     let previous_preview_path = state.last_preview.clone();
 
@@ -2098,7 +2420,7 @@ fn render_preset_picker(
     let list_items: Vec<ListItem> = state
         .filtered_indices
         .iter()
-        .map(|&idx| ListItem::new(Line::from(items[idx].label())))
+        .map(|&idx| ListItem::new(highlighted_label(items[idx].label(), &state.search_query)))
         .collect();
     let list_title = build_list_title("Select preset", status);
     let list_block = Block::default()
@@ -2167,6 +2489,20 @@ fn render_preset_picker(
     }
 }
 
+// The theme to hand back and whether it is the current one being reapplied: either
+// "No theme change" was picked or Ctrl+R asked to keep the current theme.
+fn selection_target(
+    selected_theme: &str,
+    keep_current: bool,
+    current: Option<String>,
+) -> Result<(String, bool)> {
+    if keep_current || selected_theme == NO_THEME_CHANGE_VALUE {
+        let current = current.ok_or_else(|| anyhow!("current theme not set"))?;
+        return Ok((current, true));
+    }
+    Ok((selected_theme.to_string(), false))
+}
+
 fn render_review(
     frame: &mut Frame,
     area: Rect,
@@ -2186,6 +2522,7 @@ fn render_review(
         Line::from(format!("Starship: {}", starship_label)),
         Line::from(""),
         Line::from("Apply: Ctrl+Enter"),
+        Line::from("Apply components only (keep current theme): Ctrl+R"),
         Line::from("Cancel: Esc"),
         Line::from("Switch tabs: Tab / Shift+Tab (or click tab bar)"),
     ];
@@ -2195,6 +2532,7 @@ fn render_review(
     frame.render_widget(review, area);
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_status_bar(
     frame: &mut Frame,
     area: Rect,
@@ -2239,6 +2577,10 @@ fn render_status_bar(
         Color::Magenta,
     ));
 
+    if !save_active {
+        segments.push(("? Help".to_string(), Color::Black, Color::Gray));
+    }
+
     if tab == BrowseTab::Review && !save_active {
         segments.push((
             "Ctrl+Enter Apply".to_string(),
@@ -2390,6 +2732,74 @@ fn build_list_title(title: &str, status: Option<&str>) -> String {
     out
 }
 
+fn help_lines(apply_label: &str, query: &str) -> Vec<Line<'static>> {
+    let query = query.trim().to_lowercase();
+    let mut lines = Vec::new();
+    let mut current_section = None;
+    for (section, keys, action) in HELP_BINDINGS {
+        let keys = if *keys == APPLY_KEY_PLACEHOLDER {
+            apply_label.to_string()
+        } else {
+            keys.to_string()
+        };
+        if !query.is_empty()
+            && !keys.to_lowercase().contains(&query)
+            && !action.to_lowercase().contains(&query)
+            && !section.to_lowercase().contains(&query)
+        {
+            continue;
+        }
+        if current_section != Some(*section) {
+            if current_section.is_some() {
+                lines.push(Line::from(""));
+            }
+            lines.push(Line::from(Span::styled(
+                section.to_string(),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            current_section = Some(*section);
+        }
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {keys:<32}"), Style::default().fg(Color::Cyan)),
+            Span::raw(action.to_string()),
+        ]));
+    }
+    if lines.is_empty() {
+        lines.push(Line::from("No matching bindings."));
+    }
+    lines
+}
+
+fn render_help(
+    frame: &mut Frame,
+    area: Rect,
+    lines: Vec<Line<'static>>,
+    query: &str,
+    scroll: &mut u16,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(area);
+    frame.render_widget(Clear, area);
+    render_search_input(frame, chunks[0], query, true);
+
+    let visible = chunks[1].height.saturating_sub(2) as usize;
+    let max_scroll = lines.len().saturating_sub(visible.max(1));
+    *scroll = (*scroll).min(max_scroll as u16);
+    let help = Paragraph::new(Text::from(lines))
+        .block(
+            Block::default()
+                .title("Keybindings  (? / Esc to close)")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .scroll((*scroll, 0));
+    frame.render_widget(help, chunks[1]);
+}
+
 fn render_search_input(frame: &mut Frame, area: Rect, query: &str, focused: bool) {
     let (content, style) = if query.is_empty() {
         (
@@ -2455,6 +2865,7 @@ fn active_picker_mut<'a>(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn rebuild_active_filtered(
     tab: BrowseTab,
     theme: &mut PickerState,
@@ -2481,6 +2892,63 @@ fn rebuild_active_filtered(
     }
 }
 
+fn tui_state_path() -> Option<PathBuf> {
+    let home = std::env::var("HOME").ok()?;
+    Some(PathBuf::from(home).join(".config/theme-manager/tui-state.toml"))
+}
+
+fn load_tui_state(path: &Path) -> TuiState {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| toml::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_tui_state(path: &Path, state: &TuiState) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, toml::to_string_pretty(state)?)?;
+    Ok(())
+}
+
+// Best effort: a failed write must not block leaving the TUI.
+fn save_browse_state(path: Option<&Path>, tab: BrowseTab, selected_theme: &str) {
+    let Some(path) = path else {
+        return;
+    };
+    let state = TuiState {
+        last_tab: Some(tab_name(tab).to_string()),
+        last_theme: (selected_theme != NO_THEME_CHANGE_VALUE).then(|| selected_theme.to_string()),
+    };
+    let _ = write_tui_state(path, &state);
+}
+
+fn tab_name(tab: BrowseTab) -> &'static str {
+    match tab {
+        BrowseTab::Theme => "theme",
+        BrowseTab::Waybar => "waybar",
+        BrowseTab::Walker => "walker",
+        BrowseTab::Hyprlock => "hyprlock",
+        BrowseTab::Starship => "starship",
+        BrowseTab::Presets => "presets",
+        BrowseTab::Review => "review",
+    }
+}
+
+fn tab_from_name(name: &str) -> Option<BrowseTab> {
+    match name {
+        "theme" => Some(BrowseTab::Theme),
+        "waybar" => Some(BrowseTab::Waybar),
+        "walker" => Some(BrowseTab::Walker),
+        "hyprlock" => Some(BrowseTab::Hyprlock),
+        "starship" => Some(BrowseTab::Starship),
+        "presets" => Some(BrowseTab::Presets),
+        "review" => Some(BrowseTab::Review),
+        _ => None,
+    }
+}
+
 fn tab_index(tab: BrowseTab) -> usize {
     match tab {
         BrowseTab::Theme => 0,
@@ -2545,6 +3013,23 @@ fn select_option_by_value(state: &mut PickerState, items: &[OptionItem], value:
     false
 }
 
+// Prefer the applied theme, then the last highlighted one; stale names fall through to the top.
+fn select_initial_theme(
+    state: &mut PickerState,
+    items: &[OptionItem],
+    current: Option<&str>,
+    saved: Option<&str>,
+) {
+    let selected = [current, saved]
+        .into_iter()
+        .flatten()
+        .any(|name| select_option_by_value(state, items, name));
+    if !selected && !state.filtered_indices.is_empty() {
+        state.list_state.select(Some(0));
+        state.last_selected = state.filtered_indices.first().copied();
+    }
+}
+
 fn select_preset_by_name(state: &mut PickerState, items: &[PresetItem], name: &str) -> bool {
     if let Some(item_index) = items.iter().position(|item| item.name == name) {
         if let Some(filtered_pos) = state
@@ -2593,6 +3078,7 @@ fn preset_starship_key(preset: &presets::PresetDefinition) -> Option<(String, St
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn apply_preset_to_states(
     config: &ResolvedConfig,
     preset_items: &[PresetItem],
@@ -2625,7 +3111,7 @@ fn apply_preset_to_states(
     }
 
     *selected_theme = applied_theme.clone();
-    *theme_path = theme_ops::resolve_theme_path(config, &applied_theme)?;
+    *theme_path = theme_ops::resolve_theme_source(config, &applied_theme)?;
 
     *waybar_items = build_waybar_items(config, theme_path)?;
     *walker_items = build_walker_items(config, theme_path)?;
@@ -2864,7 +3350,10 @@ fn current_starship_selection(
     };
     match items[index].kind.as_str() {
         "none" => StarshipSelection::None,
-        "theme" => StarshipSelection::Theme(theme_path.join("starship.toml")),
+        "theme" => StarshipSelection::Theme(
+            starship::find_theme_starship_config(theme_path)
+                .unwrap_or_else(|| theme_path.join("starship.toml")),
+        ),
         "preset" => StarshipSelection::Preset(items[index].value.clone()),
         _ => StarshipSelection::Named(items[index].value.clone()),
     }
@@ -2916,7 +3405,7 @@ fn filter_item_indices<T: ItemView>(items: &[T], query: &str) -> Vec<usize> {
     let mut scored: Vec<(i64, usize, String)> = Vec::new();
     for (idx, item) in items.iter().enumerate() {
         let label = item.label();
-        if let Some(score) = fuzzy_score(&label, query) {
+        if let Some((score, _)) = fuzzy_score(&label, query) {
             scored.push((score, idx, label));
         }
     }
@@ -2924,7 +3413,9 @@ fn filter_item_indices<T: ItemView>(items: &[T], query: &str) -> Vec<usize> {
     scored.into_iter().map(|(_, idx, _)| idx).collect()
 }
 
-fn fuzzy_score(label: &str, query: &str) -> Option<i64> {
+// Returns the score plus the matched character positions in `label`: the contiguous
+// run when the query appears verbatim, otherwise the subsequence hits.
+fn fuzzy_score(label: &str, query: &str) -> Option<(i64, Vec<usize>)> {
     let query = query.trim();
     if query.is_empty() {
         return None;
@@ -2959,8 +3450,15 @@ fn fuzzy_score(label: &str, query: &str) -> Option<i64> {
         }
     }
     if q != query_chars.len() {
-        return if score > 0 { Some(score) } else { None };
+        return None;
     }
+    let matched = match contains_pos {
+        Some(pos) => {
+            let start = label_lower[..pos].chars().count();
+            (start..start + qlen).collect()
+        }
+        None => positions.clone(),
+    };
 
     score += 2000;
     if positions.first() == Some(&0) {
@@ -2983,7 +3481,41 @@ fn fuzzy_score(label: &str, query: &str) -> Option<i64> {
         score -= 5000;
     }
     score += 500 - label_chars.len() as i64;
-    Some(score)
+    Some((score, matched))
+}
+
+// Styles the characters the search query matched; labels render plainly without a query.
+fn highlighted_label(label: String, query: &str) -> Line<'static> {
+    let Some((_, positions)) = fuzzy_score(&label, query) else {
+        return Line::from(label);
+    };
+    let match_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut run = String::new();
+    let mut run_matched = false;
+    for (idx, ch) in label.chars().enumerate() {
+        let matched = positions.binary_search(&idx).is_ok();
+        if matched != run_matched && !run.is_empty() {
+            let text = std::mem::take(&mut run);
+            spans.push(if run_matched {
+                Span::styled(text, match_style)
+            } else {
+                Span::raw(text)
+            });
+        }
+        run_matched = matched;
+        run.push(ch);
+    }
+    if !run.is_empty() {
+        spans.push(if run_matched {
+            Span::styled(run, match_style)
+        } else {
+            Span::raw(run)
+        });
+    }
+    Line::from(spans)
 }
 
 fn is_word_boundary(chars: &[char], idx: usize) -> bool {
@@ -3060,10 +3592,14 @@ fn selected_index(state: &ListState, len: usize) -> usize {
     state.selected().unwrap_or(0).min(len.saturating_sub(1))
 }
 
-fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
+// `mouse_capture` is off for `browse --no-mouse`, for terminals that mishandle it.
+fn setup_terminal(mouse_capture: bool) -> Result<Terminal<CrosstermBackend<Stdout>>> {
     enable_raw_mode()?;
     let mut stdout = stdout();
-    execute!(stdout, terminal::EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, terminal::EnterAlternateScreen)?;
+    if mouse_capture {
+        execute!(stdout, EnableMouseCapture)?;
+    }
     let _ = execute!(
         stdout,
         PushKeyboardEnhancementFlags(
@@ -3076,11 +3612,16 @@ fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
     Terminal::new(backend).map_err(|err| anyhow!("failed to init terminal: {err}"))
 }
 
-fn cleanup_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
+fn cleanup_terminal(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    mouse_capture: bool,
+) -> Result<()> {
     disable_raw_mode()?;
+    if mouse_capture {
+        execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    }
     execute!(
         terminal.backend_mut(),
-        DisableMouseCapture,
         PopKeyboardEnhancementFlags,
         terminal::LeaveAlternateScreen
     )?;
@@ -3088,6 +3629,80 @@ fn cleanup_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result
     Ok(())
 }
 
+// Runs `restore` when dropped while armed, so a panic or early `?` return inside the
+// TUI loop still hands the shell back out of raw mode and the alternate screen.
+struct RestoreGuard<F: FnMut()> {
+    restore: Option<F>,
+}
+
+impl<F: FnMut()> RestoreGuard<F> {
+    fn new(restore: F) -> Self {
+        Self {
+            restore: Some(restore),
+        }
+    }
+
+    // Called right before the normal `cleanup_terminal` path takes over.
+    fn disarm(&mut self) {
+        self.restore = None;
+    }
+}
+
+impl<F: FnMut()> Drop for RestoreGuard<F> {
+    fn drop(&mut self) {
+        if let Some(mut restore) = self.restore.take() {
+            restore();
+        }
+    }
+}
+
+type PanicHook = dyn Fn(&PanicHookInfo<'_>) + Sync + Send + 'static;
+
+// RestoreGuard only runs after the panic message has been printed, i.e. onto the
+// alternate screen it then leaves. This hook restores the terminal first and then hands
+// over to the previous hook; dropping the guard reinstates that hook.
+struct PanicHookGuard {
+    previous: Arc<PanicHook>,
+}
+
+impl PanicHookGuard {
+    fn install(restore: impl Fn() + Sync + Send + 'static) -> Self {
+        let previous: Arc<PanicHook> = Arc::from(panic::take_hook());
+        let chained = Arc::clone(&previous);
+        panic::set_hook(Box::new(move |info| {
+            restore();
+            chained(info);
+        }));
+        Self { previous }
+    }
+}
+
+impl Drop for PanicHookGuard {
+    fn drop(&mut self) {
+        // Hooks cannot be swapped while panicking; the process is on its way out anyway.
+        if std::thread::panicking() {
+            return;
+        }
+        let previous = Arc::clone(&self.previous);
+        panic::set_hook(Box::new(move |info| previous(info)));
+    }
+}
+
+// Best-effort `cleanup_terminal` that does not need the `Terminal`, for use while unwinding.
+fn restore_terminal(mouse_capture: bool) {
+    let _ = disable_raw_mode();
+    let mut stdout = stdout();
+    if mouse_capture {
+        let _ = execute!(stdout, DisableMouseCapture);
+    }
+    let _ = execute!(
+        stdout,
+        PopKeyboardEnhancementFlags,
+        terminal::LeaveAlternateScreen,
+        crossterm::cursor::Show
+    );
+}
+
 fn inner_rect(rect: Rect) -> Rect {
     let pad = 2;
     Rect {
@@ -3146,104 +3761,6 @@ fn previous_index(current: Option<usize>, len: usize) -> usize {
     }
 }
 
-fn list_waybar_themes(waybar_themes_dir: &Path) -> Result<Vec<String>> {
-    if !waybar_themes_dir.is_dir() {
-        return Ok(Vec::new());
-    }
-    let mut entries = Vec::new();
-    for entry in fs::read_dir(waybar_themes_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_dir() && path.join("config.jsonc").is_file() && path.join("style.css").is_file()
-        {
-            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                entries.push(name.to_string());
-            }
-        }
-    }
-    entries.sort();
-    Ok(entries)
-}
-
-fn list_walker_themes(walker_themes_dir: &Path) -> Result<Vec<String>> {
-    if !walker_themes_dir.is_dir() {
-        return Ok(Vec::new());
-    }
-    let mut entries = Vec::new();
-    for entry in fs::read_dir(walker_themes_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        // Walker themes require style.css, layout.xml is optional
-        if path.is_dir() && path.join("style.css").is_file() {
-            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                // Skip the auto-generated theme
-                if name != "theme-manager-auto" {
-                    entries.push(name.to_string());
-                }
-            }
-        }
-    }
-    entries.sort();
-    Ok(entries)
-}
-
-fn list_hyprlock_themes(hyprlock_themes_dir: &Path) -> Result<Vec<String>> {
-    if !hyprlock_themes_dir.is_dir() {
-        return Ok(Vec::new());
-    }
-    let mut entries = Vec::new();
-    for entry in fs::read_dir(hyprlock_themes_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_dir() && path.join("hyprlock.conf").is_file() {
-            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                entries.push(name.to_string());
-            }
-        }
-    }
-    entries.sort();
-    Ok(entries)
-}
-
-fn list_starship_presets() -> Vec<String> {
-    if !command_exists("starship") {
-        return Vec::new();
-    }
-    if let Ok(output) = Command::new("starship").args(["preset", "--list"]).output() {
-        if output.status.success() {
-            return parse_lines(&output.stdout);
-        }
-    }
-    if let Ok(output) = Command::new("starship").args(["preset", "-l"]).output() {
-        if output.status.success() {
-            return parse_lines(&output.stdout);
-        }
-    }
-    Vec::new()
-}
-
-fn list_starship_themes(dir: &Path) -> Result<Vec<String>> {
-    if !dir.is_dir() {
-        return Ok(Vec::new());
-    }
-    let mut themes = Vec::new();
-    for entry in fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_file() {
-            if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-                if ext.eq_ignore_ascii_case("toml") {
-                    if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
-                        themes.push(stem.to_string());
-                    }
-                }
-            }
-        }
-    }
-    themes.sort();
-    Ok(themes)
-}
-
 fn display_theme_name(name: &str) -> String {
     if name == "omarchy-default" {
         "Omarchy-Default".to_string()
@@ -3288,12 +3805,13 @@ fn convert_span(span: CoreSpan<'static>) -> ratatui::text::Span<'static> {
 }
 
 fn convert_style(style: CoreStyle) -> Style {
-    let mut out = Style::default();
-    out.fg = style.fg.map(convert_color);
-    out.bg = style.bg.map(convert_color);
-    out.add_modifier = convert_modifier(style.add_modifier);
-    out.sub_modifier = convert_modifier(style.sub_modifier);
-    out
+    Style {
+        fg: style.fg.map(convert_color),
+        bg: style.bg.map(convert_color),
+        add_modifier: convert_modifier(style.add_modifier),
+        sub_modifier: convert_modifier(style.sub_modifier),
+        ..Style::default()
+    }
 }
 
 fn convert_modifier(modifier: CoreModifier) -> Modifier {
@@ -3332,15 +3850,6 @@ fn convert_color(color: CoreColor) -> Color {
     }
 }
 
-fn parse_lines(output: &[u8]) -> Vec<String> {
-    String::from_utf8_lossy(output)
-        .lines()
-        .map(|line| line.trim())
-        .filter(|line| !line.is_empty())
-        .map(|line| line.to_string())
-        .collect()
-}
-
 fn command_exists(cmd: &str) -> bool {
     which::which(cmd).is_ok()
 }
@@ -3408,20 +3917,6 @@ impl ApplyKey {
     }
 }
 
-fn term_contains(value: &str) -> bool {
-    std::env::var("TERM")
-        .unwrap_or_default()
-        .to_lowercase()
-        .contains(value)
-}
-
-fn term_program_contains(value: &str) -> bool {
-    std::env::var("TERM_PROGRAM")
-        .unwrap_or_default()
-        .to_lowercase()
-        .contains(value)
-}
-
 trait ItemView {
     fn label(&self) -> String;
 }
@@ -3458,6 +3953,96 @@ mod tests {
         }
     }
 
+    #[test]
+    fn restore_guard_runs_when_unwinding_unless_disarmed() {
+        use std::cell::Cell;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let restored = Cell::new(false);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let _guard = RestoreGuard::new(|| restored.set(true));
+            panic!("preview closure failed");
+        }));
+        assert!(result.is_err());
+        assert!(restored.get());
+
+        let restored = Cell::new(false);
+        let mut guard = RestoreGuard::new(|| restored.set(true));
+        guard.disarm();
+        drop(guard);
+        assert!(!restored.get());
+    }
+
+    #[test]
+    fn panic_hook_guard_restores_before_the_panic_is_reported() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let restored = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&restored);
+        let guard = PanicHookGuard::install(move || flag.store(true, Ordering::SeqCst));
+        let result = catch_unwind(AssertUnwindSafe(|| panic!("render failed")));
+        drop(guard);
+        assert!(result.is_err());
+        assert!(restored.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn highlight_code_falls_back_for_unknown_code_theme() {
+        let css = "window { color: #ffffff; }\n";
+        let fallback = highlight_code(css, "css", "no-such-theme");
+        assert_eq!(fallback, highlight_code(css, "css", DEFAULT_CODE_THEME));
+        assert_ne!(fallback, highlight_code(css, "css", "InspiredGitHub"));
+    }
+
+    #[test]
+    fn plain_code_preview_skips_highlighting() {
+        let css = "window { color: #ffffff; }\n";
+        let plain = load_code_preview_from_string("style.css", css, "css", None);
+        let body: Vec<String> = plain.lines[2..]
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(body, vec!["window { color: #ffffff; }".to_string()]);
+        assert!(plain
+            .lines
+            .iter()
+            .flat_map(|line| &line.spans)
+            .all(|span| !span.content.contains('\x1b') && span.style == Style::default()));
+
+        let colored =
+            load_code_preview_from_string("style.css", css, "css", Some(DEFAULT_CODE_THEME));
+        assert!(colored.lines[2..]
+            .iter()
+            .flat_map(|line| &line.spans)
+            .any(|span| span.style.fg.is_some()));
+    }
+
+    #[test]
+    fn highlight_code_reuses_cached_syntax_sets() {
+        let css = "window { color: #ffffff; }\n";
+        let first = highlight_code(css, "css", DEFAULT_CODE_THEME);
+        let syntaxes = syntax_set() as *const SyntaxSet;
+        let themes = theme_set() as *const ThemeSet;
+
+        let started = Instant::now();
+        for _ in 0..20 {
+            assert_eq!(highlight_code(css, "css", DEFAULT_CODE_THEME), first);
+        }
+        assert!(std::ptr::eq(syntax_set(), syntaxes));
+        assert!(std::ptr::eq(theme_set(), themes));
+        assert!(
+            started.elapsed() < Duration::from_secs(2),
+            "cached highlights took {:?}",
+            started.elapsed()
+        );
+    }
+
     #[test]
     fn filter_items_empty_query_returns_all() {
         let items = vec![
@@ -3542,6 +4127,45 @@ mod tests {
         assert_eq!(filtered, vec![0]);
     }
 
+    #[test]
+    fn fuzzy_score_reports_matched_positions() {
+        let positions = |label: &str, query: &str| fuzzy_score(label, query).map(|(_, p)| p);
+        assert_eq!(
+            positions("Tokyo Night", "night"),
+            Some(vec![6, 7, 8, 9, 10])
+        );
+        assert_eq!(positions("dracula", "drc"), Some(vec![0, 1, 3]));
+        assert_eq!(positions("Rosé Pine", "pine"), Some(vec![5, 6, 7, 8]));
+        assert_eq!(positions("nord", "xyz"), None);
+
+        let line = highlighted_label("dracula".to_string(), "drc");
+        let texts: Vec<&str> = line
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(texts, ["dr", "a", "c", "ula"]);
+        assert_eq!(highlighted_label("nord".to_string(), "").spans.len(), 1);
+    }
+
+    #[test]
+    fn selection_target_keeps_current_theme_when_asked() {
+        let current = || Some("nord".to_string());
+        assert_eq!(
+            selection_target("dracula", false, current()).unwrap(),
+            ("dracula".to_string(), false)
+        );
+        assert_eq!(
+            selection_target("dracula", true, current()).unwrap(),
+            ("nord".to_string(), true)
+        );
+        assert_eq!(
+            selection_target(NO_THEME_CHANGE_VALUE, false, current()).unwrap(),
+            ("nord".to_string(), true)
+        );
+        assert!(selection_target("dracula", true, None).is_err());
+    }
+
     #[test]
     fn preset_keys_map_to_items() {
         let preset = presets::PresetDefinition {
@@ -3622,23 +4246,116 @@ mod tests {
         );
     }
 
+    fn line_text(line: &Line<'_>) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
     #[test]
-    fn preview_backend_detection_precedence() {
-        assert_eq!(
-            detect_preview_backend_kind(true, true, true, true),
-            PreviewBackendKind::Kitty
-        );
+    fn help_lines_list_every_section_and_substitute_apply_key() {
+        let lines: Vec<String> = help_lines("Alt+a", "").iter().map(line_text).collect();
+        for section in ["Global", "Lists", "Presets", "Review", "Help"] {
+            assert!(
+                lines.iter().any(|line| line == section),
+                "missing {section}"
+            );
+        }
+        assert!(lines
+            .iter()
+            .any(|line| line.contains("Alt+a") && line.contains("Apply selection")));
+        assert!(!lines
+            .iter()
+            .any(|line| line.contains(APPLY_KEY_PLACEHOLDER)));
+    }
+
+    #[test]
+    fn help_lines_filter_by_query() {
+        let lines: Vec<String> = help_lines("Ctrl+Enter", "preset")
+            .iter()
+            .map(line_text)
+            .collect();
+        assert!(lines
+            .iter()
+            .any(|line| line.contains("Save selection as a preset")));
+        assert!(!lines.iter().any(|line| line.contains("Toggle this help")));
+
+        let none: Vec<String> = help_lines("Ctrl+Enter", "zzz")
+            .iter()
+            .map(line_text)
+            .collect();
+        assert_eq!(none, ["No matching bindings."]);
+    }
+
+    #[test]
+    fn tui_state_round_trips_and_tolerates_missing_file() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("theme-manager/tui-state.toml");
+        assert_eq!(load_tui_state(&path), TuiState::default());
+
+        save_browse_state(Some(&path), BrowseTab::Walker, "tokyo-night");
+        let state = load_tui_state(&path);
         assert_eq!(
-            detect_preview_backend_kind(false, true, false, true),
-            PreviewBackendKind::Sixel
+            state.last_tab.as_deref().and_then(tab_from_name),
+            Some(BrowseTab::Walker)
         );
+        assert_eq!(state.last_theme.as_deref(), Some("tokyo-night"));
+
+        save_browse_state(Some(&path), BrowseTab::Review, NO_THEME_CHANGE_VALUE);
+        assert_eq!(load_tui_state(&path).last_theme, None);
+    }
+
+    fn theme_option_items(names: &[&str]) -> Vec<OptionItem> {
+        names
+            .iter()
+            .map(|name| OptionItem {
+                label: title_case_theme(name),
+                value: name.to_string(),
+                preview: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn initial_theme_selection_prefers_current_theme() {
+        let items = theme_option_items(&[NO_THEME_CHANGE_VALUE, "gruvbox", "nord", "tokyo-night"]);
+        let mut state = PickerState::new();
+        rebuild_filtered(&mut state, &items);
+        select_initial_theme(&mut state, &items, Some("nord"), Some("gruvbox"));
+        assert_eq!(current_theme_value(&items, &state).as_deref(), Some("nord"));
+    }
+
+    #[test]
+    fn initial_theme_selection_falls_back_to_saved_then_first() {
+        let items = theme_option_items(&[NO_THEME_CHANGE_VALUE, "gruvbox", "nord"]);
+        let mut state = PickerState::new();
+        rebuild_filtered(&mut state, &items);
+        select_initial_theme(&mut state, &items, Some("removed"), Some("gruvbox"));
         assert_eq!(
-            detect_preview_backend_kind(false, true, false, false),
-            PreviewBackendKind::Chafa
+            current_theme_value(&items, &state).as_deref(),
+            Some("gruvbox")
         );
+
+        let mut state = PickerState::new();
+        rebuild_filtered(&mut state, &items);
+        select_initial_theme(&mut state, &items, None, Some("also-removed"));
         assert_eq!(
-            detect_preview_backend_kind(false, false, false, true),
-            PreviewBackendKind::None
+            current_theme_value(&items, &state).as_deref(),
+            Some(NO_THEME_CHANGE_VALUE)
         );
     }
+
+    #[test]
+    fn stale_saved_theme_is_not_selected() {
+        let items = vec![OptionItem {
+            label: "Gruvbox".to_string(),
+            value: "gruvbox".to_string(),
+            preview: None,
+        }];
+        let mut state = PickerState::new();
+        rebuild_filtered(&mut state, &items);
+        assert!(!select_option_by_value(&mut state, &items, "removed-theme"));
+        assert!(select_option_by_value(&mut state, &items, "gruvbox"));
+    }
 }
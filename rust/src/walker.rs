@@ -5,9 +5,10 @@ use std::path::Path;
 use crate::config::ResolvedConfig;
 use crate::omarchy_defaults;
 use crate::omarchy_defaults::SymlinkEnsureResult;
-use crate::theme_ops::{CommandContext, WalkerMode};
+use crate::output;
+use crate::theme_ops::{self, CommandContext, WalkerMode};
 
-const AUTO_THEME_NAME: &str = "theme-manager-auto";
+pub const AUTO_THEME_NAME: &str = "theme-manager-auto";
 const OMARCHY_DEFAULT_THEME_NAME: &str = "omarchy-default";
 
 pub fn prepare_walker(ctx: &CommandContext<'_>, theme_dir: &Path) -> Result<()> {
@@ -96,9 +97,10 @@ pub fn ensure_omarchy_default_theme_link(config: &ResolvedConfig, quiet: bool) -
         SymlinkEnsureResult::SkippedNonSymlink => {
             if !quiet {
                 eprintln!(
-          "theme-manager: warning: preserving non-symlink path {}; cannot link Omarchy default Walker theme",
-          link_path.to_string_lossy()
-        );
+                    "{} preserving non-symlink path {}; cannot link Omarchy default Walker theme",
+                    output::warning_prefix(),
+                    link_path.to_string_lossy()
+                );
             }
         }
         SymlinkEnsureResult::Unchanged => {}
@@ -267,3 +269,35 @@ fn cleanup_auto_theme_dir(walker_themes_dir: &Path, quiet: bool) -> Result<()> {
     }
     Ok(())
 }
+
+pub fn cmd_list(config: &ResolvedConfig) -> Result<()> {
+    let names = list_walker_themes(&config.walker_themes_dir)?;
+    theme_ops::print_component_options(
+        &config.walker_themes_dir,
+        names,
+        omarchy_defaults::resolve_walker_default(config).is_some(),
+    );
+    Ok(())
+}
+
+pub fn list_walker_themes(walker_themes_dir: &Path) -> Result<Vec<String>> {
+    if !walker_themes_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(walker_themes_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        // Walker themes require style.css, layout.xml is optional
+        if path.is_dir() && path.join("style.css").is_file() {
+            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                // Skip the auto-generated theme
+                if name != AUTO_THEME_NAME {
+                    entries.push(name.to_string());
+                }
+            }
+        }
+    }
+    entries.sort();
+    Ok(entries)
+}
@@ -0,0 +1,140 @@
+use anyhow::{anyhow, Result};
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+use crate::lock;
+use crate::output;
+use crate::paths::{current_theme_name, normalize_theme_name};
+use crate::theme_ops::{self, CommandContext};
+
+const DEBOUNCE: Duration = Duration::from_millis(300);
+const POLL: Duration = Duration::from_millis(200);
+
+// Editors save through temp files and renames, so one save arrives as a burst of
+// events; an apply only fires once the burst has been quiet for `delay`.
+pub struct Debouncer {
+    delay: Duration,
+    last_event: Option<Instant>,
+}
+
+impl Debouncer {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            last_event: None,
+        }
+    }
+
+    pub fn record(&mut self, now: Instant) {
+        self.last_event = Some(now);
+    }
+
+    // Returns true once per burst, when no event has arrived for `delay`.
+    pub fn fire(&mut self, now: Instant) -> bool {
+        match self.last_event {
+            Some(last) if now.saturating_duration_since(last) >= self.delay => {
+                self.last_event = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // How long to wait for the next event before checking `fire` again.
+    pub fn wait(&self, now: Instant, idle: Duration) -> Duration {
+        match self.last_event {
+            Some(last) => self
+                .delay
+                .saturating_sub(now.saturating_duration_since(last))
+                .min(idle),
+            None => idle,
+        }
+    }
+}
+
+pub fn cmd_watch(ctx: &CommandContext<'_>, theme: Option<&str>) -> Result<()> {
+    let current = current_theme_name(&ctx.config.current_theme_link)?;
+    let name = match theme {
+        Some(theme) => normalize_theme_name(theme),
+        None => current.clone().ok_or_else(|| {
+            anyhow!(
+                "current theme not set: {}",
+                ctx.config.current_theme_link.to_string_lossy()
+            )
+        })?,
+    };
+    let source = theme_ops::resolve_theme_source(ctx.config, &name)?;
+    if current.as_deref() != Some(name.as_str()) {
+        theme_ops::cmd_set(ctx, &name)?;
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |event| {
+        let _ = tx.send(event);
+    })?;
+    watcher.watch(&source, RecursiveMode::Recursive)?;
+
+    let running = Arc::new(AtomicBool::new(true));
+    let handler_flag = Arc::clone(&running);
+    ctrlc::set_handler(move || handler_flag.store(false, Ordering::SeqCst))?;
+
+    println!(
+        "theme-manager: watching {} (Ctrl+C to stop)",
+        source.to_string_lossy()
+    );
+    let mut debouncer = Debouncer::new(DEBOUNCE);
+    while running.load(Ordering::SeqCst) {
+        match rx.recv_timeout(debouncer.wait(Instant::now(), POLL)) {
+            Ok(Ok(event)) if !matches!(event.kind, EventKind::Access(_)) => {
+                debouncer.record(Instant::now());
+            }
+            Ok(Ok(_)) => {}
+            Ok(Err(err)) => eprintln!("{} watch error: {err}", output::warning_prefix()),
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+        if debouncer.fire(Instant::now()) {
+            reapply(ctx, &source, &name);
+        }
+    }
+    println!("theme-manager: stopped watching {name}");
+    Ok(())
+}
+
+// A broken edit should not end the session, so failures only warn.
+fn reapply(ctx: &CommandContext<'_>, source: &Path, name: &str) {
+    let result =
+        lock::acquire(ctx.config).and_then(|_lock| theme_ops::reapply_theme_dir(ctx, source));
+    match result {
+        Ok(()) if !ctx.quiet => println!("theme-manager: reapplied {name}"),
+        Ok(()) => {}
+        Err(err) => eprintln!("{} reapply failed: {err}", output::warning_prefix()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debouncer_fires_once_after_a_quiet_period() {
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+        let mut debouncer = Debouncer::new(ms(300));
+        assert!(!debouncer.fire(start));
+        assert_eq!(debouncer.wait(start, ms(200)), ms(200));
+
+        debouncer.record(start);
+        debouncer.record(start + ms(100));
+        debouncer.record(start + ms(250));
+        assert!(!debouncer.fire(start + ms(400)));
+        assert_eq!(debouncer.wait(start + ms(400), ms(200)), ms(150));
+
+        assert!(debouncer.fire(start + ms(550)));
+        assert!(!debouncer.fire(start + ms(900)));
+        assert_eq!(debouncer.wait(start + ms(900), ms(200)), ms(200));
+    }
+}
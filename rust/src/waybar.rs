@@ -4,25 +4,30 @@ use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::config::ResolvedConfig;
-use crate::omarchy::{RestartAction, RestartCommand};
+use crate::omarchy::{self, RestartAction};
 use crate::omarchy_defaults;
 use crate::omarchy_defaults::SymlinkEnsureResult;
-use crate::theme_ops::{CommandContext, WaybarMode};
+use crate::output;
+use crate::theme_ops::{self, CommandContext, WaybarMode};
 use walkdir::WalkDir;
 
 const WAYBAR_LINKS_FILE: &str = ".theme-manager-waybar-links";
+// Manifest entries with this prefix are plain copies (copy mode) rather than links.
+const COPIED_ENTRY_PREFIX: &str = "copy:";
+// Hardlink mode records `hardlink:<name>\t<source>` so cleanup can confirm the inode.
+const HARDLINK_ENTRY_PREFIX: &str = "hardlink:";
 const OMARCHY_DEFAULT_THEME_NAME: &str = "omarchy-default";
 
 pub fn prepare_waybar(ctx: &CommandContext<'_>, theme_dir: &Path) -> Result<Option<RestartAction>> {
     ensure_omarchy_default_theme_link(ctx.config, ctx.quiet)?;
 
-    let waybar_dir = match ctx.waybar_mode {
-        WaybarMode::None => return Ok(None),
-        WaybarMode::Auto => theme_dir.join("waybar-theme"),
-        WaybarMode::Named => match &ctx.waybar_name {
-            Some(name) => ctx.config.waybar_themes_dir.join(name),
-            None => return Ok(None),
-        },
+    let Some(waybar_dir) = resolve_waybar_dir(
+        ctx.config,
+        &ctx.waybar_mode,
+        ctx.waybar_name.as_deref(),
+        theme_dir,
+    ) else {
+        return Ok(None);
     };
 
     if !waybar_dir.is_dir() {
@@ -50,11 +55,213 @@ pub fn prepare_waybar(ctx: &CommandContext<'_>, theme_dir: &Path) -> Result<Opti
     cleanup_waybar_links(&ctx.config.waybar_dir, ctx.quiet)?;
 
     let apply_mode = ctx.config.waybar_apply_mode.as_str();
-    if apply_mode == "copy" {
-        return apply_copy(ctx, &config_path, &style_path);
+    match apply_mode {
+        "copy" => apply_copy(ctx, &config_path, &style_path),
+        "hardlink" => apply_hardlink(ctx, &config_path, &style_path),
+        _ => apply_symlink(ctx, &config_path, &style_path),
+    }
+}
+
+pub fn resolve_waybar_dir(
+    config: &ResolvedConfig,
+    mode: &WaybarMode,
+    name: Option<&str>,
+    theme_dir: &Path,
+) -> Option<PathBuf> {
+    match mode {
+        WaybarMode::None => None,
+        WaybarMode::Auto => Some(theme_dir.join("waybar-theme")),
+        WaybarMode::Named => name.map(|name| config.waybar_themes_dir.join(name)),
+    }
+}
+
+// Read-only diagnostic: reports problems without touching ~/.config/waybar or restarting.
+pub fn check_waybar_theme(config: &ResolvedConfig, waybar_dir: &Path) -> Result<()> {
+    println!(
+        "theme-manager: checking waybar theme {}",
+        waybar_dir.to_string_lossy()
+    );
+    let checks = waybar_theme_checks(config, waybar_dir)?;
+    let mut failures = 0;
+    for (ok, message) in &checks {
+        if !ok {
+            failures += 1;
+        }
+        println!("  {} {message}", if *ok { "ok  " } else { "FAIL" });
+    }
+
+    if failures > 0 {
+        let plural = if failures == 1 { "" } else { "s" };
+        return Err(anyhow!(
+            "waybar theme check failed ({failures} problem{plural})"
+        ));
+    }
+    println!("theme-manager: waybar theme check passed");
+    Ok(())
+}
+
+// (passed, description) for each check `waybar --check` and `lint` report.
+pub fn waybar_theme_checks(
+    config: &ResolvedConfig,
+    waybar_dir: &Path,
+) -> Result<Vec<(bool, String)>> {
+    let mut checks = Vec::new();
+    let mut report = |ok: bool, message: String| checks.push((ok, message));
+
+    if !waybar_dir.is_dir() {
+        report(false, "theme directory not found".to_string());
+        return Ok(checks);
     }
 
-    apply_symlink(ctx, &config_path, &style_path)
+    let config_path = waybar_dir.join("config.jsonc");
+    let style_path = waybar_dir.join("style.css");
+    report(config_path.is_file(), "config.jsonc present".to_string());
+    report(style_path.is_file(), "style.css present".to_string());
+
+    if config_path.is_file() {
+        let content = fs::read_to_string(&config_path)?;
+        match serde_json::from_str::<serde_json::Value>(&strip_jsonc(&content)) {
+            Ok(_) => report(true, "config.jsonc parses".to_string()),
+            Err(err) => report(false, format!("config.jsonc does not parse: {err}")),
+        }
+    }
+
+    if style_path.is_file() {
+        let content = fs::read_to_string(&style_path)?;
+        for target in css_imports(&content) {
+            if target.contains("://") {
+                continue;
+            }
+            let resolved = import_candidates(config, waybar_dir, &target)
+                .into_iter()
+                .any(|path| path.is_file());
+            let message = if resolved {
+                format!("@import \"{target}\" resolves")
+            } else {
+                format!("@import \"{target}\" does not resolve")
+            };
+            report(resolved, message);
+        }
+    }
+    Ok(checks)
+}
+
+// Waybar loads style.css from ~/.config/waybar, so relative imports may target either location.
+fn import_candidates(config: &ResolvedConfig, waybar_dir: &Path, target: &str) -> Vec<PathBuf> {
+    if let Some(rest) = target.strip_prefix("~/") {
+        let home = std::env::var("HOME").unwrap_or_default();
+        return vec![PathBuf::from(home).join(rest)];
+    }
+    let path = Path::new(target);
+    if path.is_absolute() {
+        return vec![path.to_path_buf()];
+    }
+    vec![waybar_dir.join(path), config.waybar_dir.join(path)]
+}
+
+fn css_imports(content: &str) -> Vec<String> {
+    css_references(content)
+        .into_iter()
+        .filter(|reference| reference.import)
+        .map(|reference| content[reference.start..reference.end].to_string())
+        .collect()
+}
+
+struct CssReference {
+    start: usize,
+    end: usize,
+    import: bool,
+}
+
+// Byte ranges of `@import "x"`, `@import url(x)` and `url(x)` targets, without quotes.
+fn css_references(content: &str) -> Vec<CssReference> {
+    let mut references = Vec::new();
+    let mut i = 0;
+    while i < content.len() {
+        let next_import = content[i..].find("@import").map(|pos| pos + i);
+        let next_url = content[i..].find("url(").map(|pos| pos + i);
+        let (pos, import) = match (next_import, next_url) {
+            (Some(a), Some(b)) if a < b => (a, true),
+            (Some(a), None) => (a, true),
+            (_, Some(b)) => (b, false),
+            (None, None) => break,
+        };
+        let mut j = pos + if import { "@import".len() } else { 0 };
+        j += content[j..].len() - content[j..].trim_start().len();
+        let url = content[j..].starts_with("url(");
+        if url {
+            j += "url(".len();
+            j += content[j..].len() - content[j..].trim_start().len();
+        }
+        let Some(first) = content[j..].chars().next() else {
+            break;
+        };
+        let (start, end, resume) = if first == '"' || first == '\'' {
+            match content[j + 1..].find(first) {
+                Some(len) => (j + 1, j + 1 + len, j + 2 + len),
+                None => break,
+            }
+        } else if url {
+            match content[j..].find(')') {
+                Some(len) => {
+                    let target = content[j..j + len].trim_end();
+                    (j, j + target.len(), j + len + 1)
+                }
+                None => break,
+            }
+        } else {
+            i = j;
+            continue;
+        };
+        if end > start {
+            references.push(CssReference { start, end, import });
+        }
+        i = resume;
+    }
+    references
+}
+
+fn strip_jsonc(content: &str) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let mut out = String::with_capacity(content.len());
+    let mut i = 0;
+    let mut in_string = false;
+    while i < chars.len() {
+        let c = chars[i];
+        if in_string {
+            out.push(c);
+            if c == '\\' && i + 1 < chars.len() {
+                out.push(chars[i + 1]);
+                i += 1;
+            } else if c == '"' {
+                in_string = false;
+            }
+        } else if c == '"' {
+            in_string = true;
+            out.push(c);
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i += 2;
+            continue;
+        } else if c == ',' {
+            let next = chars[i + 1..].iter().find(|ch| !ch.is_whitespace());
+            if !matches!(next, Some(']') | Some('}')) {
+                out.push(c);
+            }
+        } else {
+            out.push(c);
+        }
+        i += 1;
+    }
+    out
 }
 
 pub fn ensure_omarchy_default_theme_link(config: &ResolvedConfig, quiet: bool) -> Result<()> {
@@ -86,9 +293,10 @@ pub fn ensure_omarchy_default_theme_link(config: &ResolvedConfig, quiet: bool) -
         SymlinkEnsureResult::SkippedNonSymlink => {
             if !quiet {
                 eprintln!(
-          "theme-manager: warning: preserving non-symlink path {}; cannot link Omarchy default Waybar theme",
-          link_path.to_string_lossy()
-        );
+                    "{} preserving non-symlink path {}; cannot link Omarchy default Waybar theme",
+                    output::warning_prefix(),
+                    link_path.to_string_lossy()
+                );
             }
         }
         SymlinkEnsureResult::Unchanged => {}
@@ -135,20 +343,43 @@ fn apply_copy(
         ctx.quiet,
     )?;
     fs::copy(config_path, &dest_config)?;
-    fs::copy(style_path, &dest_style)?;
-
-    copy_waybar_subdirs(
+    let style = fs::read_to_string(style_path)?;
+    let (style, style_imports) = copy_style_references(
+        &style,
         theme_waybar_dir,
         &ctx.config.waybar_dir,
         &ctx.config.waybar_themes_dir,
         &mut backup_dir,
         ctx.quiet,
+        !ctx.config.waybar_include_optional_files,
     )?;
+    fs::write(&dest_style, style)?;
 
-    Ok(Some(RestartAction::Command(RestartCommand {
-        cmd: "omarchy-restart-waybar".to_string(),
-        args: Vec::new(),
-    })))
+    let mut copied = style_imports;
+    copied.extend(copy_waybar_subdirs(
+        theme_waybar_dir,
+        &ctx.config.waybar_dir,
+        &ctx.config.waybar_themes_dir,
+        &mut backup_dir,
+        ctx.quiet,
+    )?);
+    copied.extend(place_waybar_files(
+        ctx,
+        theme_waybar_dir,
+        &mut backup_dir,
+        "copying",
+        |src, dest| {
+            fs::copy(src, dest)?;
+            Ok(())
+        },
+    )?);
+    let copied: Vec<String> = copied
+        .iter()
+        .map(|name| format!("{COPIED_ENTRY_PREFIX}{name}"))
+        .collect();
+    write_links_manifest(&ctx.config.waybar_dir, &copied)?;
+
+    Ok(Some(omarchy::waybar_restart_action(ctx.config)))
 }
 
 fn apply_symlink(
@@ -191,18 +422,193 @@ fn apply_symlink(
         &mut backup_dir,
         ctx.quiet,
     )?;
-    link_waybar_subdirs(
+    let mut linked = link_waybar_subdirs(
         theme_waybar_dir,
         &ctx.config.waybar_dir,
         &ctx.config.waybar_themes_dir,
         &mut backup_dir,
         ctx.quiet,
     )?;
+    linked.extend(place_waybar_files(
+        ctx,
+        theme_waybar_dir,
+        &mut backup_dir,
+        "linking",
+        |src, dest| Ok(std::os::unix::fs::symlink(src, dest)?),
+    )?);
+    write_links_manifest(&ctx.config.waybar_dir, &linked)?;
+
+    Ok(Some(omarchy::waybar_restart_action(ctx.config)))
+}
+
+// For sandboxed Waybar setups that cannot follow symlinks out of ~/.config/waybar,
+// while edits to the theme files still show up live.
+fn apply_hardlink(
+    ctx: &CommandContext<'_>,
+    config_path: &Path,
+    style_path: &Path,
+) -> Result<Option<RestartAction>> {
+    fs::create_dir_all(&ctx.config.waybar_dir)?;
+    let theme_waybar_dir = config_path
+        .parent()
+        .ok_or_else(|| anyhow!("waybar config has no parent directory"))?;
+    let mut backup_dir = None;
+
+    if !ctx.quiet {
+        println!(
+            "theme-manager: hardlinking waybar config from {}",
+            config_path.to_string_lossy()
+        );
+        println!(
+            "theme-manager: hardlinking waybar style from {}",
+            style_path.to_string_lossy()
+        );
+    }
+
+    let dest_config = ctx.config.waybar_dir.join("config.jsonc");
+    let dest_style = ctx.config.waybar_dir.join("style.css");
+    replace_existing_path(
+        &dest_config,
+        "config.jsonc",
+        &ctx.config.waybar_themes_dir,
+        &mut backup_dir,
+        ctx.quiet,
+    )?;
+    hardlink_or_copy(config_path, &dest_config)?;
+    replace_existing_path(
+        &dest_style,
+        "style.css",
+        &ctx.config.waybar_themes_dir,
+        &mut backup_dir,
+        ctx.quiet,
+    )?;
+    hardlink_or_copy(style_path, &dest_style)?;
+
+    let mut linked = vec![
+        ("config.jsonc".to_string(), config_path.to_path_buf()),
+        ("style.css".to_string(), style_path.to_path_buf()),
+    ];
+    for (name, entry_path) in waybar_subdirs(theme_waybar_dir)? {
+        let dest = ctx.config.waybar_dir.join(&name);
+        replace_existing_path(
+            &dest,
+            &name,
+            &ctx.config.waybar_themes_dir,
+            &mut backup_dir,
+            ctx.quiet,
+        )?;
+        hardlink_dir_recursive(&entry_path, &dest)?;
+        if !ctx.quiet {
+            println!(
+                "theme-manager: hardlinking waybar subdir {}",
+                dest.to_string_lossy()
+            );
+        }
+        linked.push((name, entry_path));
+    }
+    let files = place_waybar_files(
+        ctx,
+        theme_waybar_dir,
+        &mut backup_dir,
+        "hardlinking",
+        hardlink_or_copy,
+    )?;
+    linked.extend(
+        files
+            .into_iter()
+            .map(|name| (name.clone(), theme_waybar_dir.join(name))),
+    );
+    // Cross-filesystem fallbacks are plain copies and are recorded as such.
+    let entries: Vec<String> = linked
+        .iter()
+        .map(|(name, source)| {
+            let dest = ctx.config.waybar_dir.join(name);
+            if is_managed_hardlink(&dest, source) {
+                format!(
+                    "{HARDLINK_ENTRY_PREFIX}{name}\t{}",
+                    source.to_string_lossy()
+                )
+            } else {
+                format!("{COPIED_ENTRY_PREFIX}{name}")
+            }
+        })
+        .collect();
+    write_links_manifest(&ctx.config.waybar_dir, &entries)?;
+
+    Ok(Some(omarchy::waybar_restart_action(ctx.config)))
+}
 
-    Ok(Some(RestartAction::Command(RestartCommand {
-        cmd: "omarchy-restart-waybar".to_string(),
-        args: Vec::new(),
-    })))
+// Hard links cannot cross filesystems; fall back to a plain copy there.
+fn hardlink_or_copy(source: &Path, dest: &Path) -> Result<()> {
+    if fs::hard_link(source, dest).is_err() {
+        fs::copy(source, dest)?;
+    }
+    Ok(())
+}
+
+fn hardlink_dir_recursive(source: &Path, dest: &Path) -> Result<()> {
+    fs::create_dir_all(dest)?;
+    for entry in WalkDir::new(source).follow_links(false) {
+        let entry = entry?;
+        let entry_path = entry.path();
+        let rel = entry_path.strip_prefix(source)?;
+        if rel.as_os_str().is_empty() {
+            continue;
+        }
+        let target_path = dest.join(rel);
+        let file_type = entry.file_type();
+        if file_type.is_dir() {
+            fs::create_dir_all(&target_path)?;
+        } else if file_type.is_symlink() {
+            let link_target = fs::read_link(entry_path)?;
+            std::os::unix::fs::symlink(link_target, &target_path)?;
+        } else {
+            hardlink_or_copy(entry_path, &target_path)?;
+        }
+    }
+    Ok(())
+}
+
+// A path we hardlinked from `source`: every file still shares its inode with the
+// matching file there, so removing it loses nothing. Files the user hardlinked from
+// anywhere else do not match and are left to the backup logic.
+fn is_managed_hardlink(path: &Path, source: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    let mut hardlinked = 0;
+    for entry in WalkDir::new(path).follow_links(false) {
+        let Ok(entry) = entry else {
+            return false;
+        };
+        let file_type = entry.file_type();
+        if file_type.is_dir() || file_type.is_symlink() {
+            continue;
+        }
+        let Ok(rel) = entry.path().strip_prefix(path) else {
+            return false;
+        };
+        let original = if rel.as_os_str().is_empty() {
+            source.to_path_buf()
+        } else {
+            source.join(rel)
+        };
+        match (entry.metadata(), fs::metadata(&original)) {
+            (Ok(meta), Ok(orig)) if meta.dev() == orig.dev() && meta.ino() == orig.ino() => {
+                hardlinked += 1
+            }
+            _ => return false,
+        }
+    }
+    hardlinked > 0
+}
+
+fn remove_managed_path(path: &Path, meta: &fs::Metadata) -> Result<()> {
+    if meta.is_dir() {
+        fs::remove_dir_all(path)?;
+    } else {
+        fs::remove_file(path)?;
+    }
+    Ok(())
 }
 
 fn cleanup_waybar_links(waybar_dir: &Path, quiet: bool) -> Result<()> {
@@ -217,21 +623,44 @@ fn cleanup_waybar_links(waybar_dir: &Path, quiet: bool) -> Result<()> {
         if name.is_empty() {
             continue;
         }
-        let path = waybar_dir.join(name);
+        let copied = name.strip_prefix(COPIED_ENTRY_PREFIX);
+        let hardlinked = name
+            .strip_prefix(HARDLINK_ENTRY_PREFIX)
+            .and_then(|entry| entry.split_once('\t'));
+        let entry_name = copied.or(hardlinked.map(|(name, _)| name)).unwrap_or(name);
+        let path = waybar_dir.join(entry_name);
         let meta = match fs::symlink_metadata(&path) {
             Ok(meta) => meta,
             Err(_) => continue,
         };
-        if !meta.file_type().is_symlink() {
-            continue;
-        }
-        if !quiet {
-            println!(
-                "theme-manager: removing waybar link {}",
-                path.to_string_lossy()
-            );
+        if let Some((_, source)) = hardlinked {
+            if !is_managed_hardlink(&path, Path::new(source)) {
+                continue;
+            }
+            if !quiet {
+                println!(
+                    "theme-manager: removing waybar hardlinked path {}",
+                    path.to_string_lossy()
+                );
+            }
+            let _ = remove_managed_path(&path, &meta);
+        } else if copied.is_some() {
+            if !quiet {
+                println!(
+                    "theme-manager: removing copied waybar path {}",
+                    path.to_string_lossy()
+                );
+            }
+            let _ = remove_managed_path(&path, &meta);
+        } else if meta.file_type().is_symlink() {
+            if !quiet {
+                println!(
+                    "theme-manager: removing waybar link {}",
+                    path.to_string_lossy()
+                );
+            }
+            let _ = fs::remove_file(&path);
         }
-        let _ = fs::remove_file(&path);
     }
 
     let _ = fs::remove_file(&manifest_path);
@@ -244,13 +673,75 @@ fn link_waybar_subdirs(
     waybar_themes_dir: &Path,
     backup_dir: &mut Option<PathBuf>,
     quiet: bool,
-) -> Result<()> {
+) -> Result<Vec<String>> {
     let mut linked = Vec::new();
+    for (name, entry_path) in waybar_subdirs(theme_waybar_dir)? {
+        let dest = waybar_dir.join(&name);
+        replace_existing_path(&dest, &name, waybar_themes_dir, backup_dir, quiet)?;
+
+        std::os::unix::fs::symlink(&entry_path, &dest)?;
+        if !quiet {
+            println!(
+                "theme-manager: linking waybar subdir {}",
+                dest.to_string_lossy()
+            );
+        }
+        linked.push(name);
+    }
+    Ok(linked)
+}
+
+// Loose files next to config.jsonc/style.css (e.g. colors.css), placed with `place`
+// and returned for the links manifest. Dotfiles are skipped.
+fn place_waybar_files(
+    ctx: &CommandContext<'_>,
+    theme_waybar_dir: &Path,
+    backup_dir: &mut Option<PathBuf>,
+    verb: &str,
+    place: impl Fn(&Path, &Path) -> Result<()>,
+) -> Result<Vec<String>> {
+    let mut placed = Vec::new();
+    if !ctx.config.waybar_include_optional_files {
+        return Ok(placed);
+    }
+    for entry in fs::read_dir(theme_waybar_dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name == "config.jsonc" || name == "style.css" || name.starts_with('.') {
+            continue;
+        }
+        let entry_path = entry.path();
+        if !entry_path.is_file() {
+            continue;
+        }
+        let dest = ctx.config.waybar_dir.join(&name);
+        replace_existing_path(
+            &dest,
+            &name,
+            &ctx.config.waybar_themes_dir,
+            backup_dir,
+            ctx.quiet,
+        )?;
+        place(&entry_path, &dest)?;
+        if !ctx.quiet {
+            println!(
+                "theme-manager: {verb} waybar file {}",
+                dest.to_string_lossy()
+            );
+        }
+        placed.push(name);
+    }
+    placed.sort();
+    Ok(placed)
+}
+
+// Directories (or symlinks to directories) next to config.jsonc/style.css.
+fn waybar_subdirs(theme_waybar_dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let mut subdirs = Vec::new();
     for entry in fs::read_dir(theme_waybar_dir)? {
         let entry = entry?;
-        let name = entry.file_name();
-        let name_str = name.to_string_lossy();
-        if name_str == "config.jsonc" || name_str == "style.css" {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name == "config.jsonc" || name == "style.css" {
             continue;
         }
         let file_type = entry.file_type()?;
@@ -264,23 +755,14 @@ fn link_waybar_subdirs(
         } else {
             false
         };
-        if !is_dir {
-            continue;
+        if is_dir {
+            subdirs.push((name, entry_path));
         }
-
-        let dest = waybar_dir.join(&name);
-        replace_existing_path(&dest, &name_str, waybar_themes_dir, backup_dir, quiet)?;
-
-        std::os::unix::fs::symlink(&entry_path, &dest)?;
-        if !quiet {
-            println!(
-                "theme-manager: linking waybar subdir {}",
-                dest.to_string_lossy()
-            );
-        }
-        linked.push(name_str.to_string());
     }
+    Ok(subdirs)
+}
 
+fn write_links_manifest(waybar_dir: &Path, linked: &[String]) -> Result<()> {
     let manifest_path = waybar_dir.join(WAYBAR_LINKS_FILE);
     if linked.is_empty() {
         let _ = fs::remove_file(&manifest_path);
@@ -289,44 +771,84 @@ fn link_waybar_subdirs(
 
     let mut manifest = String::new();
     for name in linked {
-        manifest.push_str(&name);
+        manifest.push_str(name);
         manifest.push('\n');
     }
     fs::write(manifest_path, manifest)?;
     Ok(())
 }
 
-fn copy_waybar_subdirs(
+// Relative references in a copied style.css would otherwise point into ~/.config/waybar.
+// Sibling files are copied next to it (unless all loose files are copied anyway);
+// paths leaving the theme dir become absolute. Returns the rewritten style and the
+// copied sibling names for the links manifest.
+fn copy_style_references(
+    style: &str,
     theme_waybar_dir: &Path,
     waybar_dir: &Path,
     waybar_themes_dir: &Path,
     backup_dir: &mut Option<PathBuf>,
     quiet: bool,
-) -> Result<()> {
-    for entry in fs::read_dir(theme_waybar_dir)? {
-        let entry = entry?;
-        let name = entry.file_name();
-        let name_str = name.to_string_lossy();
-        if name_str == "config.jsonc" || name_str == "style.css" {
+    copy_siblings: bool,
+) -> Result<(String, Vec<String>)> {
+    let mut output = String::with_capacity(style.len());
+    let mut copied = Vec::new();
+    let mut last = 0;
+    for reference in css_references(style) {
+        let target = &style[reference.start..reference.end];
+        if target.contains(':') || target.starts_with('~') || target.starts_with('#') {
             continue;
         }
-        let file_type = entry.file_type()?;
-        let entry_path = entry.path();
-        let is_dir = if file_type.is_dir() {
-            true
-        } else if file_type.is_symlink() {
-            fs::metadata(&entry_path)
-                .map(|meta| meta.is_dir())
-                .unwrap_or(false)
-        } else {
-            false
-        };
-        if !is_dir {
+        let relative = Path::new(target);
+        if relative.is_absolute() {
+            continue;
+        }
+        let source = theme_waybar_dir.join(relative);
+        if !source.is_file() {
             continue;
         }
 
+        let components: Vec<_> = relative.components().collect();
+        if components.len() == 1 {
+            if !copy_siblings || copied.iter().any(|name| name == target) {
+                continue;
+            }
+            let dest = waybar_dir.join(relative);
+            replace_existing_path(&dest, target, waybar_themes_dir, backup_dir, quiet)?;
+            fs::copy(&source, &dest)?;
+            if !quiet {
+                println!(
+                    "theme-manager: copying waybar style import {}",
+                    source.to_string_lossy()
+                );
+            }
+            copied.push(target.to_string());
+        } else if components
+            .iter()
+            .any(|component| matches!(component, std::path::Component::ParentDir))
+            && !waybar_dir.join(relative).is_file()
+        {
+            let absolute = fs::canonicalize(&source)?;
+            output.push_str(&style[last..reference.start]);
+            output.push_str(&absolute.to_string_lossy());
+            last = reference.end;
+        }
+    }
+    output.push_str(&style[last..]);
+    Ok((output, copied))
+}
+
+fn copy_waybar_subdirs(
+    theme_waybar_dir: &Path,
+    waybar_dir: &Path,
+    waybar_themes_dir: &Path,
+    backup_dir: &mut Option<PathBuf>,
+    quiet: bool,
+) -> Result<Vec<String>> {
+    let mut copied = Vec::new();
+    for (name, entry_path) in waybar_subdirs(theme_waybar_dir)? {
         let dest = waybar_dir.join(&name);
-        replace_existing_path(&dest, &name_str, waybar_themes_dir, backup_dir, quiet)?;
+        replace_existing_path(&dest, &name, waybar_themes_dir, backup_dir, quiet)?;
         copy_dir_recursive(&entry_path, &dest)?;
         if !quiet {
             println!(
@@ -334,8 +856,9 @@ fn copy_waybar_subdirs(
                 dest.to_string_lossy()
             );
         }
+        copied.push(name);
     }
-    Ok(())
+    Ok(copied)
 }
 
 fn copy_dir_recursive(source: &Path, dest: &Path) -> Result<()> {
@@ -446,3 +969,32 @@ fn timestamp_suffix() -> Result<u64> {
         .map_err(|err| anyhow!("time error: {err}"))?
         .as_secs())
 }
+
+pub fn cmd_list(config: &ResolvedConfig) -> Result<()> {
+    let names = list_waybar_themes(&config.waybar_themes_dir)?;
+    theme_ops::print_component_options(
+        &config.waybar_themes_dir,
+        names,
+        omarchy_defaults::resolve_waybar_default(config).is_some(),
+    );
+    Ok(())
+}
+
+pub fn list_waybar_themes(waybar_themes_dir: &Path) -> Result<Vec<String>> {
+    if !waybar_themes_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(waybar_themes_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() && path.join("config.jsonc").is_file() && path.join("style.css").is_file()
+        {
+            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                entries.push(name.to_string());
+            }
+        }
+    }
+    entries.sort();
+    Ok(entries)
+}
@@ -1,5 +1,6 @@
 mod support;
 
+use predicates::prelude::PredicateBooleanExt;
 use std::fs;
 use support::*;
 
@@ -41,6 +42,7 @@ theme_root_dir = "~/.config/omarchy/themes-local"
 
     let mut cmd = cmd_with_env(&env);
     cmd.current_dir(&project);
+    cmd.env("THEME_MANAGER_TRUST_LOCAL", "1");
     cmd.args(["set", "local-theme"]);
     cmd.assert().success();
 
@@ -48,3 +50,447 @@ theme_root_dir = "~/.config/omarchy/themes-local"
     let marker = fs::read_to_string(theme_dir.join("marker.txt")).unwrap();
     assert_eq!(marker, "local");
 }
+
+#[test]
+fn local_config_is_ignored_when_disabled() {
+    let env = setup_env();
+    let project = env.temp.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+    fs::create_dir_all(omarchy_dir(&env.home).join("themes/user-theme")).unwrap();
+    write_toml(
+        &project.join(".theme-manager.toml"),
+        r#"[paths]
+theme_root_dir = "~/.config/omarchy/themes-local"
+"#,
+    );
+    fs::create_dir_all(env.home.join(".config/omarchy/themes-local/local-theme")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.current_dir(&project);
+    cmd.env("THEME_MANAGER_TRUST_LOCAL", "1");
+    cmd.arg("list");
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::diff("Local Theme\n"));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.current_dir(&project);
+    cmd.env("THEME_MANAGER_TRUST_LOCAL", "1");
+    cmd.env("THEME_MANAGER_NO_LOCAL_CONFIG", "1");
+    cmd.arg("list");
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::diff("User Theme\n"));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.current_dir(&project);
+    cmd.env("THEME_MANAGER_TRUST_LOCAL", "1");
+    cmd.args(["--no-local-config", "list"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::diff("User Theme\n"));
+}
+
+#[test]
+fn untrusted_local_config_paths_are_ignored_with_a_warning() {
+    let env = setup_env();
+    let project = env.temp.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+    write_toml(
+        &project.join(".theme-manager.toml"),
+        r#"[paths]
+theme_root_dir = "/tmp/evil-themes"
+omarchy_bin_dir = "/tmp/evil-bin"
+
+[tui]
+code_theme = "InspiredGitHub"
+"#,
+    );
+    let default_root = format!(
+        "THEME_ROOT_DIR={}\n",
+        omarchy_dir(&env.home).join("themes").display()
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.current_dir(&project);
+    cmd.arg("print-config");
+    cmd.assert()
+        .success()
+        .stdout(
+            predicates::str::contains(default_root.as_str())
+                .and(predicates::str::contains("OMARCHY_BIN_DIR=/tmp/evil-bin").not())
+                .and(predicates::str::contains("TUI_CODE_THEME=InspiredGitHub")),
+        )
+        .stderr(predicates::str::contains(
+            "ignoring paths.theme_root_dir, paths.omarchy_bin_dir from untrusted",
+        ));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.current_dir(&project);
+    cmd.env("THEME_MANAGER_TRUST_LOCAL", "1");
+    cmd.arg("print-config");
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "THEME_ROOT_DIR=/tmp/evil-themes\n",
+        ))
+        .stderr(predicates::str::contains("untrusted").not());
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.current_dir(&project);
+    cmd.args(["--trust-local", "print-config"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("OMARCHY_BIN_DIR=/tmp/evil-bin"));
+}
+
+#[test]
+fn untrusted_local_config_cannot_set_commands() {
+    let env = setup_env();
+    let project = env.temp.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+    let marker = env.temp.path().join("pwned");
+    write_toml(
+        &project.join(".theme-manager.toml"),
+        &format!(
+            r#"[waybar]
+restart_cmd = "touch {marker}"
+
+[walker]
+restart_cmd = "touch {marker}"
+
+[behavior]
+on_apply_cmd = "touch {marker}"
+run_theme_hooks = true
+quiet_default = true
+"#,
+            marker = marker.display()
+        ),
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.current_dir(&project);
+    cmd.arg("print-config");
+    cmd.assert()
+        .success()
+        .stdout(
+            predicates::str::contains("WAYBAR_RESTART_CMD=\n")
+                .and(predicates::str::contains("WALKER_RESTART_CMD=\n"))
+                .and(predicates::str::contains("ON_APPLY_CMD=\n"))
+                .and(predicates::str::contains("RUN_THEME_HOOKS=\n"))
+                .and(predicates::str::contains("QUIET_MODE_DEFAULT=1")),
+        )
+        .stderr(predicates::str::contains(
+            "ignoring waybar.restart_cmd, walker.restart_cmd, behavior.on_apply_cmd, behavior.run_theme_hooks from untrusted",
+        ));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.current_dir(&project);
+    cmd.args(["--trust-local", "print-config"]);
+    cmd.assert().success().stdout(
+        predicates::str::contains(format!("WAYBAR_RESTART_CMD=touch {}", marker.display())).and(
+            predicates::str::contains(format!("ON_APPLY_CMD=touch {}", marker.display())),
+        ),
+    );
+}
+
+#[test]
+fn config_path_prints_user_config_location() {
+    let env = setup_env();
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["config", "path"]);
+    let expected = format!(
+        "{}\n",
+        env.home.join(".config/theme-manager/config.toml").display()
+    );
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::diff(expected));
+}
+
+#[test]
+fn config_path_and_edit_follow_config_and_profile_flags() {
+    let env = setup_env();
+    let custom = env.temp.path().join("custom.toml");
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("--config").arg(&custom).args(["config", "path"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::diff(format!("{}\n", custom.display())));
+
+    let profile = env.home.join(".config/theme-manager/profiles/work.toml");
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["--profile", "work", "config", "path"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::diff(format!("{}\n", profile.display())));
+
+    let marker = env.temp.path().join("editor-arg");
+    let editor = env.bin.join("fake-editor");
+    write_script(
+        &editor,
+        &format!(
+            "#!/usr/bin/env bash\n\necho \"$1\" > {}\n",
+            marker.display()
+        ),
+    );
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("VISUAL");
+    cmd.env("EDITOR", &editor);
+    cmd.arg("--config").arg(&custom).args(["config", "edit"]);
+    cmd.assert().success();
+    assert!(fs::read_to_string(&custom)
+        .unwrap()
+        .contains("# theme_root_dir = \"~/.config/omarchy/themes\""));
+    assert_eq!(
+        fs::read_to_string(&marker).unwrap().trim(),
+        custom.to_string_lossy()
+    );
+    assert!(!env.home.join(".config/theme-manager/config.toml").exists());
+}
+
+#[test]
+fn config_edit_creates_template_and_opens_editor() {
+    let env = setup_env();
+    let marker = env.temp.path().join("editor-arg");
+    let editor = env.bin.join("fake-editor");
+    write_script(
+        &editor,
+        &format!(
+            "#!/usr/bin/env bash\n\necho \"$1\" > {}\n",
+            marker.display()
+        ),
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("VISUAL");
+    cmd.env("EDITOR", &editor);
+    cmd.args(["config", "edit"]);
+    cmd.assert().success();
+
+    let config_path = env.home.join(".config/theme-manager/config.toml");
+    let template = fs::read_to_string(&config_path).unwrap();
+    assert!(template.contains("# theme_root_dir = \"~/.config/omarchy/themes\""));
+    assert!(template.contains("# awww_transition_type = \"grow\""));
+    assert_eq!(
+        fs::read_to_string(&marker).unwrap().trim(),
+        config_path.to_string_lossy()
+    );
+}
+
+#[test]
+fn unknown_config_keys_emit_warning() {
+    let env = setup_env();
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        r#"[waybar]
+aply_mode = "copy"
+"#,
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("print-config");
+    cmd.assert().success().stderr(predicates::str::contains(
+        "unknown config key `waybar.aply_mode`",
+    ));
+}
+
+#[test]
+fn unknown_config_key_warning_can_be_disabled() {
+    let env = setup_env();
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        r#"[waybar]
+aply_mode = "copy"
+
+[behavior]
+warn_unknown_config = false
+"#,
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("print-config");
+    cmd.assert()
+        .success()
+        .stderr(predicates::str::contains("unknown config key").not());
+}
+
+#[test]
+fn color_auto_omits_escape_codes_when_piped() {
+    let env = setup_env();
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        r#"[waybar]
+aply_mode = "copy"
+"#,
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("NO_COLOR");
+    cmd.args(["--color", "auto", "print-config"]);
+    cmd.assert()
+        .success()
+        .stderr(predicates::str::contains("theme-manager: warning:"))
+        .stderr(predicates::str::contains("\x1b[").not());
+}
+
+#[test]
+fn color_always_colors_warnings_even_when_piped() {
+    let env = setup_env();
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        r#"[waybar]
+aply_mode = "copy"
+"#,
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["--color", "always", "print-config"]);
+    cmd.assert()
+        .success()
+        .stderr(predicates::str::contains("\x1b[33mwarning\x1b[0m"));
+}
+
+#[test]
+fn config_explain_lists_keys_with_defaults_and_env() {
+    let env = setup_env();
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["config", "explain"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "waybar.apply_mode\n  type: string\n  default: \"symlink\"\n  env: WAYBAR_APPLY_MODE\n",
+        ))
+        .stdout(predicates::str::contains(
+            "behavior.awww_transition_fps\n  type: integer\n  default: 60\n  env: (none)\n",
+        ));
+}
+
+#[test]
+fn config_explain_prints_single_key() {
+    let env = setup_env();
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["config", "explain", "paths.lock_file"]);
+    let expected = format!(
+        "paths.lock_file\n  type: path\n  default: \"{}\"\n  env: THEME_MANAGER_LOCK_FILE\n",
+        env.home.join(".config/theme-manager/.lock").display()
+    );
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::diff(expected));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["config", "explain", "waybar.nope"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("unknown config key: waybar.nope"));
+}
+
+#[test]
+fn config_flag_replaces_user_config() {
+    let env = setup_env();
+    let user_cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&user_cfg_dir).unwrap();
+    write_toml(
+        &user_cfg_dir.join("config.toml"),
+        r#"[paths]
+theme_root_dir = "~/.config/omarchy/themes-user"
+"#,
+    );
+    fs::create_dir_all(env.home.join(".config/omarchy/themes-user/user-theme")).unwrap();
+
+    let alt_cfg = env.temp.path().join("alt.toml");
+    write_toml(
+        &alt_cfg,
+        r#"[paths]
+theme_root_dir = "~/.config/omarchy/themes-alt"
+"#,
+    );
+    fs::create_dir_all(env.home.join(".config/omarchy/themes-alt/alt-theme")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("--config").arg(&alt_cfg).arg("list");
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("Alt Theme"))
+        .stdout(predicates::str::contains("User Theme").not());
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("--config")
+        .arg(env.temp.path().join("missing.toml"))
+        .arg("list");
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("config file not found"));
+}
+
+#[test]
+fn profiles_layer_theme_roots_and_namespace_history() {
+    let env = setup_env();
+    let profiles = env.home.join(".config/theme-manager/profiles");
+    fs::create_dir_all(&profiles).unwrap();
+    write_toml(
+        &profiles.join("work.toml"),
+        r#"[paths]
+theme_root_dir = "~/themes-work"
+"#,
+    );
+    write_toml(
+        &profiles.join("home.toml"),
+        r#"[paths]
+theme_root_dir = "~/themes-home"
+"#,
+    );
+    fs::create_dir_all(env.home.join("themes-work/office-light")).unwrap();
+    fs::create_dir_all(env.home.join("themes-home/cozy-dark")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["--profile", "work", "list"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("Office Light"))
+        .stdout(predicates::str::contains("Cozy Dark").not());
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["--profile", "home", "set", "cozy-dark"]);
+    cmd.assert().success();
+
+    let history = fs::read_to_string(profiles.join("home/history.jsonl")).unwrap();
+    assert!(history.contains("cozy-dark"));
+    assert!(!profiles.join("work/history.jsonl").exists());
+    assert!(!env
+        .home
+        .join(".config/theme-manager/history.jsonl")
+        .exists());
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["--profile", "missing", "list"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("profile not found"));
+}
+
+#[test]
+fn invalid_config_exits_with_config_code() {
+    let env = setup_env();
+    fs::create_dir_all(omarchy_dir(&env.home).join("themes/theme-a")).unwrap();
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(&cfg_dir.join("config.toml"), "[waybar]\napply_mode = \n");
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("list");
+    cmd.assert()
+        .failure()
+        .code(3)
+        .stderr(predicates::str::contains("invalid config"))
+        .stderr(predicates::str::contains("config.toml: line 2"));
+}
@@ -49,6 +49,259 @@ fn install_clones_and_sets_theme() {
     assert_eq!(name.trim(), "nord");
 }
 
+#[test]
+fn set_from_url_applies_an_installed_theme_without_cloning() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("nord")).unwrap();
+
+    let log = env.temp.path().join("git.log");
+    write_script(
+        &env.bin.join("git"),
+        &format!(
+            "#!/usr/bin/env bash\n\nprintf '%s\\n' \"$*\" >> \"{log}\"\nmkdir -p \"$3\"\n",
+            log = log.display()
+        ),
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args([
+        "set",
+        "--from-url",
+        "https://example.com/omarchy-nord-theme.git",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("nord already installed"));
+    assert!(!log.exists());
+    let name = fs::read_to_string(omarchy_dir(&env.home).join("current/theme.name")).unwrap();
+    assert_eq!(name.trim(), "nord");
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args([
+        "set",
+        "--from-url",
+        "https://example.com/omarchy-dune-theme",
+    ]);
+    cmd.assert().success();
+    assert_eq!(fs::read_to_string(&log).unwrap().lines().count(), 1);
+    assert!(themes.join("dune").is_dir());
+    let name = fs::read_to_string(omarchy_dir(&env.home).join("current/theme.name")).unwrap();
+    assert_eq!(name.trim(), "dune");
+}
+
+#[test]
+fn install_retries_failed_clone_and_cleans_partial_checkout() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(&themes).unwrap();
+
+    let counter = env.temp.path().join("clone-count");
+    write_script(
+        &env.bin.join("git"),
+        &format!(
+            "#!/usr/bin/env bash\n\ncount=$(cat \"{counter}\" 2>/dev/null || echo 0)\ncount=$((count + 1))\necho \"$count\" > \"{counter}\"\nmkdir -p \"$3\"\nif [ \"$count\" -eq 1 ]; then\n  touch \"$3/partial\"\n  exit 1\nfi\ntouch \"$3/README.md\"\nexit 0\n",
+            counter = counter.display()
+        ),
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["install", "https://example.com/omarchy-nord-theme.git"]);
+    cmd.assert()
+        .success()
+        .stderr(predicates::str::contains("attempt 1/3"));
+
+    assert_eq!(fs::read_to_string(&counter).unwrap().trim(), "2");
+    let installed = themes.join("nord");
+    assert!(installed.join("README.md").exists());
+    assert!(!installed.join("partial").exists());
+}
+
+#[test]
+fn install_gives_up_after_configured_retries() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(&themes).unwrap();
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        "[behavior]\ngit_retries = 0\n",
+    );
+
+    let log = env.temp.path().join("git.log");
+    write_script(
+        &env.bin.join("git"),
+        &format!(
+            "#!/usr/bin/env bash\n\nprintf '%s\\n' \"$*\" >> \"{}\"\nexit 1\n",
+            log.display()
+        ),
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["install", "https://example.com/omarchy-nord-theme.git"]);
+    cmd.assert().failure().stderr(predicates::str::contains(
+        "git clone failed after 1 attempt(s)",
+    ));
+
+    assert_eq!(fs::read_to_string(&log).unwrap().lines().count(), 1);
+    assert!(!themes.join("nord").exists());
+}
+
+#[test]
+fn install_failure_removes_partial_clone() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(&themes).unwrap();
+    write_script(
+        &env.bin.join("git"),
+        "#!/usr/bin/env bash\n\nmkdir -p \"$3/.git\"\ntouch \"$3/partial\"\nexit 128\n",
+    );
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        "[behavior]\ngit_retries = 0\n",
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["install", "https://example.com/omarchy-nord-theme.git"]);
+    cmd.assert()
+        .failure()
+        .code(4)
+        .stderr(predicates::str::contains("git clone failed"));
+
+    assert!(!themes.join("nord").exists());
+}
+
+#[test]
+fn install_no_apply_keeps_current_theme() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("alpha")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "alpha"]);
+    cmd.assert().success();
+
+    write_script(
+        &env.bin.join("git"),
+        "#!/usr/bin/env bash\n\nmkdir -p \"$3\"\ntouch \"$3/README.md\"\nexit 0\n",
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args([
+        "install",
+        "https://example.com/omarchy-nord-theme.git",
+        "--no-apply",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("installed nord"));
+
+    assert!(themes.join("nord/README.md").exists());
+    let name = fs::read_to_string(omarchy_dir(&env.home).join("current/theme.name")).unwrap();
+    assert_eq!(name.trim(), "alpha");
+}
+
+#[test]
+fn install_force_replaces_existing_theme() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("alpha")).unwrap();
+    fs::create_dir_all(themes.join("nord")).unwrap();
+    fs::write(themes.join("nord/stale.txt"), "old").unwrap();
+    write_script(
+        &env.bin.join("git"),
+        "#!/usr/bin/env bash\n\nmkdir -p \"$3\"\ntouch \"$3/README.md\"\nexit 0\n",
+    );
+    let url = "https://example.com/omarchy-nord-theme.git";
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["install", url, "--no-apply"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("theme already exists: nord"));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["install", url, "--force"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("replacing"));
+    assert!(themes.join("nord/README.md").exists());
+    assert!(!themes.join("nord/stale.txt").exists());
+    let name = fs::read_to_string(omarchy_dir(&env.home).join("current/theme.name")).unwrap();
+    assert_eq!(name.trim(), "nord");
+}
+
+#[test]
+fn install_force_keeps_existing_theme_when_clone_fails() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("alpha")).unwrap();
+    fs::create_dir_all(themes.join("nord")).unwrap();
+    fs::write(themes.join("nord/colors.toml"), "old").unwrap();
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        "[behavior]\ngit_retries = 0\n",
+    );
+    write_script(
+        &env.bin.join("git"),
+        "#!/usr/bin/env bash\n\nmkdir -p \"$3\"\ntouch \"$3/partial\"\nexit 128\n",
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args([
+        "install",
+        "https://example.com/omarchy-nord-theme.git",
+        "--force",
+    ]);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("git clone failed"));
+
+    assert_eq!(
+        fs::read_to_string(themes.join("nord/colors.toml")).unwrap(),
+        "old"
+    );
+    assert!(!themes.join("nord/partial").exists());
+    let mut leftovers: Vec<String> = fs::read_dir(&themes)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    leftovers.sort();
+    assert_eq!(leftovers, ["alpha", "nord"]);
+}
+
+#[test]
+fn install_force_refuses_current_only_theme() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("nord")).unwrap();
+    fs::write(themes.join("nord/keep.txt"), "old").unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "nord"]);
+    cmd.assert().success();
+
+    write_script(
+        &env.bin.join("git"),
+        "#!/usr/bin/env bash\n\nmkdir -p \"$3\"\nexit 0\n",
+    );
+    let mut cmd = cmd_with_env(&env);
+    cmd.args([
+        "install",
+        "https://example.com/omarchy-nord-theme.git",
+        "--force",
+    ]);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("cannot remove the only theme"));
+    assert!(themes.join("nord/keep.txt").exists());
+}
+
 #[test]
 fn update_warns_when_no_git_themes() {
     let env = setup_env();
@@ -60,6 +313,43 @@ fn update_warns_when_no_git_themes() {
     cmd.assert().success();
 }
 
+#[test]
+fn update_reports_failed_pulls_with_git_exit_code() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("alpha/.git")).unwrap();
+    fs::create_dir_all(themes.join("bravo")).unwrap();
+    write_script(&env.bin.join("git"), "#!/usr/bin/env bash\n\nexit 1\n");
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("update");
+    cmd.assert()
+        .failure()
+        .code(4)
+        .stderr(predicates::str::contains("git pull failed for: alpha"));
+}
+
+#[test]
+fn update_prints_progress_for_each_git_theme() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    for name in ["bravo", "alpha", "charlie"] {
+        fs::create_dir_all(themes.join(name).join(".git")).unwrap();
+    }
+    fs::create_dir_all(themes.join("plain")).unwrap();
+    write_script(&env.bin.join("git"), "#!/usr/bin/env bash\n\nexit 0\n");
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("update");
+    cmd.assert().success().stdout(predicates::str::diff(
+        "[1/3] updating alpha...\n[2/3] updating bravo...\n[3/3] updating charlie...\n",
+    ));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["update", "--quiet"]);
+    cmd.assert().success().stdout(predicates::str::is_empty());
+}
+
 #[test]
 fn remove_deletes_current_and_advances() {
     let env = setup_env();
@@ -110,3 +400,76 @@ fn remove_prompts_for_selection() {
     cmd.assert().success();
     assert!(!themes.join("bravo").exists());
 }
+
+#[test]
+fn link_registers_external_theme() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("alpha")).unwrap();
+    let source = env.home.join("dev/my-theme");
+    fs::create_dir_all(&source).unwrap();
+    fs::write(source.join("marker"), "external").unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["link", "My Theme"]).arg(&source);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("linked my-theme"));
+    assert_eq!(fs::read_link(themes.join("my-theme")).unwrap(), source);
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("list");
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::diff("Alpha\nMy Theme\n"));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "my-theme"]);
+    cmd.assert().success();
+    let marker = omarchy_dir(&env.home).join("current/theme/marker");
+    assert_eq!(fs::read_to_string(marker).unwrap(), "external");
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["link", "alpha"]).arg(&source);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("theme already exists: alpha"));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["link", "missing"]).arg(env.home.join("nope"));
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("not a directory"));
+}
+
+#[test]
+fn unlink_removes_only_symlinked_themes() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("alpha")).unwrap();
+    let source = env.home.join("dev/my-theme");
+    fs::create_dir_all(&source).unwrap();
+    fs::write(source.join("marker"), "external").unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["link", "my-theme"]).arg(&source);
+    cmd.assert().success();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["unlink", "my-theme"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("unlinked my-theme"));
+    assert!(fs::symlink_metadata(themes.join("my-theme")).is_err());
+    assert_eq!(
+        fs::read_to_string(source.join("marker")).unwrap(),
+        "external"
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["unlink", "alpha"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("not a linked theme: alpha"));
+    assert!(themes.join("alpha").is_dir());
+}
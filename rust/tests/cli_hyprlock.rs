@@ -529,3 +529,170 @@ fn hyprlock_does_not_override_non_managed_host_config() {
     let host = fs::read_to_string(hypr_dir.join("hyprlock.conf")).unwrap();
     assert_eq!(host, "source = ~/.config/hypr/custom.conf\n");
 }
+
+#[test]
+fn hyprlock_stamps_managed_marker_on_first_write() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("theme-a")).unwrap();
+
+    let hyprlock_theme = env.home.join(".config/hypr/themes/hyprlock/full-layout");
+    fs::create_dir_all(&hyprlock_theme).unwrap();
+    fs::write(
+        hyprlock_theme.join("hyprlock.conf"),
+        "background {\n  monitor =\n}\n",
+    )
+    .unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "theme-a", "--hyprlock", "full-layout"]);
+    cmd.assert().success();
+
+    let host = fs::read_to_string(env.home.join(".config/hypr/hyprlock.conf")).unwrap();
+    assert!(host.starts_with("# managed-by: theme-manager\n"));
+    assert!(host.contains("source = ~/.config/omarchy/current/theme/hyprlock.conf"));
+}
+
+#[test]
+fn hyprlock_uses_managed_marker_to_decide_ownership() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("theme-a")).unwrap();
+
+    let hyprlock_theme = env.home.join(".config/hypr/themes/hyprlock/full-layout");
+    fs::create_dir_all(&hyprlock_theme).unwrap();
+    fs::write(
+        hyprlock_theme.join("hyprlock.conf"),
+        "background {\n  monitor =\n}\n",
+    )
+    .unwrap();
+
+    let hypr_dir = env.home.join(".config/hypr");
+    let host_path = hypr_dir.join("hyprlock.conf");
+
+    // A marked file is rewritten even after it drifted from the managed content.
+    fs::write(&host_path, "# managed-by: theme-manager\nstale = true\n").unwrap();
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "theme-a", "--hyprlock", "full-layout"]);
+    cmd.assert().success();
+    let host = fs::read_to_string(&host_path).unwrap();
+    assert!(host.starts_with("# managed-by: theme-manager\n"));
+    assert!(!host.contains("stale = true"));
+
+    // A custom file that sources the current theme but carries no marker is left alone.
+    let custom = "source = ~/.config/omarchy/current/theme/hyprlock.conf\nmy_setting = 1\n";
+    fs::write(&host_path, custom).unwrap();
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "theme-a", "--hyprlock", "full-layout"]);
+    cmd.assert().success().stderr(contains("preserving custom"));
+    assert_eq!(fs::read_to_string(&host_path).unwrap(), custom);
+}
+
+#[test]
+fn hyprlock_restart_cmd_replaces_omarchy_helper_or_noops() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let omarchy_log = env.temp.path().join("omarchy-hyprlock.log");
+    write_logging_stub(&env.bin.join("omarchy-restart-hyprlock"), &omarchy_log);
+    let custom_log = env.temp.path().join("custom-hyprlock.log");
+    write_logging_stub(&env.bin.join("my-hyprlock-reload"), &custom_log);
+    let pkill_log = env.temp.path().join("pkill.log");
+    write_logging_stub(&env.bin.join("pkill"), &pkill_log);
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.env(
+        "HYPRLOCK_RESTART_CMD",
+        "my-hyprlock-reload --soft \"two words\"",
+    );
+    cmd.args(["reload", "--hyprlock"]);
+    cmd.assert().success();
+    assert_eq!(
+        fs::read_to_string(&custom_log).unwrap(),
+        "--soft two words\n"
+    );
+    assert!(pkill_log.exists());
+
+    fs::remove_file(&pkill_log).unwrap();
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.env("HYPRLOCK_RESTART_CMD", "noop");
+    cmd.args(["reload", "--hyprlock"]);
+    cmd.assert().success();
+    assert!(!pkill_log.exists());
+    assert!(!omarchy_log.exists());
+}
+
+#[test]
+fn hyprlock_host_flag_overrides_managed_host_config() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("theme-a")).unwrap();
+
+    let hyprlock_theme = env.home.join(".config/hypr/themes/hyprlock/full-layout");
+    fs::create_dir_all(&hyprlock_theme).unwrap();
+    fs::write(
+        hyprlock_theme.join("hyprlock.conf"),
+        "background {\n  monitor =\n}\n",
+    )
+    .unwrap();
+
+    let host_path = env.home.join(".config/hypr/hyprlock-alt.conf");
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.arg("--hyprlock-host").arg(&host_path).args([
+        "set",
+        "theme-a",
+        "--hyprlock",
+        "full-layout",
+    ]);
+    cmd.assert().success();
+
+    let host = fs::read_to_string(&host_path).unwrap();
+    assert!(host.starts_with("# managed-by: theme-manager\n"));
+    assert!(host.contains("source = ~/.config/omarchy/current/theme/hyprlock.conf"));
+    assert!(!env.home.join(".config/hypr/hyprlock.conf").exists());
+
+    // The config key does the same without the flag.
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        "[paths]\nhyprlock_host = \"~/.config/hypr/lock-from-config.conf\"\n",
+    );
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "theme-a", "--hyprlock", "full-layout"]);
+    cmd.assert().success();
+    assert!(env
+        .home
+        .join(".config/hypr/lock-from-config.conf")
+        .is_file());
+    assert!(!env.home.join(".config/hypr/hyprlock.conf").exists());
+}
+
+#[test]
+fn hyprlock_list_prints_modes_and_named_themes() {
+    let env = setup_env();
+    let themes_dir = env.home.join(".config/hypr/themes/hyprlock");
+    for name in ["zeta", "alpha"] {
+        let dir = themes_dir.join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("hyprlock.conf"), "").unwrap();
+    }
+    fs::create_dir_all(themes_dir.join("incomplete")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["hyprlock", "--list"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("Modes:\n  auto\n  none\n"))
+        .stdout(predicates::str::contains("  alpha\n  zeta\n"))
+        .stdout(predicates::str::contains("incomplete").not());
+}
@@ -131,6 +131,99 @@ starship.mode = "none"
     assert!(applied.contains("\"theme\": true"));
 }
 
+#[test]
+fn preset_save_refuses_to_overwrite_without_flag() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("noir")).unwrap();
+    fs::create_dir_all(themes.join("nord")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["preset", "save", "Daily", "--theme", "noir"]);
+    cmd.assert().success();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["preset", "save", "Daily", "--theme", "nord"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("preset already exists: Daily"));
+    let presets_file = env.home.join(".config/theme-manager/presets.toml");
+    let content = fs::read_to_string(&presets_file).unwrap();
+    assert!(content.contains("theme = \"noir\""));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["preset", "save", "Daily", "--theme", "nord", "--overwrite"]);
+    cmd.assert().success();
+    let content = fs::read_to_string(&presets_file).unwrap();
+    assert!(content.contains("theme = \"nord\""));
+}
+
+#[test]
+fn preset_save_from_current_snapshots_applied_components() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("noir")).unwrap();
+    let waybar_theme = env.home.join(".config/waybar/themes/shared");
+    fs::create_dir_all(&waybar_theme).unwrap();
+    fs::write(waybar_theme.join("config.jsonc"), "{}").unwrap();
+    fs::write(waybar_theme.join("style.css"), "* {}").unwrap();
+    let starship_themes = env.home.join(".config/starship-themes");
+    fs::create_dir_all(&starship_themes).unwrap();
+    fs::write(starship_themes.join("minimal.toml"), "format = \"$all\"\n").unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "noir"]);
+    cmd.assert().success();
+    for args in [["waybar", "shared"], ["starship", "named:minimal"]] {
+        let mut cmd = cmd_with_env(&env);
+        cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+        cmd.args(args);
+        cmd.assert().success();
+    }
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["preset", "save", "Snapshot", "--from-current"]);
+    cmd.assert().success();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["preset", "load", "Snapshot", "--dry-run"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("theme     noir"))
+        .stdout(predicates::str::contains("named (shared)"))
+        .stdout(predicates::str::contains("named (minimal)"))
+        .stdout(predicates::str::contains("walker    none"));
+}
+
+#[test]
+fn preset_save_from_current_detects_yaml_only_starship_theme() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("noir")).unwrap();
+    fs::write(
+        themes.join("noir/starship.yaml"),
+        "format: \"$directory$character\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "noir"]);
+    cmd.assert().success();
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["starship", "theme"]);
+    cmd.assert().success();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["preset", "save", "Snapshot", "--from-current"]);
+    cmd.assert().success();
+
+    let presets = fs::read_to_string(env.home.join(".config/theme-manager/presets.toml")).unwrap();
+    assert!(presets.contains("[preset.Snapshot.starship]\nmode = \"theme\""));
+}
+
 #[test]
 fn preset_save_persists_walker_value() {
     let env = setup_env();
@@ -255,3 +348,56 @@ starship.mode = "none"
     let applied = env.home.join(".config/omarchy/current/theme/hyprlock.conf");
     assert!(applied.exists());
 }
+
+#[test]
+fn preset_load_dry_run_prints_plan_without_applying() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("noir")).unwrap();
+    fs::create_dir_all(themes.join("tokyo")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "noir"]);
+    cmd.assert().success();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args([
+        "preset",
+        "save",
+        "Daily",
+        "--theme",
+        "tokyo",
+        "--waybar",
+        "auto",
+        "--starship",
+        "none",
+    ]);
+    cmd.assert().success();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["preset", "load", "Daily", "--dry-run"]);
+    cmd.assert().success().stdout(
+        predicates::str::contains("Preset Daily:")
+            .and(predicates::str::contains("theme     noir -> tokyo"))
+            .and(predicates::str::contains("waybar    none -> auto"))
+            .and(predicates::str::contains("starship  none (unchanged)")),
+    );
+    let name = fs::read_to_string(omarchy_dir(&env.home).join("current/theme.name")).unwrap();
+    assert_eq!(name.trim(), "noir");
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["preset", "load", "Daily", "--preview"])
+        .write_stdin("n\n");
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("preset load cancelled"));
+    let name = fs::read_to_string(omarchy_dir(&env.home).join("current/theme.name")).unwrap();
+    assert_eq!(name.trim(), "noir");
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["preset", "load", "Daily", "--diff"])
+        .write_stdin("y\n");
+    cmd.assert().success();
+    let name = fs::read_to_string(omarchy_dir(&env.home).join("current/theme.name")).unwrap();
+    assert_eq!(name.trim(), "tokyo");
+}
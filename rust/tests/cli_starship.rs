@@ -69,6 +69,63 @@ default_name = "rose-pine"
     assert_eq!(content, "user-config");
 }
 
+#[test]
+fn starship_theme_default_applies_theme_config() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("theme-a")).unwrap();
+    fs::write(themes.join("theme-a/starship.toml"), "theme-config").unwrap();
+
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        r#"[starship]
+default_mode = "theme"
+"#,
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "theme-a"]);
+    cmd.assert().success();
+
+    let applied = env.home.join(".config/starship.toml");
+    let content = fs::read_to_string(applied).unwrap();
+    assert_eq!(content, "theme-config");
+}
+
+#[test]
+fn starship_theme_converts_yaml_config_to_toml() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("theme-a")).unwrap();
+    fs::write(
+        themes.join("theme-a/starship.yaml"),
+        "add_newline: false\ncharacter:\n  success_symbol: \"[>](green)\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "theme-a"]);
+    cmd.assert().success();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["starship", "theme"]);
+    cmd.assert().success();
+
+    let applied = env.home.join(".config/starship.toml");
+    let applied: toml::Value = toml::from_str(&fs::read_to_string(applied).unwrap()).unwrap();
+    assert_eq!(applied["add_newline"].as_bool(), Some(false));
+    assert_eq!(
+        applied["character"]["success_symbol"].as_str(),
+        Some("[>](green)")
+    );
+}
+
 #[test]
 fn starship_preset_missing_errors() {
     let env = setup_env();
@@ -97,6 +154,40 @@ default_preset = "missing"
         .stderr(predicates::str::contains("failed to apply starship preset"));
 }
 
+#[test]
+fn starship_preset_warns_and_skips_when_starship_is_not_installed() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("theme-a")).unwrap();
+
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        r#"[starship]
+default_mode = "preset"
+default_preset = "tokyo-night"
+"#,
+    );
+    let starship_config = env.home.join(".config/starship.toml");
+    fs::write(&starship_config, "format = \"$all\"\n").unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "theme-a"]);
+    cmd.assert().success().stderr(predicates::str::contains(
+        "starship not installed; install it to use presets",
+    ));
+
+    let name = fs::read_to_string(omarchy_dir(&env.home).join("current/theme.name")).unwrap();
+    assert_eq!(name.trim(), "theme-a");
+    assert_eq!(
+        fs::read_to_string(&starship_config).unwrap(),
+        "format = \"$all\"\n"
+    );
+}
+
 #[test]
 fn starship_named_missing_errors() {
     let env = setup_env();
@@ -119,6 +210,7 @@ default_name = "missing"
     cmd.args(["set", "theme-a"]);
     cmd.assert()
         .failure()
+        .code(6)
         .stderr(predicates::str::contains("starship theme not found"));
 }
 
@@ -300,3 +392,30 @@ default_name = "omarchy-default"
         .failure()
         .stderr(predicates::str::contains("starship theme not found"));
 }
+
+#[test]
+fn starship_list_prints_presets_and_named_themes() {
+    let env = setup_env();
+    write_script(
+        &env.bin.join("starship"),
+        "#!/usr/bin/env bash\n\nprintf 'pastel-powerline\\ntokyo-night\\n'\n",
+    );
+    let themes_dir = env.home.join(".config/starship-themes");
+    fs::create_dir_all(&themes_dir).unwrap();
+    fs::write(themes_dir.join("zeta.toml"), "").unwrap();
+    fs::write(themes_dir.join("alpha.toml"), "").unwrap();
+    fs::write(themes_dir.join("notes.txt"), "").unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["starship", "--list"]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let stdout = String::from_utf8(output).unwrap();
+
+    let presets_at = stdout.find("Presets").unwrap();
+    let named_at = stdout.find("Named themes").unwrap();
+    assert!(presets_at < named_at);
+    let (presets, named) = stdout.split_at(named_at);
+    assert!(presets.contains("  pastel-powerline\n  tokyo-night\n"));
+    assert!(named.contains("  alpha\n  zeta\n"));
+    assert!(!named.contains("notes"));
+}
@@ -0,0 +1,70 @@
+mod support;
+
+use std::fs;
+use support::*;
+
+#[test]
+fn backup_then_restore_returns_starship_and_presets() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("alpha")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "alpha"]);
+    cmd.assert().success();
+
+    let starship = env.home.join(".config/starship.toml");
+    let presets = env.home.join(".config/theme-manager/presets.toml");
+    fs::write(&starship, "format = \"before\"\n").unwrap();
+    fs::write(&presets, "[preset.Daily]\ntheme = \"alpha\"\n").unwrap();
+
+    let backups = env.temp.path().join("backups");
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("backup").arg(&backups);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let backup_dir = std::path::PathBuf::from(String::from_utf8(output).unwrap().trim());
+    assert!(backup_dir.starts_with(&backups));
+    assert!(backup_dir.join("current/theme").is_dir());
+
+    fs::write(&starship, "format = \"after\"\n").unwrap();
+    fs::remove_file(&presets).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("restore").arg(&backup_dir);
+    cmd.write_stdin("n\n");
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("restore cancelled"));
+    assert!(!presets.exists());
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("restore").arg(&backup_dir);
+    cmd.write_stdin("y\n");
+    cmd.assert().success();
+
+    assert_eq!(
+        fs::read_to_string(&starship).unwrap(),
+        "format = \"before\"\n"
+    );
+    assert_eq!(
+        fs::read_to_string(&presets).unwrap(),
+        "[preset.Daily]\ntheme = \"alpha\"\n"
+    );
+    assert_eq!(
+        fs::read_to_string(omarchy_dir(&env.home).join("current/theme.name")).unwrap(),
+        "alpha"
+    );
+}
+
+#[test]
+fn restore_rejects_non_backup_dir() {
+    let env = setup_env();
+    let empty = env.temp.path().join("empty");
+    fs::create_dir_all(&empty).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["restore", "--yes"]).arg(&empty);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("not a theme-manager backup"));
+}
@@ -21,6 +21,71 @@ fn list_titles() {
         .stdout(predicates::str::contains("Gruvbox"));
 }
 
+#[test]
+fn list_current_marks_active_theme() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("tokyo-night")).unwrap();
+    fs::create_dir_all(themes.join("gruvbox")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "tokyo-night"]);
+    cmd.assert().success();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["list", "--current"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::diff("Gruvbox\nTokyo Night *\n"));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("list");
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::diff("Gruvbox\nTokyo Night\n"));
+}
+
+#[test]
+fn list_filters_and_prints_tags() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    for name in ["amber-crt", "synthwave", "nord", "plain"] {
+        fs::create_dir_all(themes.join(name)).unwrap();
+    }
+    fs::write(
+        themes.join("amber-crt/theme.toml"),
+        "tags = [\"retro\", \"dark\"]\n",
+    )
+    .unwrap();
+    fs::write(
+        themes.join("synthwave/theme.toml"),
+        "variant = \"dark\"\ntags = [\"Retro\"]\n",
+    )
+    .unwrap();
+    fs::write(themes.join("nord/theme.toml"), "variant = \"dark\"\n").unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["list", "--tag", "retro"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::diff("Amber CRT\nSynthwave\n"));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["list", "--tags"]);
+    cmd.assert().success().stdout(predicates::str::diff(
+        "Amber CRT [retro, dark]\nNord\nPlain\nSynthwave [Retro]\n",
+    ));
+
+    let current_dir = omarchy_dir(&env.home).join("current");
+    fs::create_dir_all(current_dir.join("theme")).unwrap();
+    fs::write(current_dir.join("theme.name"), "amber-crt").unwrap();
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["next", "--tag", "retro"]);
+    cmd.assert().success();
+    let name = fs::read_to_string(current_dir.join("theme.name")).unwrap();
+    assert_eq!(name.trim(), "synthwave");
+}
+
 #[test]
 fn set_updates_current_theme_dir() {
     let env = setup_env();
@@ -37,6 +102,31 @@ fn set_updates_current_theme_dir() {
     assert_eq!(name.trim(), "tokyo-night");
 }
 
+#[test]
+fn set_reads_theme_name_from_stdin() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("noir")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "--stdin"]).write_stdin("  noir\n");
+    cmd.assert().success();
+    let name = fs::read_to_string(omarchy_dir(&env.home).join("current/theme.name")).unwrap();
+    assert_eq!(name.trim(), "noir");
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "--stdin"]).write_stdin("\n");
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("no theme name on stdin"));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "noir", "--stdin"]).write_stdin("noir\n");
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("cannot be used with"));
+}
+
 #[test]
 fn set_generates_templates_from_colors() {
     let env = setup_env();
@@ -94,6 +184,154 @@ fn current_errors_when_missing() {
         .stderr(predicates::str::contains("current theme not set"));
 }
 
+#[test]
+fn current_falls_back_to_symlink_target_and_writes_theme_name() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("Tokyo Night")).unwrap();
+    let current_dir = omarchy_dir(&env.home).join("current");
+    fs::create_dir_all(&current_dir).unwrap();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(themes.join("Tokyo Night"), current_dir.join("theme")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("current");
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::diff("Tokyo Night\n"));
+
+    let name = fs::read_to_string(current_dir.join("theme.name")).unwrap();
+    assert_eq!(name, "tokyo-night");
+}
+
+#[test]
+fn current_path_prints_resolved_theme_dir() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("tokyo-night")).unwrap();
+    let current_dir = omarchy_dir(&env.home).join("current");
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["current", "--path"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("current theme not set"));
+
+    fs::create_dir_all(&current_dir).unwrap();
+    std::os::unix::fs::symlink("../themes/tokyo-night", current_dir.join("theme")).unwrap();
+    let expected = themes.join("tokyo-night").canonicalize().unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["current", "--path"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::diff(format!("{}\n", expected.display())));
+}
+
+#[test]
+fn gen_theme_scaffolds_a_theme_that_applies_cleanly() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let omarchy = omarchy_dir(&env.home);
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["gen-theme", "My Theme"]);
+    cmd.assert()
+        .success()
+        .stdout(
+            predicates::str::contains("created my-theme").and(predicates::str::contains(
+                "replace backgrounds/1-placeholder.png with your own wallpaper",
+            )),
+        );
+
+    let theme = omarchy.join("themes/my-theme");
+    for rel in [
+        "theme.toml",
+        "hyprland.conf",
+        "waybar-theme/config.jsonc",
+        "waybar-theme/style.css",
+        "walker-theme/style.css",
+        "hyprlock-theme/hyprlock.conf",
+        "starship.yaml",
+    ] {
+        assert!(theme.join(rel).is_file(), "missing {rel}");
+    }
+    let placeholder = fs::read(theme.join("backgrounds/1-placeholder.png")).unwrap();
+    assert!(placeholder.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["palette", "my-theme"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("accent").and(predicates::str::contains("#89b4fa")));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args([
+        "set",
+        "my-theme",
+        "--only",
+        "waybar,walker,hyprlock,starship",
+    ]);
+    cmd.assert().success();
+    assert!(omarchy
+        .join("current/theme/waybar-theme/style.css")
+        .is_file());
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["gen-theme", "my-theme"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("theme already exists: my-theme"));
+}
+
+#[test]
+fn lint_reports_each_broken_component() {
+    let env = setup_env();
+    let theme = omarchy_dir(&env.home).join("themes/broken");
+    fs::create_dir_all(theme.join("waybar-theme")).unwrap();
+    fs::create_dir_all(theme.join("walker-theme")).unwrap();
+    fs::create_dir_all(theme.join("hyprlock-theme")).unwrap();
+    fs::write(theme.join("waybar-theme/config.jsonc"), "{ \"layer\": ").unwrap();
+    fs::write(theme.join("waybar-theme/style.css"), "* {}\n").unwrap();
+    fs::write(
+        theme.join("hyprlock-theme/hyprlock.conf"),
+        "# wallpaper\npath = /home/alice/Pictures/lock.png\n",
+    )
+    .unwrap();
+    fs::write(theme.join("starship.yaml"), "- just\n- a list\n").unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["lint", "broken"]);
+    cmd.assert()
+        .failure()
+        .stdout(
+            predicates::str::contains("FAIL waybar: config.jsonc does not parse")
+                .and(predicates::str::contains("ok   waybar: style.css present"))
+                .and(predicates::str::contains("FAIL walker: style.css present"))
+                .and(predicates::str::contains(
+                    "FAIL hyprlock: hyprlock.conf line 2 uses an absolute host path",
+                ))
+                .and(predicates::str::contains(
+                    "FAIL starship: starship.yaml does not parse",
+                ))
+                .and(predicates::str::contains("FAIL backgrounds: no images")),
+        )
+        .stderr(predicates::str::contains(
+            "lint failed for broken (5 problems)",
+        ));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["gen-theme", "fresh"]);
+    cmd.assert().success();
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["lint", "fresh"]);
+    cmd.assert().success().stdout(
+        predicates::str::contains("lint passed for fresh")
+            .and(predicates::str::contains("FAIL").not()),
+    );
+}
+
 #[test]
 fn next_cycles() {
     let env = setup_env();
@@ -112,6 +350,105 @@ fn next_cycles() {
     assert_eq!(name.trim(), "bravo");
 }
 
+#[test]
+fn next_no_wrap_stops_at_last_theme() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("alpha")).unwrap();
+    fs::create_dir_all(themes.join("bravo")).unwrap();
+    let current_dir = omarchy_dir(&env.home).join("current");
+    fs::create_dir_all(current_dir.join("theme")).unwrap();
+    fs::write(current_dir.join("theme.name"), "alpha").unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["next", "--no-wrap"]);
+    cmd.assert().success();
+    let name = fs::read_to_string(current_dir.join("theme.name")).unwrap();
+    assert_eq!(name.trim(), "bravo");
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["next", "--no-wrap"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("already at last theme"));
+    let name = fs::read_to_string(current_dir.join("theme.name")).unwrap();
+    assert_eq!(name.trim(), "bravo");
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("next");
+    cmd.assert().success();
+    let name = fs::read_to_string(current_dir.join("theme.name")).unwrap();
+    assert_eq!(name.trim(), "alpha");
+}
+
+#[test]
+fn next_count_applies_only_the_final_theme_by_default() {
+    let env = setup_env();
+    let omarchy = omarchy_dir(&env.home);
+    for name in ["alpha", "bravo", "charlie", "delta"] {
+        fs::create_dir_all(omarchy.join("themes").join(name)).unwrap();
+    }
+    let current_dir = omarchy.join("current");
+    fs::create_dir_all(current_dir.join("theme")).unwrap();
+    fs::write(current_dir.join("theme.name"), "bravo").unwrap();
+    let log = env.temp.path().join("hook.log");
+    fs::create_dir_all(omarchy.join("hooks")).unwrap();
+    write_logging_stub(&omarchy.join("hooks/theme-set"), &log);
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_HOOK");
+    cmd.args(["next", "--count", "3"]);
+    cmd.assert().success();
+    let name = fs::read_to_string(current_dir.join("theme.name")).unwrap();
+    assert_eq!(name.trim(), "alpha");
+    assert_eq!(fs::read_to_string(&log).unwrap(), "alpha\n");
+
+    fs::remove_file(&log).unwrap();
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_HOOK");
+    cmd.args(["next", "--count", "2", "--each-delay", "10ms"]);
+    cmd.assert().success();
+    assert_eq!(fs::read_to_string(&log).unwrap(), "bravo\ncharlie\n");
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["next", "--count", "2", "--each-delay", "soon"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("invalid --each-delay: soon"));
+}
+
+#[test]
+fn next_filter_cycles_within_matching_themes() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    for name in ["gruvbox-dark", "gruvbox-light", "nord", "tokyo-night"] {
+        fs::create_dir_all(themes.join(name)).unwrap();
+    }
+    let current_dir = omarchy_dir(&env.home).join("current");
+    fs::create_dir_all(current_dir.join("theme")).unwrap();
+    fs::write(current_dir.join("theme.name"), "nord").unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["next", "--filter", "GRUVBOX"]);
+    cmd.assert().success();
+    let name = fs::read_to_string(current_dir.join("theme.name")).unwrap();
+    assert_eq!(name.trim(), "gruvbox-dark");
+
+    for expected in ["gruvbox-light", "gruvbox-dark"] {
+        let mut cmd = cmd_with_env(&env);
+        cmd.args(["next", "--filter", "gruvbox"]);
+        cmd.assert().success();
+        let name = fs::read_to_string(current_dir.join("theme.name")).unwrap();
+        assert_eq!(name.trim(), expected);
+    }
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["next", "--filter", "solarized"]);
+    cmd.assert().failure().stderr(predicates::str::contains(
+        "no themes match filter: solarized",
+    ));
+}
+
 #[test]
 fn bg_next_runs_command() {
     let env = setup_env();
@@ -136,6 +473,88 @@ fn bg_next_runs_command() {
     assert!(marker.exists());
 }
 
+#[test]
+fn set_only_applies_listed_components() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let theme_dir = omarchy_dir(&env.home).join("themes/noir");
+    fs::create_dir_all(theme_dir.join("waybar-theme")).unwrap();
+    fs::write(theme_dir.join("waybar-theme/config.jsonc"), "{}").unwrap();
+    fs::write(theme_dir.join("waybar-theme/style.css"), "* {}").unwrap();
+    fs::create_dir_all(theme_dir.join("walker-theme")).unwrap();
+    fs::write(theme_dir.join("walker-theme/style.css"), "walker").unwrap();
+    fs::write(theme_dir.join("starship.toml"), "format = \"$all\"\n").unwrap();
+    let walker_config = env.home.join(".config/walker/config.toml");
+    fs::create_dir_all(walker_config.parent().unwrap()).unwrap();
+    fs::write(&walker_config, "theme = \"old\"\n").unwrap();
+    let user_cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&user_cfg_dir).unwrap();
+    write_toml(
+        &user_cfg_dir.join("config.toml"),
+        "[walker]\ndefault_mode = \"auto\"\n",
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "noir", "--only", "waybar,starship"]);
+    cmd.assert().success();
+
+    assert!(env.home.join(".config/waybar/config.jsonc").exists());
+    assert_eq!(
+        fs::read_to_string(env.home.join(".config/starship.toml")).unwrap(),
+        "format = \"$all\"\n"
+    );
+    assert_eq!(
+        fs::read_to_string(&walker_config).unwrap(),
+        "theme = \"old\"\n"
+    );
+}
+
+#[test]
+fn set_timings_reports_each_phase_to_stderr() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    fs::create_dir_all(omarchy_dir(&env.home).join("themes/noir")).unwrap();
+
+    let mut cmd = cmd_with_apps_env(&env);
+    cmd.args(["set", "noir", "--timings"]);
+    cmd.assert().success().stderr(
+        predicates::str::contains("timings for noir")
+            .and(predicates::str::is_match(r"(?m)^  staging +\d+\.\dms$").unwrap())
+            .and(predicates::str::contains("  waybar "))
+            .and(predicates::str::contains("  walker "))
+            .and(predicates::str::contains("  hyprlock "))
+            .and(predicates::str::contains("  starship "))
+            .and(predicates::str::contains("  background "))
+            .and(predicates::str::contains("  reload "))
+            .and(predicates::str::contains("  total ")),
+    );
+
+    let mut cmd = cmd_with_apps_env(&env);
+    cmd.args(["set", "noir"]);
+    cmd.assert()
+        .success()
+        .stderr(predicates::str::contains("timings").not());
+}
+
+#[test]
+fn set_only_rejects_bad_tokens_and_excluded_flags() {
+    let env = setup_env();
+    fs::create_dir_all(omarchy_dir(&env.home).join("themes/noir")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "noir", "--only", "waybar,gtk"]);
+    cmd.assert().failure().stderr(predicates::str::contains(
+        "unknown component in --only: gtk",
+    ));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "noir", "--only", "waybar", "-k", "minimal"]);
+    cmd.assert().failure().stderr(predicates::str::contains(
+        "--walker conflicts with --only: walker is not listed",
+    ));
+}
+
 #[test]
 fn set_rejects_broken_symlink() {
     let env = setup_env();
@@ -150,41 +569,131 @@ fn set_rejects_broken_symlink() {
     cmd.args(["set", "broken"]);
     cmd.assert()
         .failure()
+        .code(5)
         .stderr(predicates::str::contains("theme symlink is broken"));
 }
 
 #[test]
-fn set_rejects_empty_waybar_name() {
+fn symlinked_theme_resolves_the_same_for_palette_presets_and_set() {
     let env = setup_env();
     let themes = omarchy_dir(&env.home).join("themes");
-    fs::create_dir_all(themes.join("theme-a")).unwrap();
+    fs::create_dir_all(&themes).unwrap();
+    let external = env.temp.path().join("external").join("linked");
+    fs::create_dir_all(&external).unwrap();
+    fs::write(
+        external.join("hyprland.conf"),
+        "$accent = rgb(122,162,247)\n",
+    )
+    .unwrap();
+    fs::write(external.join("starship.toml"), "format = \"$all\"\n").unwrap();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink("../../../../external/linked", themes.join("linked")).unwrap();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(themes.join("missing-target"), themes.join("dangling")).unwrap();
 
     let mut cmd = cmd_with_env(&env);
-    cmd.args(["set", "theme-a", "--waybar="]);
+    cmd.args(["palette", "linked"]);
     cmd.assert()
-        .failure()
-        .stderr(predicates::str::contains("--waybar requires a name"));
+        .success()
+        .stdout(predicates::str::contains("accent  #7aa2f7"));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args([
+        "preset",
+        "save",
+        "Linked",
+        "--theme",
+        "linked",
+        "--starship",
+        "theme",
+    ]);
+    cmd.assert().success();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "linked"]);
+    cmd.assert().success();
+    let current = omarchy_dir(&env.home).join("current/theme");
+    assert!(current.join("hyprland.conf").is_file());
+
+    for args in [["palette", "dangling"], ["set", "dangling"]] {
+        let mut cmd = cmd_with_env(&env);
+        cmd.args(args);
+        cmd.assert()
+            .failure()
+            .code(5)
+            .stderr(predicates::str::contains("theme symlink is broken"));
+    }
 }
 
 #[test]
-fn set_rejects_empty_hyprlock_name() {
+fn set_replaces_symlinked_current_theme_with_directory() {
     let env = setup_env();
     let themes = omarchy_dir(&env.home).join("themes");
-    fs::create_dir_all(themes.join("theme-a")).unwrap();
+    fs::create_dir_all(themes.join("old")).unwrap();
+    fs::write(themes.join("old/marker.txt"), "old").unwrap();
+    fs::create_dir_all(themes.join("noir")).unwrap();
+    fs::write(themes.join("noir/marker.txt"), "noir").unwrap();
+
+    let current_dir = omarchy_dir(&env.home).join("current");
+    fs::create_dir_all(&current_dir).unwrap();
+    let current_theme = current_dir.join("theme");
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(themes.join("old"), &current_theme).unwrap();
 
     let mut cmd = cmd_with_env(&env);
-    cmd.args(["set", "theme-a", "--hyprlock="]);
+    cmd.args(["set", "noir"]);
+    cmd.assert().success();
+
+    let meta = fs::symlink_metadata(&current_theme).unwrap();
+    assert!(meta.file_type().is_dir());
+    assert_eq!(
+        fs::read_to_string(current_theme.join("marker.txt")).unwrap(),
+        "noir"
+    );
+    assert_eq!(
+        fs::read_to_string(themes.join("old/marker.txt")).unwrap(),
+        "old"
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("current");
     cmd.assert()
-        .failure()
-        .stderr(predicates::str::contains("--hyprlock requires a name"));
+        .success()
+        .stdout(predicates::str::diff("Noir\n"));
 }
 
 #[test]
-fn set_succeeds_when_mako_is_missing_but_swaync_is_available() {
+fn set_rejects_empty_waybar_name() {
     let env = setup_env();
     let themes = omarchy_dir(&env.home).join("themes");
     fs::create_dir_all(themes.join("theme-a")).unwrap();
-    add_omarchy_stubs(&env.bin);
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "theme-a", "--waybar="]);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("--waybar requires a name"));
+}
+
+#[test]
+fn set_rejects_empty_hyprlock_name() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("theme-a")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "theme-a", "--hyprlock="]);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("--hyprlock requires a name"));
+}
+
+#[test]
+fn set_succeeds_when_mako_is_missing_but_swaync_is_available() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("theme-a")).unwrap();
+    add_omarchy_stubs(&env.bin);
 
     let marker = env.temp.path().join("swaync-reloaded");
     write_script(
@@ -268,3 +777,1163 @@ fn set_silences_mako_fallback_when_only_makoctl_is_installed() {
         .stderr(predicates::str::contains("Object does not exist").not());
     assert!(mako_marker.exists());
 }
+
+#[test]
+fn current_variant_reads_theme_metadata() {
+    let env = setup_env();
+    let current_dir = omarchy_dir(&env.home).join("current");
+    fs::create_dir_all(current_dir.join("theme")).unwrap();
+    fs::write(current_dir.join("theme.name"), "latte").unwrap();
+    fs::write(
+        current_dir.join("theme/theme.toml"),
+        "variant = \"light\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["current", "--variant"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::diff("light\n"));
+}
+
+#[test]
+fn current_variant_falls_back_to_background_luminance() {
+    let env = setup_env();
+    let current_dir = omarchy_dir(&env.home).join("current");
+    fs::create_dir_all(current_dir.join("theme")).unwrap();
+    fs::write(current_dir.join("theme.name"), "midnight").unwrap();
+
+    let image_path = env.temp.path().join("dark.png");
+    image::RgbImage::from_pixel(8, 8, image::Rgb([12, 14, 20]))
+        .save(&image_path)
+        .unwrap();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&image_path, current_dir.join("background")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["current", "--variant"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::diff("dark\n"));
+}
+
+#[test]
+fn current_variant_is_unknown_without_metadata_or_background() {
+    let env = setup_env();
+    let current_dir = omarchy_dir(&env.home).join("current");
+    fs::create_dir_all(current_dir.join("theme")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["current", "--variant"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::diff("unknown\n"));
+}
+
+#[test]
+fn reapply_reruns_components_without_switching_theme() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let current_dir = omarchy_dir(&env.home).join("current");
+    let waybar_theme = current_dir.join("theme/waybar-theme");
+    fs::create_dir_all(&waybar_theme).unwrap();
+    fs::write(waybar_theme.join("config.jsonc"), "cfg").unwrap();
+    fs::write(waybar_theme.join("style.css"), "style").unwrap();
+    fs::write(current_dir.join("theme.name"), "alpha").unwrap();
+
+    let marker = env.temp.path().join("waybar-restarted");
+    write_script(
+        &env.bin.join("omarchy-restart-waybar"),
+        &format!("#!/usr/bin/env bash\n\necho ok > {}\n", marker.display()),
+    );
+
+    let mut cmd = cmd_with_apps_env(&env);
+    cmd.args(["reapply", "-w"]);
+    cmd.assert().success();
+
+    let applied = env.home.join(".config/waybar/config.jsonc");
+    assert!(fs::symlink_metadata(&applied)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert!(marker.exists());
+    let name = fs::read_to_string(current_dir.join("theme.name")).unwrap();
+    assert_eq!(name.trim(), "alpha");
+}
+
+#[test]
+fn set_rejects_concurrent_run_while_lock_is_held() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("theme-a")).unwrap();
+
+    let lock_path = env.temp.path().join("theme-manager.lock");
+    let held = fs::File::create(&lock_path).unwrap();
+    held.lock().unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env("THEME_MANAGER_LOCK_FILE", &lock_path);
+    cmd.args(["set", "theme-a"]);
+    cmd.assert().failure().stderr(predicates::str::contains(
+        "another theme operation is in progress",
+    ));
+    assert!(!omarchy_dir(&env.home).join("current/theme").exists());
+
+    held.unlock().unwrap();
+    let mut cmd = cmd_with_env(&env);
+    cmd.env("THEME_MANAGER_LOCK_FILE", &lock_path);
+    cmd.args(["set", "theme-a"]);
+    cmd.assert().success();
+}
+
+#[test]
+fn bg_next_awww_overrides_reach_transition_command() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let current_dir = omarchy_dir(&env.home).join("current");
+    let backgrounds = current_dir.join("theme/backgrounds");
+    fs::create_dir_all(&backgrounds).unwrap();
+    fs::write(backgrounds.join("one.png"), "img").unwrap();
+    fs::write(current_dir.join("theme.name"), "alpha").unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env("THEME_MANAGER_AWWW_TRANSITION", "1");
+    cmd.args([
+        "bg-next",
+        "--debug-awww",
+        "--transition-type",
+        "fade",
+        "--duration",
+        "1.5",
+        "--angle",
+        "30",
+        "--fps",
+        "144",
+    ]);
+    cmd.assert().success().stderr(
+        predicates::str::contains("--transition-type fade")
+            .and(predicates::str::contains("--transition-duration 1.5"))
+            .and(predicates::str::is_match("--transition-angle=-?30 ").unwrap())
+            .and(predicates::str::contains("--transition-fps 144")),
+    );
+}
+
+#[test]
+fn no_transition_runs_plain_awww_img() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let omarchy = omarchy_dir(&env.home);
+    let backgrounds = omarchy.join("themes/alpha/backgrounds");
+    fs::create_dir_all(&backgrounds).unwrap();
+    fs::write(backgrounds.join("one.png"), "img").unwrap();
+    fs::write(backgrounds.join("two.png"), "img").unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.env("THEME_MANAGER_AWWW_TRANSITION", "1");
+    cmd.args(["--debug-awww", "set", "alpha", "--no-transition"]);
+    cmd.assert().success().stderr(
+        predicates::str::contains("awww cmd: awww img ")
+            .and(predicates::str::contains("--transition-type none"))
+            .and(predicates::str::contains("--transition-duration").not()),
+    );
+    let background = omarchy.join("current/background");
+    assert!(fs::read_link(&background).unwrap().ends_with("one.png"));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env("THEME_MANAGER_AWWW_TRANSITION", "1");
+    cmd.args(["--debug-awww", "bg-next", "--no-transition"]);
+    cmd.assert().success().stderr(
+        predicates::str::contains("awww cmd: awww img ")
+            .and(predicates::str::contains("--transition-type none"))
+            .and(predicates::str::contains("--transition-duration").not()),
+    );
+    assert!(fs::read_link(&background).unwrap().ends_with("two.png"));
+}
+
+#[test]
+fn seed_repeats_random_background_and_transition_angle() {
+    let run = |seed: &str| {
+        let env = setup_env();
+        add_omarchy_stubs(&env.bin);
+        let backgrounds = omarchy_dir(&env.home).join("themes/alpha/backgrounds");
+        fs::create_dir_all(&backgrounds).unwrap();
+        for name in ["a.png", "b.png", "c.png", "d.png", "e.png", "f.png"] {
+            fs::write(backgrounds.join(name), "img").unwrap();
+        }
+
+        let mut cmd = cmd_with_env(&env);
+        cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+        cmd.env("THEME_MANAGER_AWWW_TRANSITION", "1");
+        cmd.args([
+            "--seed",
+            seed,
+            "--print-awww-cmd",
+            "set",
+            "alpha",
+            "--background",
+            "random",
+        ]);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let stdout = String::from_utf8(output).unwrap();
+        stdout
+            .lines()
+            .find_map(|line| line.split_once("/backgrounds/"))
+            .map(|(_, image_and_flags)| image_and_flags.to_string())
+            .expect("awww command printed")
+    };
+
+    let first = run("42");
+    assert!(first.contains("--transition-angle="), "{first}");
+    assert_eq!(run("42"), first);
+}
+
+#[test]
+fn verbose_awww_log_captures_command_and_output() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let current_dir = omarchy_dir(&env.home).join("current");
+    let backgrounds = current_dir.join("theme/backgrounds");
+    fs::create_dir_all(&backgrounds).unwrap();
+    fs::write(backgrounds.join("one.png"), "img").unwrap();
+    fs::write(current_dir.join("theme.name"), "alpha").unwrap();
+    write_script(
+        &env.bin.join("awww"),
+        "#!/usr/bin/env bash\n\necho \"awww says hi\"\necho \"awww warns\" >&2\nexit 0\n",
+    );
+    let log = env.temp.path().join("logs/awww.log");
+
+    for _ in 0..2 {
+        let mut cmd = cmd_with_env(&env);
+        cmd.env("THEME_MANAGER_AWWW_TRANSITION", "1");
+        cmd.args(["bg-next", "--verbose-awww-log"]);
+        cmd.arg(&log);
+        cmd.args(["--transition-type", "fade"]);
+        cmd.assert().success();
+    }
+
+    let content = fs::read_to_string(&log).unwrap();
+    assert_eq!(content.matches("awww img ").count(), 2);
+    assert!(content.contains("--transition-type fade"));
+    assert!(content.contains("--- stdout ---\nawww says hi\n"));
+    assert!(content.contains("--- stderr ---\nawww warns\n"));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env("THEME_MANAGER_AWWW_TRANSITION", "1");
+    cmd.args(["bg-next", "--rotate-awww-log", "--verbose-awww-log"]);
+    cmd.arg(&log);
+    cmd.assert().success();
+
+    let rotated = env.temp.path().join("logs/awww.log.1");
+    assert_eq!(fs::read_to_string(rotated).unwrap(), content);
+    let fresh = fs::read_to_string(&log).unwrap();
+    assert_eq!(fresh.matches("awww img ").count(), 1);
+}
+
+#[test]
+fn bg_next_prefers_theme_image_when_names_collide() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let omarchy = omarchy_dir(&env.home);
+    let theme_backgrounds = omarchy.join("current/theme/backgrounds");
+    let user_backgrounds = omarchy.join("backgrounds/alpha");
+    fs::create_dir_all(&theme_backgrounds).unwrap();
+    fs::create_dir_all(&user_backgrounds).unwrap();
+    fs::write(theme_backgrounds.join("a.png"), "theme").unwrap();
+    fs::write(theme_backgrounds.join("b.png"), "theme").unwrap();
+    fs::write(user_backgrounds.join("b.png"), "user").unwrap();
+    fs::write(user_backgrounds.join("c.png"), "user").unwrap();
+    fs::write(omarchy.join("current/theme.name"), "alpha").unwrap();
+
+    let background = omarchy.join("current/background");
+    let mut seen = Vec::new();
+    for _ in 0..4 {
+        let mut cmd = cmd_with_env(&env);
+        cmd.env("THEME_MANAGER_AWWW_TRANSITION", "1");
+        cmd.arg("bg-next");
+        cmd.assert().success();
+        let target = fs::read_link(&background).unwrap();
+        let owner = if target.starts_with(&user_backgrounds) {
+            "user"
+        } else {
+            "theme"
+        };
+        let name = target.file_name().unwrap().to_string_lossy().to_string();
+        seen.push(format!("{owner}/{name}"));
+    }
+    assert_eq!(
+        seen,
+        ["theme/a.png", "theme/b.png", "user/c.png", "theme/a.png"]
+    );
+}
+
+#[test]
+fn set_background_pins_an_image_or_keeps_the_current_link() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let omarchy = omarchy_dir(&env.home);
+    let backgrounds = omarchy.join("themes/alpha/backgrounds");
+    fs::create_dir_all(&backgrounds).unwrap();
+    for name in ["a.png", "b.png", "c.png"] {
+        fs::write(backgrounds.join(name), "img").unwrap();
+    }
+    let background = omarchy.join("current/background");
+    let set = |choice: &str| {
+        let mut cmd = cmd_with_env(&env);
+        cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+        cmd.env("THEME_MANAGER_AWWW_TRANSITION", "1");
+        cmd.args(["set", "alpha", "--background", choice]);
+        cmd.assert()
+    };
+    let linked = || {
+        let target = fs::read_link(&background).unwrap();
+        target.file_name().unwrap().to_string_lossy().to_string()
+    };
+
+    set("c.png").success();
+    assert_eq!(linked(), "c.png");
+
+    set("b").success();
+    assert_eq!(linked(), "b.png");
+
+    set("keep")
+        .success()
+        .stderr(predicates::str::contains("background unchanged"));
+    assert_eq!(linked(), "b.png");
+
+    set("next").success();
+    assert_eq!(linked(), "c.png");
+
+    set("missing.png")
+        .failure()
+        .stderr(predicates::str::contains(
+            "background not found for alpha: missing.png",
+        ));
+    assert_eq!(linked(), "c.png");
+
+    // Switching themes replaces current/theme, so `keep` re-points the link at alpha's copy.
+    let beta = omarchy.join("themes/beta/backgrounds");
+    fs::create_dir_all(&beta).unwrap();
+    fs::write(beta.join("z.png"), "img").unwrap();
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.env("THEME_MANAGER_AWWW_TRANSITION", "1");
+    cmd.args(["set", "beta", "--background", "keep"]);
+    cmd.assert().success();
+    assert_eq!(
+        fs::read_link(&background).unwrap(),
+        backgrounds.join("c.png")
+    );
+    assert!(background.is_file());
+}
+
+#[test]
+fn bg_list_prints_backgrounds_in_cycle_order() {
+    let env = setup_env();
+    let omarchy = omarchy_dir(&env.home);
+    let theme_backgrounds = omarchy.join("themes/alpha/backgrounds");
+    let user_backgrounds = omarchy.join("backgrounds/alpha");
+    fs::create_dir_all(&theme_backgrounds).unwrap();
+    fs::create_dir_all(&user_backgrounds).unwrap();
+    fs::write(theme_backgrounds.join("b.png"), "theme").unwrap();
+    fs::write(theme_backgrounds.join("notes.txt"), "skip").unwrap();
+    fs::write(user_backgrounds.join("a.jpg"), "user").unwrap();
+    fs::write(user_backgrounds.join("b.png"), "user").unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "alpha"]);
+    cmd.assert().success();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(
+        user_backgrounds.join("a.jpg"),
+        omarchy.join("current/background"),
+    )
+    .unwrap();
+
+    let current_theme = omarchy.join("current/theme/backgrounds");
+    let expected = format!(
+        "{} *\n{}\n",
+        user_backgrounds.join("a.jpg").display(),
+        current_theme.join("b.png").display()
+    );
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("bg-list");
+    cmd.assert().success().stdout(expected);
+
+    let expected = format!(
+        "{} *\n{}\n",
+        user_backgrounds.join("a.jpg").display(),
+        theme_backgrounds.join("b.png").display()
+    );
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["bg-list", "--theme", "alpha"]);
+    cmd.assert().success().stdout(expected);
+
+    // Another theme's image with the same file name is not the current background.
+    let beta_backgrounds = omarchy.join("themes/beta/backgrounds");
+    fs::create_dir_all(&beta_backgrounds).unwrap();
+    fs::write(beta_backgrounds.join("b.png"), "beta").unwrap();
+    fs::remove_file(omarchy.join("current/background")).unwrap();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(
+        current_theme.join("b.png"),
+        omarchy.join("current/background"),
+    )
+    .unwrap();
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["bg-list", "--theme", "beta"]);
+    cmd.assert()
+        .success()
+        .stdout(format!("{}\n", beta_backgrounds.join("b.png").display()));
+}
+
+#[test]
+fn bg_next_rejects_non_positive_duration() {
+    let env = setup_env();
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["bg-next", "--duration", "0"]);
+    cmd.assert().failure().stderr(predicates::str::contains(
+        "--duration must be greater than 0",
+    ));
+}
+
+#[test]
+fn set_print_awww_cmd_prints_without_running_awww() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    let backgrounds = themes.join("theme-a/backgrounds");
+    fs::create_dir_all(&backgrounds).unwrap();
+    fs::write(backgrounds.join("one.png"), "img").unwrap();
+
+    let marker = env.temp.path().join("awww-ran");
+    write_script(
+        &env.bin.join("awww"),
+        &format!("#!/usr/bin/env bash\n\necho ok > {}\n", marker.display()),
+    );
+
+    let mut cmd = cmd_with_apps_env(&env);
+    cmd.env("THEME_MANAGER_AWWW_TRANSITION", "1");
+    cmd.args([
+        "set",
+        "theme-a",
+        "--print-awww-cmd",
+        "--transition-type",
+        "wipe",
+    ]);
+    cmd.assert().success().stdout(
+        predicates::str::contains("awww img ")
+            .and(predicates::str::contains(
+                "current/theme/backgrounds/one.png",
+            ))
+            .and(predicates::str::contains("--transition-type wipe"))
+            .and(predicates::str::contains("--transition-duration 2.4")),
+    );
+    assert!(!marker.exists());
+}
+
+#[test]
+fn bg_next_runs_awww_once_per_configured_output() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let current_dir = omarchy_dir(&env.home).join("current");
+    let backgrounds = current_dir.join("theme/backgrounds");
+    fs::create_dir_all(&backgrounds).unwrap();
+    fs::write(backgrounds.join("one.png"), "img").unwrap();
+    fs::write(current_dir.join("theme.name"), "alpha").unwrap();
+
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        r#"[behavior]
+awww_outputs = ["DP-1", "HDMI-A-1"]
+"#,
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env("THEME_MANAGER_AWWW_TRANSITION", "1");
+    cmd.args(["bg-next", "--print-awww-cmd"]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let lines: Vec<String> = String::from_utf8_lossy(&output)
+        .lines()
+        .filter(|line| line.starts_with("awww img "))
+        .map(|line| line.to_string())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].ends_with("--outputs DP-1"));
+    assert!(lines[1].ends_with("--outputs HDMI-A-1"));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env("THEME_MANAGER_AWWW_TRANSITION", "1");
+    cmd.args(["bg-next", "--print-awww-cmd", "--output", "eDP-1"]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let lines: Vec<String> = String::from_utf8_lossy(&output)
+        .lines()
+        .filter(|line| line.starts_with("awww img "))
+        .map(|line| line.to_string())
+        .collect();
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].ends_with("--outputs eDP-1"));
+}
+
+#[test]
+fn set_skips_transition_when_background_is_unchanged() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    let backgrounds = themes.join("theme-a/backgrounds");
+    fs::create_dir_all(&backgrounds).unwrap();
+    fs::write(backgrounds.join("only.png"), "img").unwrap();
+
+    let log = env.temp.path().join("awww-calls");
+    write_script(
+        &env.bin.join("awww"),
+        &format!("#!/usr/bin/env bash\n\necho \"$@\" >> {}\n", log.display()),
+    );
+    let awww_calls = || {
+        fs::read_to_string(&log)
+            .map(|content| content.lines().count())
+            .unwrap_or(0)
+    };
+
+    for _ in 0..2 {
+        let mut cmd = cmd_with_apps_env(&env);
+        cmd.env("THEME_MANAGER_AWWW_TRANSITION", "1");
+        cmd.args(["set", "theme-a"]);
+        cmd.assert().success();
+    }
+    assert_eq!(awww_calls(), 1);
+
+    let mut cmd = cmd_with_apps_env(&env);
+    cmd.env("THEME_MANAGER_AWWW_TRANSITION", "1");
+    cmd.args(["set", "theme-a", "--force-bg"]);
+    cmd.assert().success();
+    assert_eq!(awww_calls(), 2);
+
+    // Same file name, different theme: the link target is unchanged but the image is not.
+    let other = themes.join("theme-b/backgrounds");
+    fs::create_dir_all(&other).unwrap();
+    fs::write(other.join("only.png"), "other").unwrap();
+    let mut cmd = cmd_with_apps_env(&env);
+    cmd.env("THEME_MANAGER_AWWW_TRANSITION", "1");
+    cmd.args(["set", "theme-b"]);
+    cmd.assert()
+        .success()
+        .stderr(predicates::str::contains("background unchanged").not());
+    assert_eq!(awww_calls(), 3);
+}
+
+#[test]
+fn set_records_history_and_last_reverts() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("alpha")).unwrap();
+    fs::create_dir_all(themes.join("bravo")).unwrap();
+
+    for theme in ["alpha", "bravo"] {
+        let mut cmd = cmd_with_env(&env);
+        cmd.args(["set", theme]);
+        cmd.assert().success();
+    }
+
+    let history_path = env.home.join(".config/theme-manager/history.jsonl");
+    let history = fs::read_to_string(&history_path).unwrap();
+    let lines: Vec<&str> = history.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].contains("\"theme\":\"alpha\""));
+    assert!(lines[1].contains("\"theme\":\"bravo\""));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("history");
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("alpha"))
+        .stdout(predicates::str::contains("bravo"));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "--last"]);
+    cmd.assert().success();
+
+    let name = fs::read_to_string(omarchy_dir(&env.home).join("current/theme.name")).unwrap();
+    assert_eq!(name.trim(), "alpha");
+}
+
+#[test]
+fn set_last_errors_without_history() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("alpha")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "--last"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("no previous theme in history"));
+}
+
+#[test]
+fn undo_restores_previous_theme_and_components() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("alpha")).unwrap();
+    fs::create_dir_all(themes.join("bravo")).unwrap();
+
+    for name in ["shared", "other"] {
+        let waybar_theme = env.home.join(".config/waybar/themes").join(name);
+        fs::create_dir_all(&waybar_theme).unwrap();
+        fs::write(waybar_theme.join("config.jsonc"), name).unwrap();
+        fs::write(waybar_theme.join("style.css"), name).unwrap();
+    }
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "alpha", "-w", "shared"]);
+    cmd.assert().success();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["next", "-w", "other"]);
+    cmd.assert().success();
+    let waybar_config = env.home.join(".config/waybar/config.jsonc");
+    assert_eq!(fs::read_to_string(&waybar_config).unwrap(), "other");
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.arg("undo");
+    cmd.assert().success();
+
+    let name = fs::read_to_string(omarchy_dir(&env.home).join("current/theme.name")).unwrap();
+    assert_eq!(name.trim(), "alpha");
+    assert_eq!(fs::read_to_string(&waybar_config).unwrap(), "shared");
+}
+
+#[test]
+fn undo_errors_with_single_history_entry() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("alpha")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "alpha"]);
+    cmd.assert().success();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("undo");
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("nothing to undo"));
+}
+
+#[test]
+fn set_runs_on_apply_cmd_after_switch() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("alpha")).unwrap();
+
+    let marker = env.temp.path().join("on-apply-marker");
+    write_script(
+        &env.bin.join("chime"),
+        &format!(
+            "#!/usr/bin/env bash\necho \"$1 $THEME_MANAGER_THEME\" > \"{}\"\n",
+            marker.to_string_lossy()
+        ),
+    );
+
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        r#"[behavior]
+on_apply_cmd = "chime \"$1\""
+"#,
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "alpha"]);
+    cmd.assert().success();
+
+    // The command is spawned detached, so give it a moment to finish.
+    let mut content = String::new();
+    for _ in 0..50 {
+        content = fs::read_to_string(&marker).unwrap_or_default();
+        if !content.is_empty() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    assert_eq!(content.trim(), "alpha alpha");
+}
+
+#[test]
+fn set_sends_notification_when_enabled() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("tokyo-night")).unwrap();
+    fs::write(themes.join("tokyo-night/preview.png"), b"png").unwrap();
+
+    let log = env.temp.path().join("notify.log");
+    write_logging_stub(&env.bin.join("notify-send"), &log);
+
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        r#"[behavior]
+notify_on_change = true
+"#,
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "tokyo-night"]);
+    cmd.assert().success();
+
+    let logged = fs::read_to_string(&log).unwrap();
+    assert!(logged.contains("Theme Tokyo Night"));
+    assert!(logged.contains("--icon="));
+    assert!(logged.contains("preview.png"));
+
+    fs::remove_file(&log).unwrap();
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "tokyo-night", "-q"]);
+    cmd.assert().success();
+    assert!(!log.exists());
+}
+
+#[test]
+fn preview_prints_image_path_and_colors_when_piped() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    let theme_dir = themes.join("noir");
+    fs::create_dir_all(&theme_dir).unwrap();
+    fs::write(theme_dir.join("preview.png"), b"png").unwrap();
+    fs::write(
+        theme_dir.join("hyprland.conf"),
+        "# Noir\ngeneral {\n    col.active_border = rgb(c0caf5)\n    gaps_in = 5\n}\n",
+    )
+    .unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["preview", "noir"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("Noir"))
+        .stdout(predicates::str::contains("Preview: "))
+        .stdout(predicates::str::contains("noir/preview.png"))
+        .stdout(predicates::str::contains("col.active_border = rgb(c0caf5)"))
+        .stdout(predicates::str::contains("gaps_in").not());
+}
+
+#[test]
+fn preview_errors_for_unknown_theme() {
+    let env = setup_env();
+    fs::create_dir_all(omarchy_dir(&env.home).join("themes")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["preview", "missing"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("theme not found: missing"));
+}
+
+#[test]
+fn palette_prints_hex_values_for_color_variables() {
+    let env = setup_env();
+    let theme_dir = omarchy_dir(&env.home).join("themes").join("noir");
+    fs::create_dir_all(&theme_dir).unwrap();
+    fs::write(
+        theme_dir.join("hyprland.conf"),
+        "$activeBorder = rgba(122, 162, 247, 1.0) # accent\n\
+         $background = rgb(26,27,38)\n\
+         $inactive = 0x80414868\n\
+         $gaps = 5\n\
+         general {\n    col.active_border = $activeBorder\n}\n",
+    )
+    .unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["palette", "noir"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("activeBorder  #7aa2f7"))
+        .stdout(predicates::str::contains("background    #1a1b26"))
+        .stdout(predicates::str::contains("inactive      #41486880"))
+        .stdout(predicates::str::contains("gaps").not())
+        .stdout(predicates::str::contains("\x1b[").not());
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["--color", "always", "palette", "noir"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("\x1b[48;2;122;162;247m"));
+}
+
+#[test]
+fn palette_errors_when_theme_has_no_colors() {
+    let env = setup_env();
+    let theme_dir = omarchy_dir(&env.home).join("themes").join("plain");
+    fs::create_dir_all(&theme_dir).unwrap();
+    fs::write(theme_dir.join("hyprland.conf"), "general {\n}\n").unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["palette", "plain"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("no color variables found"));
+}
+
+#[test]
+fn reload_selective_flags_only_restart_their_component() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let logs_dir = env.temp.path().join("logs");
+    fs::create_dir_all(&logs_dir).unwrap();
+    let stubs = [
+        ("waybar", "omarchy-restart-waybar"),
+        ("walker", "omarchy-restart-walker"),
+        ("hyprlock", "omarchy-restart-hyprlock"),
+    ];
+    for (name, stub) in stubs {
+        write_logging_stub(&env.bin.join(stub), &logs_dir.join(name));
+    }
+    write_logging_stub(
+        &env.bin.join("omarchy-theme-set-gnome"),
+        &logs_dir.join("setters"),
+    );
+
+    for (flag, expected) in [
+        ("--waybar", "waybar"),
+        ("--walker", "walker"),
+        ("--hyprlock", "hyprlock"),
+    ] {
+        for (name, _) in stubs {
+            let _ = fs::remove_file(logs_dir.join(name));
+        }
+        let mut cmd = cmd_with_env(&env);
+        cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+        cmd.args(["reload", flag]);
+        cmd.assert().success();
+        for (name, _) in stubs {
+            assert_eq!(
+                logs_dir.join(name).exists(),
+                name == expected,
+                "reload {flag} restarted {name}"
+            );
+        }
+    }
+    assert!(!logs_dir.join("setters").exists());
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["reload"]);
+    cmd.assert().success();
+    for (name, _) in stubs {
+        assert!(logs_dir.join(name).exists());
+    }
+    assert!(logs_dir.join("setters").exists());
+}
+
+#[test]
+fn set_wait_polls_pgrep_for_waybar() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("theme-a")).unwrap();
+    let log = env.temp.path().join("pgrep.log");
+    write_script(
+        &env.bin.join("pgrep"),
+        &format!(
+            "#!/usr/bin/env bash\n\nprintf '%s\\n' \"$*\" >> \"{}\"\n[ \"$2\" = \"waybar\" ] && echo 4242\nexit 0\n",
+            log.display()
+        ),
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "theme-a", "--wait"]);
+    cmd.assert()
+        .success()
+        .stderr(predicates::str::contains("waybar not running").not());
+
+    let logged = fs::read_to_string(&log).unwrap();
+    assert!(logged.lines().any(|line| line == "-x waybar"));
+}
+
+#[test]
+fn set_wait_warns_but_succeeds_on_timeout() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("theme-a")).unwrap();
+    write_script(&env.bin.join("pgrep"), "#!/usr/bin/env bash\n\nexit 1\n");
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        "[behavior]\nrestart_wait_ms = 200\n",
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "theme-a", "--wait"]);
+    cmd.assert()
+        .success()
+        .stderr(predicates::str::contains("waybar not running after 200ms"));
+}
+
+#[test]
+fn set_unknown_theme_exits_with_not_found_code() {
+    let env = setup_env();
+    fs::create_dir_all(omarchy_dir(&env.home).join("themes/theme-a")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "missing-theme"]);
+    cmd.assert()
+        .failure()
+        .code(2)
+        .stderr(predicates::str::contains("theme not found: missing-theme"));
+}
+
+#[test]
+fn hung_helper_is_killed_after_command_timeout() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("theme-a")).unwrap();
+    write_script(
+        &env.bin.join("omarchy-restart-walker"),
+        "#!/usr/bin/env bash\n\nexec sleep 30\n",
+    );
+    let log = env.temp.path().join("setter.log");
+    write_logging_stub(&env.bin.join("omarchy-theme-set-gnome"), &log);
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        "[behavior]\ncommand_timeout_ms = 300\n",
+    );
+
+    let started = std::time::Instant::now();
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "theme-a"]);
+    cmd.assert().success().stderr(predicates::str::contains(
+        "omarchy-restart-walker timed out after 300ms",
+    ));
+
+    assert!(started.elapsed() < std::time::Duration::from_secs(10));
+    assert!(log.exists());
+}
+
+#[test]
+fn parallel_setters_run_concurrently() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("theme-a")).unwrap();
+    let log = env.temp.path().join("setters.log");
+    for setter in ["gnome", "browser", "vscode", "obsidian"] {
+        write_script(
+            &env.bin.join(format!("omarchy-theme-set-{setter}")),
+            &format!(
+                "#!/usr/bin/env bash\n\nsleep 0.6\necho {setter} >> \"{}\"\n",
+                log.display()
+            ),
+        );
+    }
+
+    let started = std::time::Instant::now();
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["--parallel-setters", "set", "theme-a"]);
+    cmd.assert().success();
+
+    // Run one after another the four setters would take at least 2.4s.
+    assert!(started.elapsed() < std::time::Duration::from_millis(1800));
+    assert_eq!(fs::read_to_string(&log).unwrap().lines().count(), 4);
+}
+
+#[test]
+fn extra_bin_dirs_make_custom_setters_resolvable() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    fs::remove_file(env.bin.join("omarchy-theme-set-vscode")).unwrap();
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("theme-a")).unwrap();
+    let extra_bin = env.home.join("my-bin");
+    fs::create_dir_all(&extra_bin).unwrap();
+    let marker = env.temp.path().join("custom-setter-ran");
+    write_script(
+        &extra_bin.join("omarchy-theme-set-vscode"),
+        &format!("#!/usr/bin/env bash\n\ntouch \"{}\"\n", marker.display()),
+    );
+    let user_cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&user_cfg_dir).unwrap();
+    write_toml(
+        &user_cfg_dir.join("config.toml"),
+        "[paths]\nextra_bin_dirs = [\"~/my-bin\"]\n",
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("print-config");
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains(format!(
+            "EXTRA_BIN_DIRS={}",
+            extra_bin.display()
+        )));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "theme-a"]);
+    cmd.assert().success();
+    assert!(marker.exists());
+}
+
+#[test]
+fn set_runs_theme_local_hook_when_enabled() {
+    let env = setup_env();
+    let theme_dir = omarchy_dir(&env.home).join("themes/theme-a");
+    let marker = env.temp.path().join("theme-hook-ran");
+    let hook = theme_dir.join("hooks/theme-set");
+    fs::create_dir_all(hook.parent().unwrap()).unwrap();
+    write_script(
+        &hook,
+        &format!(
+            "#!/usr/bin/env bash\n\necho \"$1\" > \"{}\"\n",
+            marker.display()
+        ),
+    );
+    let user_cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&user_cfg_dir).unwrap();
+    write_toml(
+        &user_cfg_dir.join("config.toml"),
+        "[behavior]\nrun_theme_hooks = true\n",
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_HOOK");
+    cmd.args(["set", "theme-a"]);
+    cmd.assert().success();
+    assert_eq!(fs::read_to_string(&marker).unwrap().trim(), "theme-a");
+
+    fs::remove_file(&marker).unwrap();
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "theme-a"]);
+    cmd.assert().success();
+    assert!(!marker.exists());
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_HOOK");
+    cmd.args(["--no-hook", "set", "theme-a"]);
+    cmd.assert().success();
+    assert!(!marker.exists());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&hook, fs::Permissions::from_mode(0o644)).unwrap();
+        let mut cmd = cmd_with_env(&env);
+        cmd.env_remove("THEME_MANAGER_SKIP_HOOK");
+        cmd.args(["set", "theme-a"]);
+        cmd.assert().success().stderr(predicates::str::contains(
+            "theme hook that is not executable",
+        ));
+        assert!(!marker.exists());
+    }
+}
+
+#[test]
+fn set_finds_theme_in_second_root_dir() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    let extra = env.home.join("extra-themes");
+    fs::create_dir_all(themes.join("nord")).unwrap();
+    fs::write(themes.join("nord/marker"), "primary").unwrap();
+    fs::create_dir_all(extra.join("nord")).unwrap();
+    fs::write(extra.join("nord/marker"), "extra").unwrap();
+    fs::create_dir_all(extra.join("solarized")).unwrap();
+    fs::write(extra.join("solarized/marker"), "extra").unwrap();
+    let user_cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&user_cfg_dir).unwrap();
+    write_toml(
+        &user_cfg_dir.join("config.toml"),
+        "[paths]\ntheme_root_dirs = [\"~/extra-themes\"]\n",
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.arg("list");
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::diff("Nord\nSolarized\n"));
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "solarized"]);
+    cmd.assert().success();
+    let current = omarchy_dir(&env.home).join("current");
+    assert_eq!(
+        fs::read_to_string(current.join("theme/marker")).unwrap(),
+        "extra"
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "nord"]);
+    cmd.assert().success();
+    assert_eq!(
+        fs::read_to_string(current.join("theme/marker")).unwrap(),
+        "primary"
+    );
+}
+
+#[test]
+fn theme_root_dirs_env_accepts_colon_separated_paths() {
+    let env = setup_env();
+    let first = env.home.join("first");
+    let second = env.home.join("second");
+    fs::create_dir_all(first.join("nord")).unwrap();
+    fs::create_dir_all(second.join("solarized")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env(
+        "THEME_ROOT_DIRS",
+        format!("{}:{}", first.display(), second.display()),
+    );
+    cmd.args(["set", "solarized"]);
+    cmd.assert().success();
+    let name = fs::read_to_string(omarchy_dir(&env.home).join("current/theme.name")).unwrap();
+    assert_eq!(name.trim(), "solarized");
+}
+
+#[test]
+fn duplicate_theme_names_across_roots_are_listed_once() {
+    let env = setup_env();
+    let themes = omarchy_dir(&env.home).join("themes");
+    let extra = env.home.join("extra-themes");
+    let external = env.home.join("dev/bravo");
+    fs::create_dir_all(themes.join("alpha")).unwrap();
+    fs::create_dir_all(&external).unwrap();
+    std::os::unix::fs::symlink(&external, themes.join("bravo")).unwrap();
+    fs::create_dir_all(extra.join("bravo")).unwrap();
+    fs::create_dir_all(extra.join("charlie")).unwrap();
+    let current_dir = omarchy_dir(&env.home).join("current");
+    fs::create_dir_all(current_dir.join("theme")).unwrap();
+    fs::write(current_dir.join("theme.name"), "bravo").unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env("THEME_ROOT_DIRS", &extra).arg("list");
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::diff("Alpha\nBravo\nCharlie\n"));
+
+    for expected in ["charlie", "alpha", "bravo"] {
+        let mut cmd = cmd_with_env(&env);
+        cmd.env("THEME_ROOT_DIRS", &extra).arg("next");
+        cmd.assert().success();
+        let name = fs::read_to_string(current_dir.join("theme.name")).unwrap();
+        assert_eq!(name.trim(), expected);
+    }
+}
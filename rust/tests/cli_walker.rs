@@ -1,5 +1,6 @@
 mod support;
 
+use predicates::prelude::PredicateBooleanExt;
 use std::fs;
 use support::*;
 
@@ -212,6 +213,54 @@ fn walker_standalone_command() {
     assert!(marker.exists());
 }
 
+#[test]
+fn walker_restart_cmd_replaces_omarchy_helper() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("theme-a")).unwrap();
+    let walker_theme = env.home.join(".config/walker/themes/minimal");
+    fs::create_dir_all(&walker_theme).unwrap();
+    fs::write(walker_theme.join("style.css"), "minimal-style").unwrap();
+    fs::write(
+        env.home.join(".config/walker/config.toml"),
+        "theme = \"old\"\n",
+    )
+    .unwrap();
+
+    let omarchy_log = env.temp.path().join("omarchy-walker.log");
+    write_logging_stub(&env.bin.join("omarchy-restart-walker"), &omarchy_log);
+    let custom_log = env.temp.path().join("custom-walker.log");
+    write_logging_stub(&env.bin.join("my-walker-restart"), &custom_log);
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        "[walker]\nrestart_cmd = \"my-walker-restart --now 'two words'\"\n",
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "theme-a"]);
+    cmd.assert().success();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["walker", "minimal"]);
+    cmd.assert().success();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.env("WALKER_RESTART_CMD", "my-walker-restart --from-env");
+    cmd.args(["reload", "--walker"]);
+    cmd.assert().success();
+
+    assert_eq!(
+        fs::read_to_string(&custom_log).unwrap(),
+        "--now two words\n--from-env\n"
+    );
+    assert!(!omarchy_log.exists());
+}
+
 #[test]
 fn walker_none_skips_theme() {
     let env = setup_env();
@@ -302,6 +351,39 @@ fn next_walker_auto_flag_uses_theme_walker() {
     assert!(content.contains("theme = \"theme-manager-auto\""));
 }
 
+#[test]
+fn set_walker_auto_default_uses_theme_walker() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("bravo/walker-theme")).unwrap();
+    fs::write(themes.join("bravo/walker-theme/style.css"), "style").unwrap();
+
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        r#"[walker]
+default_mode = "auto"
+"#,
+    );
+
+    let walker_dir = env.home.join(".config/walker");
+    fs::create_dir_all(walker_dir.join("themes")).unwrap();
+    fs::write(walker_dir.join("config.toml"), "theme = \"old\"\n").unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "bravo"]);
+    cmd.assert().success();
+
+    let content = fs::read_to_string(walker_dir.join("config.toml")).unwrap();
+    assert!(content.contains("theme = \"theme-manager-auto\""));
+    assert!(walker_dir
+        .join("themes/theme-manager-auto/style.css")
+        .exists());
+}
+
 #[test]
 fn walker_links_omarchy_default_theme_when_missing() {
     let env = setup_env();
@@ -455,3 +537,23 @@ default_name = "omarchy-default"
     let link_path = walker_dir.join("themes/omarchy-default");
     assert!(!link_path.exists());
 }
+
+#[test]
+fn walker_list_prints_modes_and_named_themes() {
+    let env = setup_env();
+    let themes_dir = env.home.join(".config/walker/themes");
+    for name in ["zeta", "alpha"] {
+        let dir = themes_dir.join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("style.css"), "").unwrap();
+    }
+    fs::create_dir_all(themes_dir.join("incomplete")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["walker", "--list"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("Modes:\n  auto\n  none\n"))
+        .stdout(predicates::str::contains("  alpha\n  zeta\n"))
+        .stdout(predicates::str::contains("incomplete").not());
+}
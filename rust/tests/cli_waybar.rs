@@ -1,5 +1,6 @@
 mod support;
 
+use predicates::prelude::PredicateBooleanExt;
 use std::fs;
 use std::path::Path;
 use support::*;
@@ -80,6 +81,96 @@ default_mode = "auto"
         .is_symlink());
 }
 
+#[test]
+fn waybar_apply_mode_flag_overrides_config_for_one_run() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("theme-a")).unwrap();
+    let waybar_theme = env.home.join(".config/waybar/themes/shared");
+    fs::create_dir_all(&waybar_theme).unwrap();
+    fs::write(waybar_theme.join("config.jsonc"), "cfg").unwrap();
+    fs::write(waybar_theme.join("style.css"), "style").unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "theme-a"]);
+    cmd.assert().success();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["waybar", "shared", "--apply-mode", "copy"]);
+    cmd.assert().success();
+
+    let waybar_dir = env.home.join(".config/waybar");
+    for name in ["config.jsonc", "style.css"] {
+        let meta = fs::symlink_metadata(waybar_dir.join(name)).unwrap();
+        assert!(meta.file_type().is_file());
+    }
+    assert_eq!(
+        fs::read_to_string(waybar_dir.join("config.jsonc")).unwrap(),
+        "cfg"
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["waybar", "shared"]);
+    cmd.assert().success();
+    assert_is_symlink(&waybar_dir.join("config.jsonc"));
+}
+
+#[test]
+fn waybar_copy_mode_carries_style_imports() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    let theme_dir = themes.join("theme-a/waybar-theme");
+    fs::create_dir_all(&theme_dir).unwrap();
+    fs::write(theme_dir.join("config.jsonc"), "cfg").unwrap();
+    fs::write(theme_dir.join("colors.css"), "@define-color fg #fff;").unwrap();
+    fs::write(themes.join("theme-a/shared.css"), "* {}").unwrap();
+    fs::write(
+        theme_dir.join("style.css"),
+        "@import \"colors.css\";\n@import url('../shared.css');\n@import \"../omarchy/current/theme/waybar.css\";\n",
+    )
+    .unwrap();
+
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        r#"[waybar]
+apply_mode = "copy"
+"#,
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "theme-a", "-w"]);
+    cmd.assert().success();
+
+    let waybar_dir = env.home.join(".config/waybar");
+    assert_eq!(
+        fs::read_to_string(waybar_dir.join("colors.css")).unwrap(),
+        "@define-color fg #fff;"
+    );
+    let style = fs::read_to_string(waybar_dir.join("style.css")).unwrap();
+    assert!(style.contains("@import \"colors.css\";"));
+    let shared = fs::canonicalize(themes.join("theme-a/shared.css")).unwrap();
+    assert!(style.contains(&format!("url('{}')", shared.to_string_lossy())));
+    assert!(style.contains("@import \"../omarchy/current/theme/waybar.css\";"));
+
+    // The copied import is recorded, so switching away removes it.
+    let other = themes.join("theme-b/waybar-theme");
+    fs::create_dir_all(&other).unwrap();
+    fs::write(other.join("config.jsonc"), "cfg").unwrap();
+    fs::write(other.join("style.css"), "style").unwrap();
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "theme-b", "-w"]);
+    cmd.assert().success();
+    assert!(!waybar_dir.join("colors.css").exists());
+}
+
 #[test]
 fn waybar_symlink_links_subdirs_and_cleans_up_on_switch() {
     let env = setup_env();
@@ -137,6 +228,350 @@ apply_mode = "symlink"
     assert!(target.ends_with("themes/alt/scripts"));
 }
 
+#[test]
+fn waybar_copy_mode_removes_copied_subdirs_on_switch() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    for (name, with_scripts) in [("theme-a", true), ("theme-b", false)] {
+        let waybar_theme = themes.join(name).join("waybar-theme");
+        fs::create_dir_all(&waybar_theme).unwrap();
+        fs::write(waybar_theme.join("config.jsonc"), "cfg").unwrap();
+        fs::write(waybar_theme.join("style.css"), "style").unwrap();
+        if with_scripts {
+            fs::create_dir_all(waybar_theme.join("scripts")).unwrap();
+            fs::write(waybar_theme.join("scripts/clock.sh"), "echo").unwrap();
+        }
+    }
+
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        r#"[waybar]
+apply_mode = "copy"
+"#,
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "theme-a", "-w"]);
+    cmd.assert().success();
+
+    let scripts = env.home.join(".config/waybar/scripts");
+    assert!(scripts.join("clock.sh").is_file());
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "theme-b", "-w"]);
+    cmd.assert().success();
+
+    assert!(!scripts.exists());
+    // Removed as a copy of ours, not moved into a backup dir (`themes/existing*`).
+    let themes_dir = env.home.join(".config/waybar/themes");
+    let backed_up: Vec<_> = fs::read_dir(&themes_dir)
+        .map(|entries| {
+            entries
+                .map(|entry| entry.unwrap().path())
+                .filter(|path| path.join("scripts").exists())
+                .collect()
+        })
+        .unwrap_or_default();
+    assert!(backed_up.is_empty(), "scripts backed up to {backed_up:?}");
+}
+
+#[test]
+fn waybar_places_loose_files_and_removes_them_on_switch() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("theme-a")).unwrap();
+
+    let waybar_root = env.home.join(".config/waybar/themes");
+    let colorful = waybar_root.join("colorful");
+    fs::create_dir_all(&colorful).unwrap();
+    fs::write(colorful.join("config.jsonc"), "cfg").unwrap();
+    fs::write(colorful.join("style.css"), "@import \"colors.css\";").unwrap();
+    fs::write(colorful.join("colors.css"), "@define-color fg #fff;").unwrap();
+    let plain = waybar_root.join("plain");
+    fs::create_dir_all(&plain).unwrap();
+    fs::write(plain.join("config.jsonc"), "cfg2").unwrap();
+    fs::write(plain.join("style.css"), "style2").unwrap();
+
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    let waybar_dir = env.home.join(".config/waybar");
+    let colors = waybar_dir.join("colors.css");
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "theme-a"]);
+    cmd.assert().success();
+
+    for mode in ["symlink", "copy", "hardlink"] {
+        write_toml(
+            &cfg_dir.join("config.toml"),
+            &format!("[waybar]\napply_mode = \"{mode}\"\ninclude_optional_files = true\n"),
+        );
+
+        let mut cmd = cmd_with_env(&env);
+        cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+        cmd.args(["waybar", "colorful"]);
+        cmd.assert().success();
+        assert_eq!(
+            fs::read_to_string(&colors).unwrap(),
+            "@define-color fg #fff;",
+            "{mode}"
+        );
+
+        let mut cmd = cmd_with_env(&env);
+        cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+        cmd.args(["waybar", "plain"]);
+        cmd.assert().success();
+        assert!(fs::symlink_metadata(&colors).is_err(), "{mode}");
+    }
+
+    // Off by default: only config.jsonc and style.css are placed.
+    fs::write(colorful.join("preview.png"), "img").unwrap();
+    fs::remove_file(cfg_dir.join("config.toml")).unwrap();
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["waybar", "colorful"]);
+    cmd.assert().success();
+    assert!(fs::symlink_metadata(&colors).is_err());
+    assert!(fs::symlink_metadata(waybar_dir.join("preview.png")).is_err());
+}
+
+#[test]
+fn waybar_hardlink_mode_shares_inodes_and_cleans_up_on_switch() {
+    use std::os::unix::fs::MetadataExt;
+
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("theme-a")).unwrap();
+
+    let waybar_root = env.home.join(".config/waybar/themes");
+    let shared = waybar_root.join("shared");
+    fs::create_dir_all(shared.join("scripts")).unwrap();
+    fs::write(shared.join("config.jsonc"), "cfg").unwrap();
+    fs::write(shared.join("style.css"), "style").unwrap();
+    fs::write(shared.join("scripts/clock.sh"), "echo").unwrap();
+
+    let alt = waybar_root.join("alt");
+    fs::create_dir_all(&alt).unwrap();
+    fs::write(alt.join("config.jsonc"), "cfg2").unwrap();
+    fs::write(alt.join("style.css"), "style2").unwrap();
+
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        r#"[waybar]
+apply_mode = "hardlink"
+"#,
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "theme-a", "-w", "shared"]);
+    cmd.assert().success();
+
+    let waybar_dir = env.home.join(".config/waybar");
+    for (applied, source) in [
+        (waybar_dir.join("config.jsonc"), shared.join("config.jsonc")),
+        (waybar_dir.join("style.css"), shared.join("style.css")),
+        (
+            waybar_dir.join("scripts/clock.sh"),
+            shared.join("scripts/clock.sh"),
+        ),
+    ] {
+        let applied_meta = fs::symlink_metadata(&applied).unwrap();
+        assert!(applied_meta.file_type().is_file());
+        assert_eq!(applied_meta.ino(), fs::metadata(&source).unwrap().ino());
+    }
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "theme-a", "-w", "alt"]);
+    cmd.assert().success();
+
+    assert!(!waybar_dir.join("scripts").exists());
+    assert!(!waybar_root.join("existing").exists());
+    assert_eq!(
+        fs::read_to_string(waybar_dir.join("config.jsonc")).unwrap(),
+        "cfg2"
+    );
+    assert_eq!(
+        fs::read_to_string(shared.join("config.jsonc")).unwrap(),
+        "cfg"
+    );
+}
+
+#[test]
+fn waybar_backs_up_user_files_hardlinked_outside_the_theme() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    fs::create_dir_all(omarchy_dir(&env.home).join("themes/theme-a")).unwrap();
+    let shared = env.home.join(".config/waybar/themes/shared");
+    fs::create_dir_all(&shared).unwrap();
+    fs::write(shared.join("config.jsonc"), "cfg").unwrap();
+    fs::write(shared.join("style.css"), "style").unwrap();
+
+    let dotfiles = env.home.join("dotfiles/waybar");
+    fs::create_dir_all(&dotfiles).unwrap();
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    let waybar_dir = env.home.join(".config/waybar");
+    let waybar_root = waybar_dir.join("themes");
+
+    for mode in ["symlink", "copy", "hardlink"] {
+        fs::write(dotfiles.join("config.jsonc"), format!("mine {mode}")).unwrap();
+        let _ = fs::remove_file(waybar_dir.join("config.jsonc"));
+        fs::hard_link(
+            dotfiles.join("config.jsonc"),
+            waybar_dir.join("config.jsonc"),
+        )
+        .unwrap();
+        for entry in fs::read_dir(&waybar_root).unwrap() {
+            let path = entry.unwrap().path();
+            if path
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with("existing")
+            {
+                fs::remove_dir_all(path).unwrap();
+            }
+        }
+        write_toml(
+            &cfg_dir.join("config.toml"),
+            &format!("[waybar]\napply_mode = \"{mode}\"\n"),
+        );
+
+        let mut cmd = cmd_with_env(&env);
+        cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+        cmd.args(["set", "theme-a", "-w", "shared"]);
+        cmd.assert().success();
+
+        assert_eq!(
+            fs::read_to_string(waybar_root.join("existing/config.jsonc")).unwrap(),
+            format!("mine {mode}"),
+            "{mode}"
+        );
+        assert_eq!(
+            fs::read_to_string(dotfiles.join("config.jsonc")).unwrap(),
+            format!("mine {mode}"),
+            "{mode}"
+        );
+    }
+}
+
+#[test]
+fn waybar_builtin_restart_kills_and_relaunches_waybar() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let log = env.temp.path().join("restart.log");
+    write_logging_stub(&env.bin.join("pkill"), &log);
+    write_logging_stub(&env.bin.join("waybar"), &log);
+    write_logging_stub(&env.bin.join("uwsm-app"), &log);
+    let helper_log = env.temp.path().join("helper.log");
+    write_logging_stub(&env.bin.join("omarchy-restart-waybar"), &helper_log);
+
+    let themes = omarchy_dir(&env.home).join("themes");
+    let theme_dir = themes.join("theme-a/waybar-theme");
+    fs::create_dir_all(&theme_dir).unwrap();
+    fs::write(theme_dir.join("config.jsonc"), "cfg").unwrap();
+    fs::write(theme_dir.join("style.css"), "style").unwrap();
+
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        r#"[waybar]
+restart_cmd = "builtin"
+"#,
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["set", "theme-a", "-w"]);
+    cmd.assert().success();
+
+    let waybar_dir = env.home.join(".config/waybar");
+    let logged = fs::read_to_string(&log).unwrap();
+    assert!(logged.contains("-x waybar"));
+    assert!(logged.contains(&format!(
+        "waybar -c {} -s {}",
+        waybar_dir.join("config.jsonc").display(),
+        waybar_dir.join("style.css").display()
+    )));
+    assert!(!helper_log.exists());
+}
+
+#[test]
+fn waybar_custom_restart_cmd_runs_through_the_shell() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let log = env.temp.path().join("restart.log");
+    write_logging_stub(&env.bin.join("my-waybar-restart"), &log);
+    let helper_log = env.temp.path().join("helper.log");
+    write_logging_stub(&env.bin.join("omarchy-restart-waybar"), &helper_log);
+
+    let cfg_dir = env.home.join(".config/theme-manager");
+    fs::create_dir_all(&cfg_dir).unwrap();
+    write_toml(
+        &cfg_dir.join("config.toml"),
+        r#"[waybar]
+restart_cmd = "my-waybar-restart 'two words' && my-waybar-restart done"
+"#,
+    );
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["reload", "--waybar"]);
+    cmd.assert().success();
+
+    assert_eq!(fs::read_to_string(&log).unwrap(), "two words\ndone\n");
+    assert!(!helper_log.exists());
+}
+
+#[test]
+fn waybar_no_restart_applies_without_restarting_until_reload() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let log = env.temp.path().join("restart.log");
+    write_logging_stub(&env.bin.join("omarchy-restart-waybar"), &log);
+
+    let themes = omarchy_dir(&env.home).join("themes");
+    fs::create_dir_all(themes.join("theme-a")).unwrap();
+    let waybar_theme = env.home.join(".config/waybar/themes/shared");
+    fs::create_dir_all(&waybar_theme).unwrap();
+    fs::write(waybar_theme.join("config.jsonc"), "cfg").unwrap();
+    fs::write(waybar_theme.join("style.css"), "style").unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "theme-a"]);
+    cmd.assert().success();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["waybar", "shared", "--no-restart"]);
+    cmd.assert().success();
+
+    let waybar_dir = env.home.join(".config/waybar");
+    assert_eq!(
+        fs::read_to_string(waybar_dir.join("config.jsonc")).unwrap(),
+        "cfg"
+    );
+    assert!(!log.exists());
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["reload"]);
+    cmd.assert().success();
+    assert!(log.exists());
+}
+
 #[test]
 fn waybar_symlink_backs_up_existing_non_symlinks() {
     let env = setup_env();
@@ -325,3 +760,113 @@ default_name = "omarchy-default"
     let target = fs::read_link(&link_path).unwrap();
     assert_eq!(target, config_waybar);
 }
+
+#[test]
+fn waybar_check_passes_for_valid_theme_without_applying() {
+    let env = setup_env();
+    let waybar_theme = env.home.join(".config/waybar/themes/good");
+    fs::create_dir_all(&waybar_theme).unwrap();
+    fs::write(
+        waybar_theme.join("config.jsonc"),
+        "// bar config\n{\n  \"layer\": \"top\", /* inline */\n  \"modules-left\": [\"clock\",],\n}\n",
+    )
+    .unwrap();
+    fs::write(waybar_theme.join("colors.css"), "@define-color fg #fff;").unwrap();
+    fs::write(
+        waybar_theme.join("style.css"),
+        "@import \"colors.css\";\n* { color: @fg; }\n",
+    )
+    .unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["waybar", "good", "--check"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("config.jsonc parses"))
+        .stdout(predicates::str::contains("@import \"colors.css\" resolves"))
+        .stdout(predicates::str::contains("check passed"));
+
+    assert!(!env.home.join(".config/waybar/config.jsonc").exists());
+    assert!(!env.home.join(".config/waybar/style.css").exists());
+}
+
+#[test]
+fn waybar_check_reports_dangling_import() {
+    let env = setup_env();
+    let waybar_theme = env.home.join(".config/waybar/themes/broken");
+    fs::create_dir_all(&waybar_theme).unwrap();
+    fs::write(waybar_theme.join("config.jsonc"), "{}").unwrap();
+    fs::write(
+        waybar_theme.join("style.css"),
+        "@import url(\"missing.css\");\n",
+    )
+    .unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["waybar", "broken", "--check"]);
+    cmd.assert()
+        .failure()
+        .stdout(predicates::str::contains(
+            "FAIL @import \"missing.css\" does not resolve",
+        ))
+        .stderr(predicates::str::contains("waybar theme check failed"));
+}
+
+#[test]
+fn waybar_list_prints_modes_and_named_themes() {
+    let env = setup_env();
+    let themes_dir = env.home.join(".config/waybar/themes");
+    for name in ["zeta", "alpha"] {
+        let dir = themes_dir.join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("config.jsonc"), "").unwrap();
+        fs::write(dir.join("style.css"), "").unwrap();
+    }
+    fs::create_dir_all(themes_dir.join("incomplete")).unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["waybar", "--list"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("Modes:\n  auto\n  none\n"))
+        .stdout(predicates::str::contains("  alpha\n  zeta\n"))
+        .stdout(predicates::str::contains("incomplete").not());
+}
+
+#[test]
+fn waybar_auto_with_theme_uses_named_theme_without_switching() {
+    let env = setup_env();
+    add_omarchy_stubs(&env.bin);
+    let themes = omarchy_dir(&env.home).join("themes");
+    let current_waybar = themes.join("theme-a/waybar-theme");
+    fs::create_dir_all(&current_waybar).unwrap();
+    fs::write(current_waybar.join("config.jsonc"), "cfg-a").unwrap();
+    fs::write(current_waybar.join("style.css"), "style-a").unwrap();
+    let noir_waybar = themes.join("noir/waybar-theme");
+    fs::create_dir_all(&noir_waybar).unwrap();
+    fs::write(noir_waybar.join("config.jsonc"), "cfg-noir").unwrap();
+    fs::write(noir_waybar.join("style.css"), "style-noir").unwrap();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["set", "theme-a"]);
+    cmd.assert().success();
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.env_remove("THEME_MANAGER_SKIP_APPS");
+    cmd.args(["waybar", "auto", "--theme", "noir"]);
+    cmd.assert().success();
+
+    let waybar_dir = env.home.join(".config/waybar");
+    assert_eq!(
+        fs::read_to_string(waybar_dir.join("config.jsonc")).unwrap(),
+        "cfg-noir"
+    );
+    let name = fs::read_to_string(omarchy_dir(&env.home).join("current/theme.name")).unwrap();
+    assert_eq!(name.trim(), "theme-a");
+
+    let mut cmd = cmd_with_env(&env);
+    cmd.args(["waybar", "auto", "--theme", "missing"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("theme not found: missing"));
+}
@@ -73,6 +73,16 @@ pub fn write_stub_ok(path: &Path) {
     write_script(path, "#!/usr/bin/env bash\n\nexit 0\n");
 }
 
+pub fn write_logging_stub(path: &Path, log: &Path) {
+    write_script(
+        path,
+        &format!(
+            "#!/usr/bin/env bash\n\nprintf '%s\\n' \"$*\" >> \"{}\"\nexit 0\n",
+            log.display()
+        ),
+    );
+}
+
 pub fn add_omarchy_stubs(bin: &Path) {
     let cmds = [
         "omarchy-restart-waybar",